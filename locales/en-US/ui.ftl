@@ -0,0 +1,847 @@
+# Interface text for Rust Raid.
+#
+# Puzzle content (room stories, hints, floor names) lives in the puzzle packs;
+# this file only covers the game's own menus, labels and messages. To translate
+# it, copy it to ~/.config/rust-raid/locales/<locale>.ftl (for example de.ftl)
+# and set `locale` in settings.toml, or rely on LANG.
+
+## Title screen
+
+title-continue = CONTINUE
+title-new-game = NEW GAME
+title-placement = SKIP AHEAD (QUIZ)
+title-level-select = SELECT FLOOR
+title-practice = PRACTICE
+title-settings = SETTINGS
+title-campaigns = CAMPAIGNS
+title-credits = CREDITS
+title-quit = QUIT
+
+## Attract demo, played when the title screen sits idle
+
+attract-prompt = Press any key
+attract-fact-title = {" "}From the codex{" "}
+attract-replay-title = {" "}{ $room }{" "}
+attract-replay-hint = Editing the starter code...
+
+## Pause menu
+
+pause-resume = Resume
+pause-restart-room = Restart Room
+pause-settings = Settings
+pause-view-keys = View Keys
+pause-save-quit = Save & Quit
+
+## How-to-play pages, shown before the first room of a first game
+
+onboarding-welcome-title = Welcome, Raider
+onboarding-welcome =
+    Each chamber holds a small Rust program that **refuses to compile** or prints the wrong thing.
+
+    Fix the code in the editor on the right so it compiles and prints *exactly* the expected output.
+
+    The Chamber pane on the left tells you the story of each room.
+onboarding-run-title = Run with F5
+onboarding-run =
+    Press **F5** (or **Ctrl+R**) to cast your solution at the compiler.
+
+    Its verdict appears in the *Compiler Whispers* pane at the bottom. Read it carefully: the compiler is stern, but it is usually right.
+onboarding-hp-title = HP: Your Life Force
+onboarding-hp =
+    You start each run with a pool of **HP**.
+
+    Every failed attempt (a compile error or wrong output) costs a little HP. If it reaches zero, the borrow checker wins and the run is over.
+onboarding-gold-title = Gold and Hints
+onboarding-gold =
+    Clearing a room earns **gold**. The fewer hints you use, the more you earn.
+
+    Stuck? Press **F1** for a hint. Hints cost HP, and each room only has a few.
+onboarding-sealed-title = Sealed Lines
+onboarding-sealed =
+    Some lines are *sealed by ancient magic* and cannot be edited. A yellow bar beside the line number marks them. They are the puzzle's fixed scaffolding: work around them.
+
+    Press **Esc** any time for the pause menu, or type `:keys` to see every shortcut.
+
+    Good luck. The dungeon awaits.
+
+## Room flow
+
+editor-title = {" "}Code Editor [F5: Run | F1: Hint | F2: Keys | :q]{" "}
+editor-title-short = {" "}Code Editor [F5: Run | F1: Hint | :q Quit]{" "}
+room-start = Fix the code. The compiler will guide you...
+press-enter-continue = Press ENTER to continue...
+coop-waiting = Waiting for a partner to join on { $address }...
+coop-joined = A second adventurer ({ $peer }) joins you. You share one editor; either of you can press F5.
+coop-partner-left = Your partner's torch fades. You continue alone.
+door-locked = The way is sealed. It will only open for someone carrying the { $item }.
+door-opens = The { $item } opens the way, and you keep it.
+door-takes-item = The door keeps the { $item } as you pass.
+level-complete-banner = === LEVEL { $level } COMPLETE! ===
+level-mastered = You've mastered the art of { $name }.
+level-mastered-perfect = You've mastered the art of { $name }. PERFECT RUN!
+level-descend = Press ENTER to descend to Level { $level }: { $name }...
+level-descend-theme = Press ENTER to descend to Level { $level }: { $name }, { $theme }...
+stats-title = LEVEL STATS
+stats-rooms-cleared = Rooms cleared:
+stats-compile-errors = Compile errors:
+stats-hints-used = Hints used:
+stats-floor-time = Floor time:
+stats-fastest-room = Fastest room:
+stats-under-par = Under par:
+stats-gold-medals = Gold medals:
+stats-silver-medals = Silver medals:
+stats-bronze-medals = Bronze medals:
+stats-gold-earned = Gold earned:
+stats-hp-remaining = HP remaining:
+inventory-heading = INVENTORY:
+inventory-empty = (empty)
+
+## Altar between rooms; $hp and $gold are the amounts of one offering
+
+altar-title = {" "}The Altar{" "}
+altar-inscription =
+    A blood-stained altar blocks the passage. The inscription reads:
+    "Gold for flesh, flesh for gold. { $rate } coins to the drop."
+altar-purse = You carry { $hp } HP and { $gold } gold.
+altar-offer-gold = Offer { $gold } gold for { $hp } HP
+altar-offer-blood = Bleed { $hp } HP for { $gold } gold
+altar-leave = Walk on
+altar-healed = The altar drinks { $gold } gold. Warmth returns: +{ $hp } HP.
+altar-bled = You open your palm over the stone: -{ $hp } HP, +{ $gold } gold.
+
+## Shop after a floor's last room, or between rooms; $gold is a price
+
+shop-title = {" "}The Wandering Merchant{" "}
+shop-welcome =
+    A hooded merchant has spread a blanket by the stairs down.
+    "Gold is no use to you below, friend. Or is it?"
+shop-purse = You carry { $gold } gold.
+shop-buy = { $item } ({ $gold } gold)
+shop-sold = { $item } (carried)
+shop-welcome-between =
+    A hooded merchant steps out of an alcove in the corridor.
+    "Potions, scrolls, a little protection. Gold first, friend."
+shop-leave = Take the stairs down
+shop-walk-on = Walk on
+shop-bought =
+    The merchant pockets { $gold } gold and hands you the { $item }.
+
+    { $description }
+shop-bought-supply =
+    The merchant pockets { $gold } gold and hands you the { $item }.
+
+    { $note }
+shop-potion = You drink it down: +{ $hp } HP.
+shop-hint-scroll = Your next hint costs no HP.
+shop-intent-scroll = Type :intent in a room with sealed lines to learn why they're sealed.
+shop-shield = Your next compile error costs no HP.
+shield-used =
+    Your shield takes the blow: no HP lost.{ $count ->
+        [0] {""}
+        [one] {" "}1 shield left.
+       *[other] {" "}{ $count } shields left.
+    }
+intent = WHY THESE LINES ARE SEALED: { $intent }
+intent-none = Nothing here is sealed for a reason worth a scroll.
+intent-no-scroll = You need an intent scroll to read why these lines are sealed. A merchant may sell one.
+carryover-gold = Your purse empties on the way down.
+carryover-items = Everything you carried is gone when you reach the bottom of the stairs.
+carryover-hp = The descent restores you to { $hp } HP.
+
+## Event deck cards; the card's own title and text come from the puzzle pack
+
+event-blessing = {" "}Blessing: { $title }{" "}
+event-curse = {" "}Curse: { $title }{" "}
+event-trade = {" "}Offer: { $title }{" "}
+event-hp = { $amount } HP
+event-gold = { $amount } gold
+event-lasting = For the rest of the run: { $effect }
+event-continue = [Enter] Continue
+event-trade-prompt = [Enter] Accept   [Esc] Decline
+event-cannot-afford = You can't afford it. [Esc] Move on
+event-takes-item = Takes your { $item }
+event-missing-item = You don't have what they want. [Esc] Move on
+
+## Errors loading a floor
+
+error-puzzles-load = Could not load puzzles from { $dir }
+error-puzzles-empty = No rooms found in { $dir }
+error-level-room-count = Level { $level } only has { $rooms } rooms
+error-level-count =
+    { $floors ->
+        [one] There is no level { $level }; this dungeon has 1 floor
+       *[other] There is no level { $level }; this dungeon has { $floors } floors
+    }
+error-level-missing = Level { $level } not implemented yet
+error-level-empty = No rooms found in level { $level }
+error-level-load = Failed to load level { $level }: { $error }
+
+## Verdicts
+
+# Sent as a bell or desktop notification when a slow run finishes
+verdict-cleared = Room cleared!
+verdict-compile-error = The compiler refused your code.
+verdict-lint-error = Clippy had complaints.
+verdict-wrong-output = Wrong output.
+verdict-rejected = The room's check failed.
+verdict-timeout = Your code ran too long and was stopped.
+verdict-failed = The run failed.
+room-cleared = *** ROOM CLEARED! ***  +{ $gold } gold  [ Press ENTER ]
+room-cleared-perfect = *** ROOM CLEARED! ***  +{ $gold } gold (perfect!)  [ Press ENTER ]
+room-time = Time { $time } (best { $best })
+room-time-new-best = Time { $time }, a new best!
+room-time-first = Time { $time }
+par-bonus = Under par: +{ $gold } gold
+medal-gold = GOLD medal!
+medal-silver = SILVER medal!
+medal-bronze = BRONZE medal!
+item-default-description = A mysterious artifact
+item-acquired =
+    {"**"} ITEM ACQUIRED: { $item } **
+    { $description }
+codex-updated =
+    {"**"} CODEX UPDATED: { $title } **
+    Type :codex to review your knowledge.
+alternative-approach = ALTERNATIVE APPROACH: { $solution }
+output-expected = Expected:
+output-expected-regex = Expected output matching this pattern:
+output-expected-contains = Expected output containing:
+output-expected-unordered = Expected these lines, in any order:
+output-got = Got:
+output-too-long = (Your output has { $got } lines, expected { $expected }—are you printing too much?)
+output-too-short = (Your output has { $got } lines, expected { $expected }—are you missing something?)
+output-compare-hint = (F3 or :diff to compare side by side)
+mismatch-at = First difference: line { $line }, column { $column }
+mismatch-expected = expected:
+mismatch-got = got:
+mismatch-no-line = (no line)
+mismatch-hint = HINT: { $hint }
+system-error = System error: { $error }
+compiling = { $spinner } Compiling and running... { $time }
+compiling-help = Esc cancels
+run-cancelled = Run cancelled. It doesn't count as an attempt.
+run-echo = The code hasn't changed since the last run, and the echo answers again:
+attempts-extra-hp = Out of attempts! That one cost { $hp } HP more.
+attempts-forced-hint =
+    Out of attempts! The room forces a hint on you (and its price):
+    { $hint }
+attempts-counterattack = Out of attempts! The guardian strikes back and your work is scattered.
+attempts-counterattack-cost = (-{ $hp } HP, code reset to the start)
+compile-errors-folded = (Notes and help folded. F8 shows everything rustc said.)
+compile-errors-expanded = (F8 folds the notes and help again.)
+compile-errors-none = No compiler errors to expand.
+hidden-test-failed = Hidden test { $number } of { $total } printed something else.
+hidden-test-args = Arguments: { $args }
+hidden-test-input =
+    Input:
+    { $input }
+hidden-test-printed =
+    Your code printed:
+    { $output }
+check-forbidden = This room forbids `{ $name }`.
+check-required = This room has to be solved with `{ $name }`.
+check-script-failed = The room's check failed ({ $status }).
+test-focus-offer = Ctrl+T pins this test's input under the editor, and F5 then runs only it.
+test-focus-on = Hidden test { $number } of { $total } is pinned under the editor. F5 runs only it, for free, and it can't clear the room. Ctrl+T unpins it.
+test-focus-off = Unpinned. F5 runs every check again.
+test-focus-none = No hidden test has failed in this room yet.
+test-focus-passed = Hidden test { $number } of { $total } passes now, so it's unpinned. F5 runs every check again.
+test-focus-failed = Hidden test { $number } of { $total } still fails.
+test-focus-title = {" "}Hidden test { $number } of { $total }: F5 runs only this, Ctrl+T unpins{" "}
+test-focus-args = Arguments: { $args }
+test-focus-stdin = Input:
+test-focus-no-input = No arguments or input
+explain-nothing = No error code to explain. Run code that fails to compile first, or name one: :explain E0382
+explain-invalid = `{ $code }` isn't an error code. They look like E0382.
+explain-asking = Asking rustc about { $code }...
+explain-busy = rustc is still looking up an error code...
+explain-failed = rustc couldn't explain { $code }: { $error }
+explain-title = { $code }, explained by rustc
+explain-others = The last run also hit { $codes }; :explain <code> shows those.
+mercy-granted = The dungeon takes pity on you. Wrong answers cost half as much HP for the rest of this room, and errors come with plain words.
+mercy-explained = In plain words:
+explain-E0004 = a `match` doesn't cover every case. Add the missing patterns, or a `_ =>` arm to catch the rest.
+explain-E0106 = a reference in a signature or struct needs a lifetime, because Rust can't tell which input it borrows from. Name one, like `'a`, or return an owned value.
+explain-E0282 = Rust can't work out a type here. Add an annotation, such as `let v: Vec<i32> = ...`, or a turbofish like `collect::<Vec<_>>()`.
+explain-E0308 = a value has a different type than the code around it expects. Compare the "expected" and "found" types and convert one to the other.
+explain-E0382 = a value was used after it moved somewhere else. Borrow it with `&` instead, or `.clone()` it if you really need two copies.
+explain-E0384 = a variable that isn't `mut` was assigned twice. Declare it with `let mut`.
+explain-E0412 = that type name doesn't exist here. Check the spelling, or bring it into scope with `use`.
+explain-E0425 = that name doesn't exist here. Check the spelling, and that it's declared before and in scope of where it's used.
+explain-E0499 = something is borrowed mutably twice at once. Finish with the first `&mut` before taking another.
+explain-E0502 = something is borrowed mutably while a shared borrow of it is still in use. Finish reading before you change it, or copy out what you need first.
+explain-E0505 = a value moved while something still borrowed it. Let the borrow end first, or clone the value.
+explain-E0507 = you tried to move a value out from behind a reference. Borrow it, `.clone()` it, or take it with `std::mem::take`.
+explain-E0515 = a function returns a reference to something it created, which is dropped when the function ends. Return the owned value instead.
+explain-E0596 = you tried to change something through a shared borrow. Declare it `mut`, or borrow it with `&mut`.
+explain-E0597 = something is dropped while a borrow of it is still in use. Make the value live longer, for example by declaring it in an outer scope.
+explain-E0599 = that type has no such method. Check the spelling, the type you actually have, and whether a trait needs importing.
+timeout-narrative = Time stands still in the chamber. Whatever you set in motion never came to rest.
+lint-failed = The door swings open a crack, then stops. The room is particular about how a thing is done, and Clippy has objections:
+run-timed-out = (Stopped after { $seconds } seconds. Is there a loop that never ends?)
+
+## Placement quiz
+
+placement-title = {" "}Placement: question { $number } of { $total } (floor { $level }){" "}
+placement-ask = What does this print?
+placement-help = ↑/↓ choose • Enter answer • Esc back to the menu
+placement-result-title = {" "}Placement{" "}
+placement-missed = Not quite. This one:
+placement-missed-answer = prints{" "}
+placement-result = You start on floor { $level }: { $name }.
+placement-result-help = Enter to begin • Esc back to the menu
+placement-started = You begin on floor { $level }: { $name }, carrying what the floors above would have given you: { $items }.
+
+## Inventory
+
+inventory-title = INVENTORY
+inventory-empty-hint = Your bag is light. Solve puzzles to collect artifacts!
+inventory-count = { $used }/{ $capacity } slots used
+inventory-drop-hint = :drop <item> leaves an item behind
+inventory-supplies = SUPPLIES (no slot needed)
+inventory-hint-scrolls =
+    { $count ->
+        [one] 1 hint scroll (a free hint)
+       *[other] { $count } hint scrolls (free hints)
+    }
+inventory-intent-scrolls =
+    { $count ->
+        [one] 1 intent scroll (:intent)
+       *[other] { $count } intent scrolls (:intent)
+    }
+inventory-shields =
+    { $count ->
+        [one] 1 shield against a compile error
+       *[other] { $count } shields against compile errors
+    }
+inventory-full = Your pack is full ({ $capacity } slots). On the way out you'll have to leave something behind.
+inventory-full-title = {" "}Your Pack Is Full{" "}
+inventory-full-body = There is no room for the { $item }. What do you leave behind?
+inventory-full-help = ↑/↓ choose • Enter leaves it • Esc keeps your pack
+inventory-drop = Leave the { $item }
+inventory-leave-new = Leave the { $item } here
+inventory-dropped = You leave the { $item } behind.
+inventory-drop-needed = A door ahead on this floor needs the { $item }.
+errors-title = Compile error { $current } of { $total } (:errors again for the next)
+errors-none = No compile errors to go to. They are marked in the editor after a run that doesn't compile.
+map-title = LEVEL { $level }: { $name }
+map-cleared = (cleared)
+map-here = (you are here)
+map-exit = The way down
+map-door-locked = Locked: needs the { $item }, which you don't have
+map-door-open = Needs the { $item }, which you carry
+map-door-open-keeps = Needs the { $item }, which you carry. The door keeps it
+inventory-no-such-item = You're not carrying anything called "{ $item }" that you can leave.
+item-kind-relic = (relic)
+item-kind-key = (key)
+item-kind-pack = (pack)
+celebration-room = ROOM CLEARED!
+celebration-level = LEVEL COMPLETE!
+
+## Help
+
+keys-help =
+    KEYBOARD SHORTCUTS
+
+    {" "}{ $keyboard }
+
+     GAME
+      F5 / Ctrl+R   Run code
+      F1            Show hint (-5 HP)
+      F3            Expected vs. got side by side
+      F4            Show/hide expected output
+      F6            Show/hide key cheat sheet
+      F7            Open the codex entry underlined in the text
+      F8            Expand/fold compiler notes and help
+      F9            Explain the last compile error in depth
+      Ctrl+T        Pin the failed hidden test so F5 runs only it (again to unpin)
+    {"  "}{ $scroll }       Scroll messages
+      Shift+PgUp/Dn Scroll the Chamber narrative
+      Esc           Pause menu
+      :             Enter command mode
+
+     COMMAND MODE
+      Tab           Complete a command's name
+      ↑/↓           Previous/next command run this session
+
+     NAVIGATION
+      ←↑↓→          Move cursor
+      Home/End      Start/end of line
+      Ctrl+←/→      Jump by word
+      Ctrl+Home/End Start/end of file
+
+     EDITING
+      Ctrl+Z        Undo
+      Ctrl+Shift+Z  Redo
+      Ctrl+Y        Yank (copy) line
+      Ctrl+P        Paste line below
+      Ctrl+D        Delete entire line
+      Ctrl+K        Delete to end of line
+      Ctrl+U        Delete to start of line
+      Ctrl+W        Delete word before cursor
+
+     FALLBACK CHORDS (for keys your terminal doesn't pass through)
+      Alt+1 … Alt+9 F1 … F9
+      Alt+Z         Redo (Ctrl+Shift+Z)
+
+     COMMANDS (:)
+      :q            Quit game (asks first)
+      :q!           Quit without asking
+      :keys         This help screen
+      :inv          Show inventory
+      :hints        Re-read this room's revealed hints; see what the rest cost
+      :feedback     Rate this room for its author
+      :intent       Read why this room's sealed lines are sealed (an intent scroll)
+      :drop <item>  Leave an item behind to free a slot
+      :map          Rooms on this floor, and which doors are locked
+      :goal         What this campaign takes to win, and how you stand
+      :achievements Badges earned across every run, and the ones to go
+      :codex        Open Codex
+      :codex <term> Open the codex at an entry, e.g. :codex lifetimes
+      :codex export Save codex to ~/rust-raid-codex.md
+      :archive      Browse the solutions that cleared rooms before
+      :campaigns    Progress in every installed pack, side by side
+      :bugreport    Write ~/rust-raid-bugreport.txt for an issue
+      :share        Upload this code to the Rust Playground
+      :doc <item>   Offline summary, e.g. :doc Vec::push
+      :errors       Jump to the next line a compile error points at
+      :explain [E…] rustc's explanation of an error code, by default the last one
+      :accept       Apply the compiler spirit's fix (after the last hint){ $oracle }
+      :sync push    Upload progress to a private GitHub gist
+      :sync pull    Download progress from that gist
+      :restart-room Reload this room's starter code
+      :summon       Swap in a different incarnation of a room you keep failing
+      :howto        How-to-play pages
+      :scratch      Open/close a free scratch pad (F5 runs it)
+      :eval <expr>  Print an expression's value, e.g. :eval "a b".split(' ').count()
+      :diff         Toggle side-by-side output
+      :expected     Show/hide expected output
+      :5            Jump to line 5
+      :+5 :-3       Jump 5 lines down, 3 lines up
+      :last-error   Jump to the last run's first compile error
+      :top :bot     Jump to start/end
+keys-protocol-enhanced = Keyboard: enhanced (kitty protocol), every binding should work
+keys-protocol-legacy = Keyboard: legacy encoding, so Ctrl+Shift+Z and some F-keys may not arrive
+keys-protocol-tmux = Keyboard: legacy encoding under tmux, so F-keys and Ctrl+Shift chords may not arrive
+keys-protocol-unknown = Keyboard: not detected
+keys-help-oracle = :oracle       AI hint for your last error (-2x HP, -25 gold)
+
+## Editing
+
+goto-line = Line { $line }/{ $lines }
+line-sealed = That line is sealed by ancient magic. It cannot be changed.
+yank-done = Yanked: `{ $line }`
+paste-empty = Nothing to paste. Use Ctrl+Y to yank a line first.
+paste-locked = Cannot paste on a locked line.
+paste-done = Pasted line below.
+diff-nothing = Nothing to compare yet. Run a solution with F5 first.
+
+## Scratch pad
+
+scratch-title = {" "}Scratch Pad [F5: Run | :scratch back to the room]{" "}
+scratch-opened = Scratch pad open. Try anything: F5 runs it for free. Your room's code is safe; :scratch brings it back.
+scratch-closed = Back to the room, your code just as you left it.
+scratch-output = The scratch pad printed:
+scratch-no-output = It compiled and ran, printing nothing.
+scratch-compile-error = The scratch pad doesn't compile (no HP lost):
+scratch-failed = Could not run the scratch pad: { $error }
+eval-result = `{ $expr }` = { $value }
+eval-compile-error = `{ $expr }` doesn't compile on its own (no HP lost):
+
+## Hints, the compiler spirit and the oracle
+
+hint = HINT: { $hint }
+hint-ask-title = Reveal a hint?
+hint-ask = Reveal hint { $number } of { $total } for { $hp } HP?
+hint-ask-left =
+    { $count ->
+        [0] This is the last hint for this room
+        [one] 1 hint left after this one
+       *[other] { $count } hints left after this one
+    }
+hint-ask-fatal = You don't have the HP to survive it
+hint-ask-scroll =
+    { $count ->
+        [one] Your hint scroll pays for it
+       *[other] A hint scroll pays for it (you have { $count })
+    }
+hints-exhausted = No more hints available. You're on your own...
+hint-index-none = This room has no hints.
+hint-index-title = {" "}Hints: { $room } ({ $revealed }/{ $total } revealed) [Esc to close]{" "}
+hint-index-help = ↑/↓ to move • ENTER reveals the next hint • Esc back to the room
+hint-index-revealed = Hint { $number }
+hint-index-next = Hint { $number }: locked, { $hp } HP to reveal (ENTER)
+hint-index-locked = Hint { $number }: locked, { $hp } HP once the ones before it are revealed
+hint-index-spirit = After the last hint, the compiler spirit offers rustc's own fix for { $hp } HP.
+feedback-title = {" "}Feedback: { $room }{" "}
+feedback-question = How did this room feel?
+feedback-too-easy = Too easy
+feedback-just-right = Just right
+feedback-too-hard = Too hard
+feedback-comment = Anything to add for the room's author? (optional)
+feedback-help = ←/→ rate • type to comment • ENTER saves • Esc cancels
+feedback-saved = Thanks! Your feedback was added to { $path }.
+feedback-failed = Could not save your feedback: { $error }
+recap-title = {" "}Floor { $level }: { $name }, recap{" "}
+recap-help = ↑/↓ to scroll • ENTER to descend
+recap-learned = What you learned
+recap-learned-none = No codex entries were found on this floor.
+recap-missed =
+    { $count ->
+        [one] One more entry was left to find here.
+       *[other] { $count } more entries were left to find here.
+    }
+recap-ahead = Ahead, Floor { $level }: { $name }
+recap-ahead-none = What lies below is still a mystery.
+spirit-summoning = Your hints are spent. You call on the compiler spirit, and it studies your code...
+spirit-line = {"  "}line { $line }: { $code }
+spirit-ask-title = Take the spirit's fix?
+spirit-ask = Pay { $hp } HP for rustc's own fix?
+spirit-offer =
+    The compiler spirit offers to guide your hand.  -{ $hp } HP
+
+    { $suggestion }
+
+    Type :accept to let it.
+spirit-waiting =
+    The compiler spirit waits, hand outstretched:
+
+    { $suggestion }
+
+    Type :accept to let it guide your hand.
+spirit-nothing = Nothing has been offered. The spirit appears once your hints run out.
+spirit-locked = The spirit's hand stops at line { $line }. It is locked; make the change elsewhere.
+spirit-applied = The spirit guides your hand. Press F5 to see if it was right.
+spirit-stale = The code has changed too much; the spirit's advice no longer fits.
+oracle-busy = The oracle is still gazing into the smoke...
+oracle-no-error = The oracle only reads compiler errors. Press F5 and bring it one.
+oracle-asking = You ask the oracle. It stares into the smoke...
+oracle-answer =
+    ORACLE (AI-generated, may be wrong)  -{ $hp } HP  -{ $gold } gold
+
+    { $hint }
+oracle-failed = The oracle is silent: { $error }
+
+## Confirmations
+
+quit-title = Quit Rust Raid?
+quit-body = Leave the dungeon? Your run is saved with:
+quit-keep-progress = Progress on Level { $level }, Room { $room }/{ $rooms }
+quit-keep-gold = { $gold } gold
+quit-keep-items =
+    { $count ->
+        [one] 1 item
+       *[other] { $count } items
+    }
+quit-keep-codex =
+    { $count ->
+        [one] 1 codex entry
+       *[other] { $count } codex entries
+    }
+quit-continue = Pick Continue on the title screen to carry on.
+restart-title = Restart Room?
+restart-body = Reload the starter code for "{ $room }"?
+restart-lose-edits = Your edits in this room will be lost
+restart-lose-hints = The room's hint counter resets
+restart-lose-hp = HP already spent is not refunded
+restart-done = The chamber shimmers and resets itself...
+summon-offer = This chamber has other incarnations. Type :summon to face a different one.
+summon-done =
+    The walls fold in on themselves and the chamber reforms, its riddle
+    written anew. Same lesson, different code.
+summon-none = This chamber has only the one incarnation.
+summon-too-soon = The chamber won't change for you yet. Fail { $attempts ->
+        [one] once more
+       *[other] { $attempts } more times
+    } first.
+summon-coop = A chamber shared with a partner can't change its shape.
+
+## Settings
+
+setting-on = ON
+setting-off = OFF
+setting-on-auto = ON (auto)
+setting-typewriter = Typewriter narration
+setting-celebrations = Victory celebrations
+setting-ascii-only = ASCII-only glyphs
+setting-cheat-sheet = Key cheat sheet panel
+setting-theme = Color theme
+setting-check-for-updates = Check for updates
+setting-telemetry = Share anonymous stats
+setting-verdict-alert = Alert on slow verdicts
+setting-autosave = Save progress
+setting-break-reminder = Break reminder
+setting-pause-clock-while-reading = Pause clock while reading
+setting-minutes = Every { $minutes } min
+setting-discord = Discord presence
+setting-sound = Sound
+setting-volume = Volume
+alert-bell = Bell
+alert-desktop = Desktop
+autosave-every-room = Every room
+autosave-every-floor = Every floor
+autosave-on-quit = On quit
+settings-save-failed = Could not save settings: { $error }
+
+## Codex, sync, docs, sharing and bug reports
+
+codex-empty = Your codex is empty. Solve puzzles to learn!
+archive-empty = No solutions archived yet. Every room you clear keeps the code that did it.
+archive-copied = Your solution to { $title } is in the scratch pad. :scratch brings the room's code back.
+codex-not-found = No codex entry matches "{ $term }" yet.
+codex-nothing-mentioned = Nothing here mentions a codex entry you've unlocked.
+codex-no-home = Could not find your home directory to write the codex.
+codex-exported = Your codex has been transcribed to { $path } ({ $count } entries).
+write-failed = Could not write { $path }: { $error }
+sync-busy = A sync is already in progress...
+sync-started = Sending a raven to GitHub...
+sync-pushed = Progress uploaded ({ $files } file(s), gist { $gist }).
+sync-pulled = Progress downloaded ({ $files } file(s), gist { $gist }).
+sync-failed = Sync failed: { $error }
+doc-ambiguous = Which one?
+doc-not-found = The archives hold nothing on `{ $query }`. The full docs: https://doc.rust-lang.org/std/
+share-busy = Already carrying your code to the Playground...
+share-started = Carrying your code to the Rust Playground...
+share-done =
+    Your code is on the Playground:
+
+    { $url }
+share-done-copied =
+    Your code is on the Playground (copied to your clipboard):
+
+    { $url }
+share-failed = Could not share: { $error }
+bugreport-written = Bug report written to { $path }. Paste it into an issue; paths and your user name have been removed.
+bugreport-failed = Could not write bug report: { $error }
+expected-shown = The room's desired outcome is etched beneath your code.
+expected-hidden = The etching fades from view.
+
+## Discord presence
+
+presence-gate = At the dungeon gate
+presence-main-menu = Main menu
+presence-floor = Floor { $floor }: { $name }
+presence-room = Room { $room }/{ $rooms }: { $title }
+
+## Key cheat sheet, as the action each key performs
+
+cheat-run-command = Run command
+cheat-cancel = Cancel
+cheat-complete = Complete
+cheat-command-history = Earlier commands
+cheat-quit = Quit
+cheat-codex = Codex
+cheat-inventory = Inventory
+cheat-map = Floor map
+cheat-hint = Hint
+cheat-compare-output = Compare output
+cheat-expected-output = Expected output
+cheat-look-up-codex = Look up in codex
+cheat-expand-errors = Expand compiler notes
+cheat-explain-error = Explain error code
+cheat-pin-test = Pin failed test
+cheat-unpin-test = Unpin test
+cheat-go-to-line-5 = Go to line 5
+cheat-start-end = Start / end
+cheat-all-shortcuts = All shortcuts
+cheat-next-room = Next room
+cheat-scroll-story = Scroll story
+cheat-back-to-editor = Back to editor
+cheat-enter-room = Enter room
+cheat-descend = Descend
+cheat-scroll-stats = Scroll stats
+cheat-choose-offering = Choose offering
+cheat-make-offering = Make offering
+cheat-leave-altar = Move on
+cheat-choose-ware = Choose ware
+cheat-buy-ware = Buy
+cheat-leave-shop = Go downstairs
+cheat-walk-on = Walk on to the next room
+cheat-accept-event = Accept / continue
+cheat-decline-event = Decline
+cheat-choose-item = Choose item
+cheat-leave-item = Leave it behind
+cheat-leave-new-item = Leave the new one
+cheat-run-code = Run code
+cheat-run-pinned-test = Run pinned test
+cheat-hint-hp = Hint (-HP)
+cheat-pause-menu = Pause menu
+cheat-command-mode = Command mode
+cheat-undo = Undo
+cheat-delete-line = Delete line
+cheat-yank-paste = Yank / paste
+cheat-scroll-messages = Scroll messages
+cheat-hide-this-panel = Hide this panel
+
+## Menus and dialogs
+
+title-help = ↑/↓ to select  •  ENTER to confirm  •  q to quit
+update-available = Rust Raid { $version } is available (you have { $current })
+pause-title = {" "}Paused [Esc: resume]{" "}
+pause-session = Session { $time }
+save-resumed = Welcome back. Your run continues where you left it, code and all.
+save-resumed-cleared = Welcome back. You had just cleared this room. Press ENTER to go on.
+save-written = Run saved to { $path }.
+save-failed = Could not save the run: { $error }
+save-nothing = There is no run in progress to save.
+save-unusable = The saved run doesn't fit these puzzles any more, so it can't be continued.
+toast-hp-lost = -{ $amount } HP
+toast-hp-gained = +{ $amount } HP
+toast-gold-spent = -{ $amount } gold
+toast-gold-gained = +{ $amount } gold
+toast-achievement = ACHIEVEMENT: { $title }
+toast-item = ITEM: { $item }
+break-title = {" "}Time for a Break{" "}
+break-body = You've been raiding for { $session } this session.
+break-advice = Stand up, stretch, and look at something far away for a minute. The dungeon will wait.
+break-paused = Timers paused · { $time }
+break-help = Enter or Esc to return to the room
+settings-title = {" "}Settings{" "}
+settings-help = ENTER toggles  •  Esc goes back
+confirm-yes = Yes
+confirm-save-quit = Save & Quit
+confirm-no = No
+onboarding-title = {" "}How to Play{" "}
+onboarding-footer = Page { $page }/{ $pages }  •  ←/→ to page  •  ENTER to continue  •  Esc to skip
+onboarding-footer-last = Page { $page }/{ $pages }  •  ←/→ to page  •  ENTER to begin  •  Esc to skip
+story-run-prompt = Press F5 to run your solution.
+story-cannot-proceed = Cannot proceed: { $error }
+
+## Credits
+
+credits-title = {" "}Credits{" "}
+credits-heading = R U S T   R A I D
+credits-version = version { $version }
+credits-build = build { $hash } ({ $profile }, { $target })
+credits-created-by = CREATED BY
+credits-puzzle-packs = BUNDLED PUZZLE PACKS
+credits-pack-by = { $name } by { $author }
+credits-pack-license = Licensed under { $license }
+credits-pack-no-license = No license given in its campaign.toml
+credits-built-with = BUILT WITH
+credits-built-with-ui = ratatui · crossterm · tui-textarea (MIT)
+credits-built-with-data = serde · toml · anyhow · tempfile · glob (MIT / Apache-2.0)
+credits-compiler = And the Rust compiler, our stern but fair dungeon master.
+credits-back = Press Esc to return
+
+## Codex
+
+codex-title = {" "}Codex [Esc to close]{" "}
+archive-title = {" "}Solution Archive ({ $count }) [Esc to close]{" "}
+archive-help = ↑/↓ to move • ENTER opens a solution • s copies it to the scratch pad • Esc to close
+archive-reading-help = ↑/↓ to scroll • s copies it to the scratch pad • ENTER or Esc back to the list
+archive-stats = Floor { $floor } · { $time } · Try { $attempts } · Hint { $hints }
+campaigns-title = {" "}Campaigns ({ $count }) [Esc to close]{" "}
+campaigns-help = Progress across every run of each installed pack • Esc to close
+campaigns-column-name = Campaign
+campaigns-column-cleared = Cleared
+campaigns-column-codex = Codex
+campaigns-column-best-run = Best run
+campaigns-column-sum-of-best = Sum of best
+campaigns-current = ▸ { $name }
+campaigns-only-one = Only this pack is installed. Packs in ~/.local/share/rust-raid/packs/ show up here too.
+
+floor-select-title = {" "}Select Floor [Esc to close]{" "}
+floor-select-help = ↑/↓ to choose • ENTER to start a new run there • Esc back
+floor-select-row = Floor { $level }: { $name }
+floor-select-cleared = { $cleared }/{ $rooms } rooms cleared
+floor-select-locked = locked until floor { $above } is cleared
+floor-select-carry = A run started below the first floor carries what the floors above would have given.
+codex-heading = ADVENTURER'S CODEX
+codex-tagline = Knowledge gained from the depths of the dungeon.
+codex-help = ↑/↓ to move • ENTER folds a floor • / to search • Esc to close
+codex-search = Search:
+codex-no-match = No entries match your search.
+codex-floor = FLOOR { $floor }: { $name } ({ $count })
+codex-example = Example:
+codex-undiscovered = { $count } more entries to discover...
+
+## Playing a room
+
+game-over =
+    OWNED
+
+    The borrow checker wins. Your HP has reached zero.
+cursor-position = Line { $line }/{ $lines }, Col { $col }
+command-help = Commands: :q :keys :inv :codex :hint | Type :? for all shortcuts
+invalid-line = Invalid line number: { $line }
+unknown-command = Unknown command: { $command }
+usage-drop = :drop <item>  Leave an item behind to free a slot
+usage-codex = :codex <term>  Open at an entry · :codex export  Save to ~/rust-raid-codex.md
+usage-sync = :sync push  Upload progress to a gist · :sync pull  Download it
+usage-doc = :doc <item>  Offline summary, e.g. :doc Vec::push
+usage-explain = :explain [code]  Explain an error code, by default the last one
+usage-eval = :eval <expr>  Print an expression's value
+usage-goto = :goto <line>  Jump to a line · :goto +5 / :goto -3  Down or up that many lines
+secret-room =
+    {"***"} SECRET ROOM ***
+
+    You stand in a room with walls of pure code.
+    Flickering runes on the floor read:
+
+    {"   "}'Made by Bradleyd Smith'
+secret-nothing = A hollow voice whispers... 'Nothing happens here.'
+goal-none = Clear the last floor and the dungeon is yours. Nothing more is asked.
+goal =
+    THE CAMPAIGN'S GOAL
+
+    { $goal }
+    ({ $condition })
+goal-standing = So far: HP { $hp }/{ $max_hp }, { $gold } gold, codex { $codex }/{ $codex_total }, secrets { $secrets }/{ $secrets_total }, { $items } items, { $hints } hints, { $errors } compile errors.
+achievements-heading = ACHIEVEMENTS: { $earned } of { $total } earned, kept across every run
+campaign-won = The campaign's goal is met: { $goal }
+campaign-lost = The last floor is cleared, but the campaign's goal is not: { $goal } The dungeon lets you go, unconquered.
+hud-room = L{ $level } Room { $room }/{ $rooms }
+hud-gold = Gold: { $gold }
+hud-hp = HP: { $hp }
+modifier-hint-hp = hints { $change }
+modifier-wrong-answer-hp = mistakes { $change }
+modifier-gold = gold { $change }
+difficulty-easy = Easy
+difficulty-normal = Normal
+difficulty-hard = Hard
+hud-room-stats = Try { $attempts } · Hint { $hints }/{ $hints_total } · { $time }
+hud-room-stats-limited = Try { $attempts }/{ $max } · Hint { $hints }/{ $hints_total } · { $time }
+ghost-cleared = Your echo just cleared { $room }
+ghost-in-room = Echo in room { $room }
+mode-command = COMMAND
+mode-edit = EDIT
+mode-coop = CO-OP
+chamber-title = {" "}The Chamber{" "}
+chamber-title-scroll = {" "}The Chamber [Shift+PgUp/PgDn]{" "}
+cheat-sheet-title = {" "}Keys [F6]{" "}
+expected-title = {" "}Expected Output [F4: hide]{" "}
+comparison-title = {" "}Expected | Got [F3: stacked view]{" "}
+comparison-expected = Expected
+comparison-got = Got
+
+# Titles of the message pane at the bottom of the room
+message-title = Compiler Whispers
+message-title-command = Command
+message-title-victory = VICTORY!
+message-title-descending = Descending...
+message-title-level-complete = LEVEL COMPLETE!
+message-title-altar = The Altar
+message-title-shop = The Merchant
+message-title-event = On the Way
+message-title-game-over = GAME OVER
+message-scroll = [{ $keys } to scroll]
+
+## Printed after the game closes
+
+victory-conquered = You have conquered the Borrow Dungeon!
+victory-bows = The borrow checker bows before your mastery.
+victory-farewell = Now go forth and write Rust without fear!
+final-stats = FINAL STATS
+final-gold = Gold Collected:
+final-hp = HP Remaining:
+final-codex = Codex Entries:
+final-items = Items:
+final-medals = Medals (G/S/B):
+level-completed = Congratulations! You've completed Level { $level }: { $name }.
+game-over-farewell = Game Over. The borrow checker claimed another victim.
+card-copied = Result card copied to your clipboard.
+card-saved = Result card saved to { $path }.
+card-copied-saved = Result card copied to your clipboard and saved to { $path }.
+ghost-new-best = New personal best: { $time } (was { $previous }). Your echo will race you next time.
+ghost-recorded = Run recorded ({ $time }). Your echo will race you next time.
@@ -0,0 +1,69 @@
+//! Badges earned across runs. What each asks for is data, in the bundled
+//! `achievements.toml`; the profile keeps which have been earned.
+
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use crate::puzzle::{Condition, Standing};
+
+#[derive(Debug, Deserialize)]
+pub struct Achievement {
+    /// Kept in the profile, so it mustn't change once released
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub on: Moment,
+    /// Only checked on this floor
+    #[serde(default)]
+    pub floor: Option<usize>,
+    pub when: Condition,
+}
+
+/// When an achievement's condition is checked.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Moment {
+    /// As a room is cleared
+    Room,
+    /// As a floor's last room is
+    Floor,
+    /// As a run is won
+    Run,
+}
+
+#[derive(Deserialize)]
+struct Index {
+    achievement: Vec<Achievement>,
+}
+
+/// Every achievement, in the file's order. Parsed on first use; the file is
+/// compiled into the binary.
+pub fn all() -> &'static [Achievement] {
+    static INDEX: OnceLock<Vec<Achievement>> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        toml::from_str::<Index>(include_str!("achievements.toml"))
+            .map(|index| index.achievement)
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "bundled achievements are broken");
+                Vec::new()
+            })
+    })
+}
+
+/// The achievements not yet in `earned` that `standing` meets at `moment`
+/// on floor `level`.
+pub fn check(
+    moment: Moment,
+    level: usize,
+    standing: &Standing,
+    earned: &BTreeSet<String>,
+) -> Vec<&'static Achievement> {
+    all()
+        .iter()
+        .filter(|achievement| achievement.on == moment)
+        .filter(|achievement| achievement.floor.is_none_or(|floor| floor == level))
+        .filter(|achievement| !earned.contains(&achievement.id))
+        .filter(|achievement| achievement.when.holds(standing))
+        .collect()
+}
@@ -0,0 +1,78 @@
+use std::time::{Duration, Instant};
+
+/// How often the main loop redraws while an animation is running.
+pub const FRAME: Duration = Duration::from_millis(33);
+
+/// Character-by-character reveal of a block of text.
+pub struct Typewriter {
+    started: Instant,
+    chars_per_second: u32,
+    total_chars: usize,
+}
+
+impl Typewriter {
+    /// Starts revealing `text` after `delay`, e.g. once a celebration has played.
+    pub fn new(text: &str, chars_per_second: u32, delay: Duration) -> Self {
+        Typewriter {
+            started: Instant::now() + delay,
+            chars_per_second: chars_per_second.max(1),
+            total_chars: text.chars().count(),
+        }
+    }
+
+    pub fn visible_chars(&self) -> usize {
+        let elapsed = Instant::now()
+            .saturating_duration_since(self.started)
+            .as_millis() as usize;
+        (elapsed * self.chars_per_second as usize / 1000).min(self.total_chars)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.visible_chars() >= self.total_chars
+    }
+
+    /// Returns the portion of `text` revealed so far.
+    pub fn visible<'t>(&self, text: &'t str) -> &'t str {
+        match text.char_indices().nth(self.visible_chars()) {
+            Some((idx, _)) => &text[..idx],
+            None => text,
+        }
+    }
+}
+
+/// Short confetti burst with a flashing border, played when a room or level is cleared.
+pub struct Celebration {
+    started: Instant,
+    pub headline: String,
+    /// Varies the particle layout between bursts.
+    pub seed: u64,
+}
+
+impl Celebration {
+    pub const DURATION: Duration = Duration::from_millis(1600);
+
+    pub fn new(headline: String, seed: u64) -> Self {
+        Celebration {
+            started: Instant::now(),
+            headline,
+            seed,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.started.elapsed() >= Self::DURATION
+    }
+
+    /// Frame counter used to move particles and cycle colors.
+    pub fn frame(&self) -> u64 {
+        (self.started.elapsed().as_millis() / FRAME.as_millis()) as u64
+    }
+}
+
+/// Cheap deterministic hash (SplitMix64) for particle placement.
+pub fn scramble(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
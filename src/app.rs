@@ -0,0 +1,4260 @@
+//! Game state and the rules that move it forward, independent of the terminal.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders},
+};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tui_textarea::TextArea;
+
+use crate::achievements::{self, Moment};
+use crate::anim::{Celebration, Typewriter};
+use crate::codex::Codex;
+use crate::commands;
+use crate::compiler::{self, CompileErrors, OutputMatch, RunSpec, ValidationResult};
+use crate::engine::{PuzzleDir, PuzzleSource, Validator};
+use crate::glyphs::{self, Glyphs};
+use crate::i18n::{self, t};
+use crate::inventory::{Inventory, Item, ItemKind, Supplies};
+use crate::menu::{Menu, MenuItem};
+use crate::modifiers::{Modifiers, Source, Stat};
+use crate::placement::Placement;
+use crate::profile::{CampaignProgress, Profile};
+use crate::puzzle::{
+    Altar, Campaign, Carryover, Consumable, Deck, Door, Economy, EventCard, EventKind,
+    FloorManifest, Medal, OutOfAttempts, Room, Standing, Ware,
+};
+use crate::save::SaveGame;
+use crate::settings::{Autosave, Settings};
+use crate::theme::Theme;
+use crate::timer::{Stopwatch, Timer};
+use crate::toast::{ToastKind, Toasts};
+use crate::ui::format_duration;
+use crate::{
+    anim, archive, attract, bugreport, campaigns, card, coop, feed, feedback, ghost, input,
+    mismatch, notify, paths, presence, sound, stddoc, sync, telemetry,
+};
+
+/// Which screen the game is showing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameState {
+    TitleScreen,
+    Attract, // Demo that plays over an idle title screen
+    Playing,
+    RoomComplete,
+    RoomTransition, // Shows entry narrative when moving to next room
+    Altar,          // Gold-for-HP trades between rooms, where the floor has an altar
+    Shop, // Items and supplies for gold, after a floor's last room or the room its shop.toml names
+    Event, // A card from the event deck, drawn between rooms
+    InventoryFull, // A new item and no slot for it: choose what to leave behind
+    Placement, // The quiz that lets experienced players start on a later floor
+    BreakReminder, // A nudge to rest after a long stretch; the timers stand still
+    Compiling, // A run is being checked on a worker thread; Esc calls it off
+    LevelComplete,
+    FloorRecap, // What the floor taught and what the next one will, before descending
+    ViewingCodex,
+    ViewingArchive,   // Past solutions, from :archive
+    ViewingCampaigns, // Every installed pack's progress side by side
+    SelectingFloor,   // The pack's floors, to start a run on one already reached
+    ViewingHints,     // The room's hints, revealed and still locked, from :hints
+    Feedback,         // :feedback's form, to rate the room for its author
+    GameOver,
+    Paused,
+    Settings,
+    Credits,
+    Onboarding,
+    Confirm,
+}
+
+/// Actions that ask "are you sure?" before running.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PendingAction {
+    Quit,
+    RestartRoom,
+    RevealHint,
+    SummonSpirit,
+}
+
+/// What a run checked in the background was started for.
+pub(crate) enum RunPurpose {
+    Room,
+    /// Only the room's hidden test at this index, from [`App::toggle_test_focus`]
+    Test(usize),
+    Scratch,
+    Eval(String),
+}
+
+/// A run being checked while [`GameState::Compiling`] shows.
+pub(crate) struct PendingRun {
+    purpose: RunPurpose,
+    job: compiler::worker::Job,
+    pub(crate) started: std::time::Instant,
+    return_to: GameState,
+}
+
+/// Mixed into the run seed for event draws, so they don't follow the
+/// celebration bursts that hash the same room numbers.
+const EVENT_SALT: u64 = 0xE7E7_DEC4;
+
+/// Failed runs in a room before `:summon` is offered, for rooms with variants.
+pub(crate) const VARIANT_OFFER_AFTER: u32 = 3;
+
+/// What `:oracle` costs on top of its HP penalty.
+#[cfg(feature = "ai-hints")]
+const ORACLE_GOLD: u32 = 25;
+
+/// How-to-play pages shown before the first room of a player's first game,
+/// as Fluent ids of each page's title and body.
+pub(crate) const ONBOARDING_PAGES: [(&str, &str); 5] = [
+    ("onboarding-welcome-title", "onboarding-welcome"),
+    ("onboarding-run-title", "onboarding-run"),
+    ("onboarding-hp-title", "onboarding-hp"),
+    ("onboarding-gold-title", "onboarding-gold"),
+    ("onboarding-sealed-title", "onboarding-sealed"),
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum MenuOption {
+    Continue,
+    NewGame,
+    Placement,
+    LevelSelect,
+    Practice,
+    Settings,
+    Campaigns,
+    Credits,
+    Quit,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PauseOption {
+    Resume,
+    RestartRoom,
+    Settings,
+    ViewKeys,
+    SaveQuit,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum AltarOption {
+    OfferGold,
+    OfferBlood,
+    Leave,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum ShopOption {
+    /// The ware at this index in the floor's shop
+    Buy(usize),
+    Leave,
+}
+
+/// A choice when a new item doesn't fit.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum DropOption {
+    /// Leave the carried item at this index behind to make room
+    Drop(usize),
+    /// Leave the new item instead
+    LeaveNew,
+}
+
+/// Everything carried that can be left behind, then the new item (unless it
+/// can't be). Packs stay on, and so does whatever a door ahead still needs.
+pub(crate) fn drop_menu(
+    inventory: &Inventory,
+    keep: &[String],
+    can_leave_new: bool,
+) -> Menu<DropOption> {
+    let entry = |label, action, enabled| {
+        if enabled {
+            MenuItem::new(label, action)
+        } else {
+            MenuItem::disabled(label, action)
+        }
+    };
+    let mut items: Vec<MenuItem<DropOption>> = inventory
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let droppable = item.kind != ItemKind::Pack && !keep.contains(&item.name);
+            entry("inventory-drop", DropOption::Drop(index), droppable)
+        })
+        .collect();
+    // Something always has to be leavable, even if a door then stays shut
+    let can_leave_new = can_leave_new || !items.iter().any(|item| item.enabled);
+    items.push(entry(
+        "inventory-leave-new",
+        DropOption::LeaveNew,
+        can_leave_new,
+    ));
+    Menu::new(items)
+}
+
+/// The altar's trades, with the ones the player can't make greyed out.
+pub(crate) fn altar_menu(altar: &Altar, hp: u32, max_hp: u32, gold: u32) -> Menu<AltarOption> {
+    let item = |label, action, enabled| {
+        if enabled {
+            MenuItem::new(label, action)
+        } else {
+            MenuItem::disabled(label, action)
+        }
+    };
+    let price = altar.hp_per_offering.min(max_hp.saturating_sub(hp)) * altar.gold_per_hp;
+    Menu::new(vec![
+        item(
+            "altar-offer-gold",
+            AltarOption::OfferGold,
+            hp < max_hp && gold >= price,
+        ),
+        // Bleeding out on the altar would end the run, so the last HP stay
+        item(
+            "altar-offer-blood",
+            AltarOption::OfferBlood,
+            hp > altar.hp_per_offering,
+        ),
+        item("altar-leave", AltarOption::Leave, true),
+    ])
+}
+
+/// The floor's wares, greyed out when they're already carried, too dear or
+/// there's no slot for them. Supplies are never carried, but a potion is no
+/// use at full HP. `leave` labels the way out.
+pub(crate) fn shop_menu(
+    wares: &[Ware],
+    inventory: &Inventory,
+    gold: u32,
+    (hp, max_hp): (u32, u32),
+    leave: &'static str,
+) -> Menu<ShopOption> {
+    let mut items: Vec<MenuItem<ShopOption>> = wares
+        .iter()
+        .enumerate()
+        .map(|(index, ware)| {
+            let available = match ware.effect {
+                Some(Consumable::Potion { .. }) => hp < max_hp,
+                Some(_) => true,
+                None => {
+                    let fits = ware.kind == ItemKind::Pack || !inventory.is_full();
+                    fits && !inventory.contains(&ware.name)
+                }
+            };
+            if gold >= ware.price && available {
+                MenuItem::new("shop-buy", ShopOption::Buy(index))
+            } else {
+                MenuItem::disabled("shop-buy", ShopOption::Buy(index))
+            }
+        })
+        .collect();
+    items.push(MenuItem::new(leave, ShopOption::Leave));
+    Menu::new(items)
+}
+
+pub(crate) fn title_menu() -> Menu<MenuOption> {
+    Menu::new(vec![
+        MenuItem::disabled("title-continue", MenuOption::Continue),
+        MenuItem::new("title-new-game", MenuOption::NewGame),
+        MenuItem::new("title-placement", MenuOption::Placement),
+        MenuItem::disabled("title-level-select", MenuOption::LevelSelect),
+        MenuItem::disabled("title-practice", MenuOption::Practice),
+        MenuItem::new("title-settings", MenuOption::Settings),
+        MenuItem::disabled("title-campaigns", MenuOption::Campaigns),
+        MenuItem::new("title-credits", MenuOption::Credits),
+        MenuItem::new("title-quit", MenuOption::Quit),
+    ])
+}
+
+pub(crate) fn pause_menu() -> Menu<PauseOption> {
+    Menu::new(vec![
+        MenuItem::new("pause-resume", PauseOption::Resume),
+        MenuItem::new("pause-restart-room", PauseOption::RestartRoom),
+        MenuItem::new("pause-settings", PauseOption::Settings),
+        MenuItem::new("pause-view-keys", PauseOption::ViewKeys),
+        MenuItem::new("pause-save-quit", PauseOption::SaveQuit),
+    ])
+}
+
+/// The room's editor, put aside while the scratch pad is open.
+pub(crate) struct RoomBuffer<'a> {
+    editor: TextArea<'a>,
+    locked_lines: Vec<usize>,
+    last_run_code: String,
+}
+
+/// One player's session: the current run, the screen being shown and its contents.
+pub struct App<'a> {
+    pub(crate) rooms: Vec<Room>,
+    pub(crate) current_room: usize,
+    pub(crate) current_level: usize,
+    pub(crate) editor: TextArea<'a>,
+    /// Set while `:scratch` is open; `editor` then holds the scratch pad
+    pub(crate) room_buffer: Option<RoomBuffer<'a>>,
+    /// What the scratch pad held when it was last closed
+    pub(crate) scratch_lines: Vec<String>,
+    pub(crate) locked_lines: Vec<usize>,
+    pub(crate) yank_buffer: String,
+    pub(crate) message: String,
+    pub(crate) message_style: Style,
+    pub(crate) message_scroll: u16,
+    pub(crate) narrative_scroll: u16,
+    pub(crate) state: GameState,
+    pub(crate) menu: Menu<MenuOption>,
+    /// The current floor's `floor.toml`
+    pub(crate) floor: FloorManifest,
+    pub(crate) altar_menu: Menu<AltarOption>,
+    pub(crate) shop_menu: Menu<ShopOption>,
+    pub(crate) deck: Deck,
+    pub(crate) campaign: Campaign,
+    /// Ids of the rooms whose secret the run has found
+    pub(crate) secrets_found: Vec<String>,
+    /// The card drawn on the way to the next room, until it's dealt with
+    pub(crate) event: Option<EventCard>,
+    pub(crate) hp: u32,
+    pub(crate) gold: u32,
+    pub(crate) inventory: Inventory,
+    /// A reward that didn't fit, waiting for a slot to be freed
+    pub(crate) pending_item: Option<Item>,
+    pub(crate) drop_menu: Menu<DropOption>,
+    pub(crate) modifiers: Modifiers,
+    pub(crate) codex: Codex,
+    pub(crate) hints_used_room: usize,
+    /// Highlighted hint on the `:hints` screen
+    pub(crate) hints_cursor: usize,
+    /// The `:feedback` form
+    pub(crate) feedback: feedback::Draft,
+    /// The next floor's `concepts`, for the recap before it
+    pub(crate) recap_ahead: Vec<String>,
+    pub(crate) recap_scroll: u16,
+    pub(crate) oracle_hints_room: usize,
+    /// rustc working out the compiler spirit's fix
+    spirit_job: Option<compiler::worker::Job<Vec<compiler::suggest::Suggestion>>>,
+    /// The fix the compiler spirit offers, waiting for the player to pay
+    pub(crate) spirit_offer: Option<compiler::suggest::Suggestion>,
+    /// The fix the compiler spirit offered, waiting for `:accept`
+    pub(crate) spirit_suggestion: Option<compiler::suggest::Suggestion>,
+    pub(crate) attempts_room: u32,
+    /// Compile errors in a row in this room
+    pub(crate) compile_streak: u32,
+    /// Whether the streak earned mercy: halved penalties and errors in plain
+    /// words for the rest of the room
+    pub(crate) mercy: bool,
+    /// The hidden test the last run failed, by index, for Ctrl+T to pin
+    pub(crate) failed_test: Option<usize>,
+    /// The hidden test F5 runs on its own instead of the full check
+    pub(crate) focused_test: Option<usize>,
+    /// Bought at a shop and not yet used
+    pub(crate) supplies: Supplies,
+    /// Whether an intent scroll was spent on this room
+    pub(crate) intent_read: bool,
+    pub(crate) room_clock: Stopwatch,
+    pub(crate) hints_used_total: usize,
+    pub(crate) compile_errors_total: u32,
+    /// Clear time of each room cleared on this floor, in seconds
+    pub(crate) floor_times: Vec<u64>,
+    /// Rooms on this floor cleared within par
+    pub(crate) floor_under_par: usize,
+    // Whole-run totals for the result card; the *_total fields above reset per floor
+    /// The speedrun clock
+    pub(crate) run_clock: Stopwatch,
+    pub(crate) run_hints: usize,
+    pub(crate) run_compile_errors: u32,
+    pub(crate) run_marks: Vec<card::RoomMark>,
+    /// Time attack medals won this run, with the floor each came from
+    pub(crate) medals: Vec<(usize, Medal)>,
+    // Room clear times for racing the personal-best ghost; only full runs count
+    pub(crate) run_splits: Vec<ghost::Split>,
+    pub(crate) racing: bool,
+    pub(crate) ghost: Option<ghost::Ghost>,
+    /// Where cleared rooms' solutions are kept; None when they aren't
+    pub(crate) archive: Option<archive::Archive>,
+    pub(crate) command_mode: bool,
+    pub(crate) command_buffer: String,
+    /// Commands run this session, for Up and Down in command mode
+    pub(crate) command_history: commands::History,
+    pub(crate) last_output: Option<(String, String)>, // (expected, got) from the last wrong answer
+    /// The last room run's verdict and a hash of the code it was for, given
+    /// again without a rebuild if the same code is run again
+    pub(crate) echo: Option<(u64, ValidationResult)>,
+    // rustc's complaint, kept for bug reports; F8 expands it
+    pub(crate) last_compile_output: Option<CompileErrors>,
+    pub(crate) compile_errors_expanded: bool,
+    /// Where the last run's errors point in the editor's code, until it's
+    /// run again or replaced
+    pub(crate) error_markers: Vec<compiler::Marker>,
+    pub(crate) room_error_codes: Vec<String>, // For telemetry
+    pub(crate) side_by_side: bool,
+    pub(crate) show_expected: bool,
+    pub(crate) settings: Settings,
+    pub(crate) sound: sound::Sound,
+    /// Whether the terminal has focus, as far as focus reports tell
+    pub(crate) focused: bool,
+    pub(crate) key_protocol: input::KeyProtocol,
+    /// Set when a run took long enough that the player may have looked away
+    pub(crate) slow_verdict: Option<String>,
+    pub(crate) unicode_supported: bool,
+    pub(crate) profile: Profile,
+    /// Profile changes not yet written, waiting on the autosave setting
+    profile_unsaved: bool,
+    /// A run has started and not yet ended; it's what `save.toml` holds
+    run_live: bool,
+    /// Set when the autosave setting wants the run written on the next tick
+    run_save_due: bool,
+    /// The run Continue on the title screen picks up
+    pub(crate) saved_run: Option<SaveGame>,
+    pub(crate) onboarding_page: usize,
+    pub(crate) typewriter: Option<Typewriter>,
+    pub(crate) celebration: Option<Celebration>,
+    pub(crate) launched: std::time::Instant,
+    pub(crate) toasts: Toasts,
+    /// When the player last came back from a break (or sat down)
+    pub(crate) last_break: std::time::Instant,
+    /// When the break reminder on screen now appeared
+    break_started: Option<std::time::Instant>,
+    /// When the player last pressed a key, to start the attract demo when idle
+    pub(crate) last_key: std::time::Instant,
+    /// Whether the message pane shows the keys, from F2 or `:keys`, and
+    /// nothing has been typed since
+    pub(crate) reading_keys: bool,
+    pub(crate) attract: Option<attract::Demo>,
+    pub(crate) placement: Option<Placement>,
+    // Set whenever something visible changed; the loop only draws when it's set
+    pub(crate) needs_redraw: bool,
+    pub(crate) clock_timer: Timer,
+    pub(crate) replay_label: Option<String>, // Shown in the corner while playing back --replay
+    // Session options from the command line
+    pub(crate) puzzles: Box<dyn PuzzleSource>,
+    pub(crate) seed: u64,
+    pub(crate) theme: Theme,
+    pub(crate) accessible: bool,
+    pub(crate) validator: Arc<dyn Validator + Send + Sync>,
+    pub(crate) pending_run: Option<PendingRun>,
+    pub(crate) update_notice: Option<String>, // Newer release version, shown on the title screen
+    pub(crate) update_rx: Option<std::sync::mpsc::Receiver<String>>,
+    pub(crate) sync_rx: Option<std::sync::mpsc::Receiver<Result<sync::Outcome, String>>>,
+    pub(crate) share_rx: Option<std::sync::mpsc::Receiver<Result<String, String>>>,
+    /// The error code `rustc --explain` is looking up, and its answer
+    pub(crate) explain_rx: Option<(String, std::sync::mpsc::Receiver<Result<String, String>>)>,
+    #[cfg(feature = "ai-hints")]
+    pub(crate) oracle_rx: Option<std::sync::mpsc::Receiver<Result<String, String>>>,
+    pub(crate) coop: Option<coop::Session>,
+    // What the partner last saw, so changes are only sent once and never echoed
+    pub(crate) coop_lines: Vec<String>,
+    pub(crate) coop_room: (usize, usize),
+    pub(crate) last_run_code: String, // Buffer contents at the last F5 (or room load)
+    pub(crate) pause_menu: Menu<PauseOption>,
+    pub(crate) settings_selection: usize,
+    pub(crate) settings_return: GameState,
+    pub(crate) pending_action: PendingAction,
+    pub(crate) confirm_return: GameState,
+    /// The dashboard's rows while it's open
+    pub(crate) campaigns: Vec<campaigns::Summary>,
+    pub(crate) campaigns_return: GameState,
+    /// The puzzle packs the credits list, while they're open
+    pub(crate) pack_credits: Vec<campaigns::Credit>,
+    pub(crate) floor_select: Vec<campaigns::FloorProgress>,
+    pub(crate) floor_select_cursor: usize,
+}
+
+impl<'a> App<'a> {
+    pub fn new(rooms: Vec<Room>, settings: Settings, profile: Profile) -> Self {
+        let room = &rooms[0];
+        let code = room.challenge.code.trim();
+        let locked_lines = room.challenge.locked_lines.clone();
+        let last_run_code = code.to_string();
+        let theme = settings.theme;
+
+        let mut editor = TextArea::from(code.lines());
+        editor.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t!("editor-title-short")),
+        );
+        editor.set_line_number_style(Style::default().fg(Color::DarkGray));
+
+        App {
+            rooms,
+            current_room: 0,
+            current_level: 1,
+            editor,
+            room_buffer: None,
+            scratch_lines: Vec::new(),
+            locked_lines,
+            yank_buffer: String::new(),
+            message: t!("room-start"),
+            message_style: Style::default().fg(Color::Yellow),
+            message_scroll: 0,
+            narrative_scroll: 0,
+            state: GameState::TitleScreen,
+            menu: title_menu(),
+            floor: FloorManifest::default(),
+            altar_menu: Menu::new(Vec::new()),
+            shop_menu: Menu::new(Vec::new()),
+            deck: Deck::default(),
+            campaign: Campaign::default(),
+            secrets_found: Vec::new(),
+            event: None,
+            hp: Economy::default().starting_hp,
+            gold: 0,
+            inventory: Inventory::default(),
+            pending_item: None,
+            drop_menu: Menu::new(Vec::new()),
+            modifiers: Modifiers::default(),
+            codex: Codex::default(),
+            hints_used_room: 0,
+            hints_cursor: 0,
+            feedback: feedback::Draft::default(),
+            recap_ahead: Vec::new(),
+            recap_scroll: 0,
+            oracle_hints_room: 0,
+            spirit_job: None,
+            spirit_offer: None,
+            spirit_suggestion: None,
+            attempts_room: 0,
+            room_clock: Stopwatch::start(),
+            hints_used_total: 0,
+            compile_errors_total: 0,
+            floor_times: Vec::new(),
+            floor_under_par: 0,
+            compile_streak: 0,
+            mercy: false,
+            failed_test: None,
+            focused_test: None,
+            supplies: Supplies::default(),
+            intent_read: false,
+            run_clock: Stopwatch::start(),
+            run_hints: 0,
+            run_compile_errors: 0,
+            run_marks: Vec::new(),
+            medals: Vec::new(),
+            run_splits: Vec::new(),
+            racing: false,
+            ghost: None,
+            archive: None,
+            command_mode: false,
+            command_buffer: String::new(),
+            command_history: commands::History::default(),
+            last_output: None,
+            last_compile_output: None,
+            compile_errors_expanded: false,
+            error_markers: Vec::new(),
+            echo: None,
+            room_error_codes: Vec::new(),
+            side_by_side: false,
+            show_expected: false,
+            sound: sound::Sound::new(settings.sound, settings.volume),
+            focused: true,
+            key_protocol: input::KeyProtocol::Unknown,
+            slow_verdict: None,
+            settings,
+            unicode_supported: true,
+            profile,
+            profile_unsaved: false,
+            run_live: false,
+            run_save_due: false,
+            saved_run: None,
+            onboarding_page: 0,
+            typewriter: None,
+            celebration: None,
+            launched: std::time::Instant::now(),
+            toasts: Toasts::default(),
+            last_break: std::time::Instant::now(),
+            break_started: None,
+            last_key: std::time::Instant::now(),
+            reading_keys: false,
+            attract: None,
+            placement: None,
+            needs_redraw: true,
+            clock_timer: Timer::every(std::time::Duration::from_secs(1)),
+            replay_label: None,
+            puzzles: Box::new(PuzzleDir::new("puzzles")),
+            seed: 0,
+            theme,
+            accessible: false,
+            validator: Arc::new(compiler::Backend::Local),
+            pending_run: None,
+            update_notice: None,
+            update_rx: None,
+            sync_rx: None,
+            share_rx: None,
+            explain_rx: None,
+            #[cfg(feature = "ai-hints")]
+            oracle_rx: None,
+            coop: None,
+            coop_lines: Vec::new(),
+            coop_room: (1, 0),
+            last_run_code,
+            pause_menu: pause_menu(),
+            settings_selection: 0,
+            settings_return: GameState::Paused,
+            pending_action: PendingAction::Quit,
+            confirm_return: GameState::Playing,
+            campaigns: Vec::new(),
+            pack_credits: Vec::new(),
+            campaigns_return: GameState::Playing,
+            floor_select: Vec::new(),
+            floor_select_cursor: 0,
+        }
+    }
+
+    /// Loads floors from `puzzles` instead of the bundled `puzzles` directory.
+    pub fn with_puzzles(mut self, puzzles: impl PuzzleSource + 'static) -> Self {
+        self.campaign = puzzles
+            .load_campaign()
+            .inspect_err(|e| tracing::warn!(error = %e, "playing without the campaign manifest"))
+            .unwrap_or_default();
+        self.hp = self.max_hp();
+        self.puzzles = Box::new(puzzles);
+        self
+    }
+
+    /// Checks solutions with `validator` instead of the local rustc.
+    pub fn with_validator(mut self, validator: impl Validator + Send + Sync + 'static) -> Self {
+        self.validator = Arc::new(validator);
+        self
+    }
+
+    pub(crate) fn room(&self) -> &Room {
+        &self.rooms[self.current_room]
+    }
+
+    /// ASCII mode is on when the player asks for it or the terminal probe failed.
+    pub(crate) fn ascii_only(&self) -> bool {
+        self.settings.ascii_only || !self.unicode_supported || self.accessible
+    }
+
+    pub(crate) fn glyphs(&self) -> &'static Glyphs {
+        if self.ascii_only() {
+            &glyphs::ASCII
+        } else {
+            &glyphs::UNICODE
+        }
+    }
+
+    pub(crate) fn is_animating(&self) -> bool {
+        self.typewriter.as_ref().is_some_and(|t| !t.is_done())
+            || self.celebration.as_ref().is_some_and(|c| !c.is_done())
+    }
+
+    /// Plays the attract demo over the idle title screen.
+    pub(crate) fn start_attract(&mut self) {
+        let floors: Vec<Room> = (1..=self.puzzles.floor_count())
+            .filter_map(|level| self.puzzles.load_floor(level).ok())
+            .flatten()
+            .collect();
+        // A different room and facts each time the demo comes round
+        let seed = anim::scramble(self.seed ^ self.launched.elapsed().as_secs());
+        self.attract = Some(attract::Demo::new(&floors, seed));
+        self.state = GameState::Attract;
+    }
+
+    /// Frame counter for ambient animation; frozen in accessible mode.
+    pub(crate) fn ambient_frame(&self) -> u64 {
+        if self.accessible {
+            return 0;
+        }
+        (self.launched.elapsed().as_millis() / anim::FRAME.as_millis()) as u64
+    }
+
+    /// Advances time-based state; called by the main loop once per [`crate::timer::TICK`].
+    pub fn tick(&mut self) {
+        if self.is_animating() {
+            self.needs_redraw = true;
+        }
+        crate::screens::for_state(self.state).update(self);
+        self.check_break();
+        if std::mem::take(&mut self.run_save_due) {
+            self.save_run();
+        }
+        let now = std::time::Instant::now();
+        let held = self.clocks_held();
+        self.room_clock.hold(held, now);
+        self.run_clock.hold(held, now);
+        if self.toasts.update(self.hp, self.gold, &self.inventory, now) {
+            self.needs_redraw = true;
+        }
+        if let Some(version) = self.update_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.update_notice = Some(version);
+            self.update_rx = None;
+            self.needs_redraw = true;
+        }
+        if self.coop.is_some() {
+            self.sync_coop();
+        }
+        if let Some(result) = self.sync_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.sync_rx = None;
+            self.finish_sync(result);
+            self.needs_redraw = true;
+        }
+        if let Some(result) = self.share_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.share_rx = None;
+            self.finish_share(result);
+            self.needs_redraw = true;
+        }
+        if let Some(result) = self
+            .explain_rx
+            .as_ref()
+            .and_then(|(_, rx)| rx.try_recv().ok())
+        {
+            if let Some((code, _)) = self.explain_rx.take() {
+                self.finish_explain(&code, result);
+            }
+            self.needs_redraw = true;
+        }
+        // The spirit's offer waits until the player is back in the room
+        if matches!(self.state, GameState::Playing | GameState::ViewingHints) {
+            if let Some(result) = self.spirit_job.as_ref().and_then(|job| job.try_result()) {
+                self.spirit_job = None;
+                self.finish_spirit(result);
+                self.needs_redraw = true;
+            }
+        }
+        #[cfg(feature = "ai-hints")]
+        if let Some(result) = self.oracle_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.oracle_rx = None;
+            self.finish_oracle(result);
+            self.needs_redraw = true;
+        }
+        if self.typewriter.as_ref().is_some_and(|t| t.is_done()) {
+            self.typewriter = None;
+            self.needs_redraw = true;
+        }
+        if self.celebration.as_ref().is_some_and(|c| c.is_done()) {
+            self.celebration = None;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Shows the break reminder once `break_reminder_minutes` have passed
+    /// since the last one. Only in the editor, so no dialog is covered.
+    fn check_break(&mut self) {
+        let minutes = self.settings.break_reminder_minutes;
+        // Never during playback, where the recording decides what happens,
+        // or with a partner who may not want the break
+        if minutes == 0
+            || self.state != GameState::Playing
+            || self.replay_label.is_some()
+            || self.coop.is_some()
+        {
+            return;
+        }
+        if self.last_break.elapsed() >= std::time::Duration::from_secs(u64::from(minutes) * 60) {
+            self.break_started = Some(std::time::Instant::now());
+            self.state = GameState::BreakReminder;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// How long the break reminder on screen has been up.
+    pub(crate) fn break_elapsed(&self) -> std::time::Duration {
+        self.break_started
+            .map_or(std::time::Duration::ZERO, |started| started.elapsed())
+    }
+
+    /// Back to the room. The clocks stood still for the break.
+    pub(crate) fn end_break(&mut self) {
+        self.break_started = None;
+        self.last_break = std::time::Instant::now();
+        self.state = GameState::Playing;
+    }
+
+    /// Time in the room so far, less time the clocks stood still.
+    pub(crate) fn room_elapsed(&self) -> std::time::Duration {
+        self.room_clock.elapsed()
+    }
+
+    /// Time in the run so far, less time the clocks stood still.
+    pub(crate) fn run_elapsed(&self) -> std::time::Duration {
+        self.run_clock.elapsed()
+    }
+
+    /// Whether the room and run clocks stand still: always for a break, and
+    /// with `pause_clock_while_reading` while the player reads rather than
+    /// plays, on the codex, the keys, the narratives between rooms or the
+    /// pause menu.
+    fn clocks_held(&self) -> bool {
+        match self.state {
+            GameState::BreakReminder => true,
+            _ if !self.settings.pause_clock_while_reading => false,
+            GameState::Playing => self.reading_keys,
+            GameState::ViewingCodex
+            | GameState::RoomComplete
+            | GameState::RoomTransition
+            | GameState::LevelComplete
+            | GameState::FloorRecap
+            | GameState::Paused => true,
+            GameState::Settings => self.settings_return == GameState::Paused,
+            _ => false,
+        }
+    }
+
+    /// Stops any running animation. Returns true if one was still playing.
+    pub(crate) fn skip_animations(&mut self) -> bool {
+        let was_animating = self.is_animating();
+        self.typewriter = None;
+        self.celebration = None;
+        was_animating
+    }
+
+    pub(crate) fn start_typewriter(&mut self) {
+        if self.settings.typewriter && !self.accessible {
+            // Let a celebration finish before the text starts appearing
+            let delay = if self.celebration.is_some() {
+                Celebration::DURATION
+            } else {
+                std::time::Duration::ZERO
+            };
+            self.typewriter = Some(Typewriter::new(
+                &self.message,
+                self.settings.typewriter_chars_per_second,
+                delay,
+            ));
+        }
+    }
+
+    pub(crate) fn start_celebration(&mut self, headline: String) {
+        if self.settings.celebrations && !self.accessible {
+            // Each room gets its own burst, reproducible with --seed
+            let seed =
+                anim::scramble(self.seed ^ (self.current_level * 100 + self.current_room) as u64);
+            self.celebration = Some(Celebration::new(headline, seed));
+        }
+    }
+
+    pub(crate) fn start_game(&mut self) {
+        self.state = GameState::Playing;
+        self.gold = 0;
+        self.inventory.clear();
+        self.supplies = Supplies::default();
+        self.pending_item = None;
+        self.modifiers = Modifiers::new(self.settings.difficulty);
+        self.current_level = 1;
+        self.floor = self.load_manifest(1);
+        self.deck = self
+            .puzzles
+            .load_events()
+            .inspect_err(|e| tracing::warn!(error = %e, "playing without the event deck"))
+            .unwrap_or_default();
+        self.campaign = self
+            .puzzles
+            .load_campaign()
+            .inspect_err(|e| tracing::warn!(error = %e, "playing without the campaign manifest"))
+            .unwrap_or_default();
+        self.hp = self.max_hp();
+        self.secrets_found.clear();
+        self.hints_used_room = 0;
+        self.hints_used_total = 0;
+        self.compile_errors_total = 0;
+        self.floor_times.clear();
+        self.floor_under_par = 0;
+        self.run_clock = Stopwatch::start();
+        self.run_hints = 0;
+        self.run_compile_errors = 0;
+        self.medals.clear();
+        self.run_marks.clear();
+        self.run_splits.clear();
+        self.racing = true;
+        self.run_live = true;
+        // Starting over leaves the old run behind
+        self.saved_run = None;
+        self.menu.set_enabled(MenuOption::Continue, false);
+        self.toasts.reset(self.hp, self.gold, &self.inventory);
+        self.load_room(0);
+        if let Some(intro) = &self.floor.intro {
+            self.message = format!("{}\n\n{}", intro.trim(), self.message);
+        }
+        self.show_onboarding_if_new();
+    }
+
+    /// Begins a run in a specific room (0-based), e.g. from `--level`/`--room`.
+    pub(crate) fn start_at(&mut self, level: usize, room: usize) -> Result<(), String> {
+        let floors = self.puzzles.floor_count();
+        if level == 0 || level > floors {
+            return Err(t!("error-level-count", level = level, floors = floors));
+        }
+        self.start_game();
+        // Splits from a run that skipped rooms can't be compared with a full one
+        self.racing = (level, room) == (1, 0);
+        if level != self.current_level {
+            self.load_level(level)?;
+        }
+        if room >= self.rooms.len() {
+            return Err(t!(
+                "error-level-room-count",
+                level = level,
+                rooms = self.rooms.len()
+            ));
+        }
+        self.load_room(room);
+        self.show_onboarding_if_new();
+        Ok(())
+    }
+
+    pub(crate) fn show_onboarding_if_new(&mut self) {
+        if !self.profile.seen_onboarding {
+            self.onboarding_page = 0;
+            self.state = GameState::Onboarding;
+        }
+    }
+
+    pub(crate) fn finish_onboarding(&mut self) {
+        self.state = GameState::Playing;
+        if !self.profile.seen_onboarding {
+            self.profile.seen_onboarding = true;
+            self.profile_changed();
+        }
+    }
+
+    /// Starts the placement quiz, or an ordinary new game if the pack has none.
+    pub(crate) fn start_placement(&mut self) {
+        let quiz = self
+            .puzzles
+            .load_placement()
+            .inspect_err(|e| tracing::warn!(error = %e, "no placement quiz"))
+            .unwrap_or_default();
+        if quiz.is_empty() {
+            self.start_game();
+            return;
+        }
+        self.placement = Some(Placement::new(quiz));
+        self.state = GameState::Placement;
+    }
+
+    /// Begins a run on the floor the quiz placed the player on.
+    pub(crate) fn finish_placement(&mut self) {
+        let Some(placement) = self.placement.take() else {
+            return;
+        };
+        self.start_on_floor(placement.floor());
+    }
+
+    /// The title screen's floor select, on the deepest floor reached.
+    pub(crate) fn show_floor_select(&mut self) {
+        self.floor_select = campaigns::floors(self.puzzles.as_ref(), &self.profile);
+        self.floor_select_cursor = self
+            .floor_select
+            .iter()
+            .rposition(|floor| floor.unlocked)
+            .unwrap_or(0);
+        self.state = GameState::SelectingFloor;
+    }
+
+    /// Begins a run on floor `level`, carrying what the floors above would
+    /// have given: their items and codex entries.
+    pub(crate) fn start_on_floor(&mut self, level: usize) {
+        if let Err(e) = self.start_at(level, 0) {
+            tracing::warn!(level, error = %e, "could not start on the chosen floor");
+            self.start_game();
+            return;
+        }
+        let mut items = Vec::new();
+        let mut used_up = Vec::new();
+        for skipped in 1..level {
+            let rooms = match self.puzzles.load_floor(skipped) {
+                Ok(rooms) => rooms,
+                Err(e) => {
+                    tracing::warn!(level = skipped, error = %e, "no starting items from floor");
+                    continue;
+                }
+            };
+            for room in &rooms {
+                if let Some(entry) = room.codex.clone() {
+                    self.codex.add(entry, skipped, &room.meta.concept);
+                }
+                items.extend(room.rewards.as_ref().and_then(Item::from_rewards));
+            }
+            // Whatever the skipped floor's doors would have kept stays there
+            let exit = self.load_manifest(skipped).exit;
+            used_up.extend(
+                rooms
+                    .iter()
+                    .filter_map(|room| room.meta.door.clone())
+                    .chain(exit)
+                    .filter(|door| door.consumes)
+                    .map(|door| door.requires_item),
+            );
+        }
+        items.retain(|item| !used_up.contains(&item.name));
+        // Packs first, so everything after them has the room
+        items.sort_by_key(|item| item.kind != ItemKind::Pack);
+        let mut names = Vec::new();
+        for item in items {
+            let (name, adjustments) = (item.name.clone(), item.modifiers.clone());
+            if self.inventory.add(item).is_ok() {
+                self.modifiers.add(name.clone(), Source::Item, adjustments);
+                names.push(name);
+            }
+        }
+        if level > 1 {
+            self.message = t!(
+                "placement-started",
+                level = level,
+                name = self.puzzles.floor_name(level),
+                items = names.join(", ")
+            );
+            self.message_style = Style::default().fg(Color::Cyan);
+        }
+    }
+
+    /// What Enter does once the floor is cleared: the way down, naming the
+    /// floor below, or just carrying on after the last one.
+    fn next_floor_prompt(&self) -> String {
+        let next = self.current_level + 1;
+        if next > self.puzzles.floor_count() {
+            return t!("press-enter-continue");
+        }
+        let name = self.puzzles.floor_name(next);
+        match self.load_manifest(next).theme {
+            Some(theme) => t!(
+                "level-descend-theme",
+                level = next,
+                name = name,
+                theme = theme.trim()
+            ),
+            None => t!("level-descend", level = next, name = name),
+        }
+    }
+
+    pub(crate) fn load_level(&mut self, level: usize) -> Result<(), String> {
+        if level == 0 || level > self.puzzles.floor_count() {
+            return Err(t!("error-level-missing", level = level));
+        }
+        match self.puzzles.load_floor(level) {
+            Ok(rooms) if !rooms.is_empty() => {
+                self.rooms = rooms;
+                self.floor = self.load_manifest(level);
+                self.current_level = level;
+                self.current_room = 0;
+                self.hints_used_total = 0;
+                self.compile_errors_total = 0;
+                self.floor_times.clear();
+                self.floor_under_par = 0;
+                self.load_room(0);
+                if let Some(intro) = &self.floor.intro {
+                    self.message = format!("{}\n\n{}", intro.trim(), self.message);
+                }
+                Ok(())
+            }
+            Ok(_) => Err(t!("error-level-empty", level = level)),
+            Err(e) => Err(t!("error-level-load", level = level, error = e.to_string())),
+        }
+    }
+
+    /// A broken `floor.toml` only costs the floor its extras, not the run.
+    fn load_manifest(&self, level: usize) -> FloorManifest {
+        self.puzzles
+            .load_manifest(level)
+            .inspect_err(|e| tracing::warn!(level, error = %e, "ignoring floor manifest"))
+            .unwrap_or_default()
+    }
+
+    /// Scrolls the Chamber pane, stopping once its last wrapped line is at the top.
+    pub(crate) fn scroll_narrative(&mut self, delta: i32) {
+        let (width, _) = crossterm::terminal::size().unwrap_or((100, 40));
+        // The narrative pane is 35% wide minus its borders
+        let pane_width = (width as usize * 35 / 100).saturating_sub(2).max(1);
+        let wrapped_lines: usize = self
+            .room()
+            .narrative
+            .intro
+            .lines()
+            .map(|l| l.chars().count().div_ceil(pane_width).max(1))
+            .sum();
+        let max = wrapped_lines.saturating_sub(3) as i32;
+        self.narrative_scroll = (self.narrative_scroll as i32 + delta).clamp(0, max.max(0)) as u16;
+    }
+
+    pub(crate) fn is_modified(&self) -> bool {
+        self.editor.lines().join("\n") != self.last_run_code
+    }
+
+    pub(crate) fn is_line_locked(&self, line: usize) -> bool {
+        self.locked_lines.contains(&(line + 1))
+    }
+
+    pub(crate) fn load_room(&mut self, index: usize) {
+        // The room's own buffer is about to be replaced anyway
+        if self.room_buffer.take().is_some() {
+            self.scratch_lines = self.editor.lines().to_vec();
+        }
+        self.current_room = index;
+        let room = &self.rooms[index];
+        let code = room.challenge.code.trim();
+        self.locked_lines = room.challenge.locked_lines.clone();
+
+        self.last_run_code = code.to_string();
+        self.set_editor_lines(code.lines().map(String::from).collect());
+
+        self.message = t!("room-start");
+        self.message_style = Style::default().fg(Color::Yellow);
+        self.state = GameState::Playing;
+        self.sound.set_floor(self.current_level);
+        self.hints_used_room = 0;
+        self.oracle_hints_room = 0;
+        if let Some(job) = self.spirit_job.take() {
+            job.cancel();
+        }
+        self.spirit_offer = None;
+        self.spirit_suggestion = None;
+        self.last_compile_output = None;
+        self.echo = None;
+        self.attempts_room = 0;
+        self.compile_streak = 0;
+        self.mercy = false;
+        self.intent_read = false;
+        self.failed_test = None;
+        self.focused_test = None;
+        self.narrative_scroll = 0;
+        self.room_error_codes.clear();
+        self.room_clock = Stopwatch::start();
+        self.reading_keys = false;
+        self.last_output = None;
+        self.typewriter = None;
+        self.celebration = None;
+        self.run_save_due |= match self.settings.autosave {
+            Autosave::EveryRoom => true,
+            Autosave::EveryFloor => index == 0,
+            Autosave::OnQuit => false,
+        };
+    }
+
+    /// Replaces the editor contents, keeping the cursor as close to where it was as possible.
+    pub(crate) fn set_editor_lines(&mut self, lines: Vec<String>) {
+        let (row, col) = self.editor.cursor();
+        self.editor = TextArea::new(lines);
+        self.editor.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t!("editor-title")),
+        );
+        self.editor
+            .set_line_number_style(Style::default().fg(Color::DarkGray));
+        self.editor
+            .move_cursor(tui_textarea::CursorMove::Jump(row as u16, col as u16));
+        self.error_markers.clear();
+    }
+
+    /// F5: runs the solution here and, in co-op, on the partner's side too.
+    pub(crate) fn run_shared(&mut self) {
+        if self.room_buffer.is_some() {
+            self.run_scratch();
+            return;
+        }
+        // A pinned hidden test only runs on this side
+        if self.coop.is_some() && self.focused_test.is_none() {
+            // The partner must have the exact buffer before it runs
+            self.sync_coop();
+            self.send_coop(coop::Message::Run);
+        }
+        self.run_solution();
+    }
+
+    pub(crate) fn send_coop(&mut self, message: coop::Message) {
+        let Some(session) = self.coop.as_mut() else {
+            return;
+        };
+        if let Err(e) = session.send(&message) {
+            tracing::warn!(error = %e, "co-op send failed");
+            self.end_coop();
+        }
+    }
+
+    pub(crate) fn end_coop(&mut self) {
+        if self.coop.take().is_some() {
+            self.message = t!("coop-partner-left");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Applies the partner's messages, then sends any local room or buffer change.
+    pub(crate) fn sync_coop(&mut self) {
+        loop {
+            let next = match self.coop.as_ref().map(|session| session.poll()) {
+                Some(Ok(Some(message))) => message,
+                Some(Ok(None)) | None => break,
+                Some(Err(())) => {
+                    self.end_coop();
+                    return;
+                }
+            };
+            self.needs_redraw = true;
+            // The partner is working on the room, so follow them back to it
+            self.close_scratch();
+            match next {
+                coop::Message::Buffer { lines } => {
+                    if lines != self.editor.lines() {
+                        self.set_editor_lines(lines.clone());
+                    }
+                    self.coop_lines = lines;
+                }
+                coop::Message::Run => self.run_solution(),
+                coop::Message::Goto { level, room } => {
+                    if (level, room) != (self.current_level, self.current_room) {
+                        let loaded = if level != self.current_level {
+                            self.load_level(level)
+                        } else {
+                            Ok(())
+                        };
+                        if loaded.is_ok() && room < self.rooms.len() {
+                            self.load_room(room);
+                        }
+                    }
+                    self.coop_room = (level, room);
+                }
+            }
+        }
+
+        let room = (self.current_level, self.current_room);
+        if room != self.coop_room {
+            self.coop_room = room;
+            self.send_coop(coop::Message::Goto {
+                level: room.0,
+                room: room.1,
+            });
+        }
+        if self.room_buffer.is_none() && self.editor.lines() != self.coop_lines.as_slice() {
+            self.coop_lines = self.editor.lines().to_vec();
+            let lines = self.coop_lines.clone();
+            self.send_coop(coop::Message::Buffer { lines });
+        }
+    }
+
+    /// `:scratch`: swaps the room's code for a free-form buffer, or back.
+    pub(crate) fn toggle_scratch(&mut self) {
+        if self.room_buffer.is_some() {
+            self.close_scratch();
+            return;
+        }
+        let lines = if self.scratch_lines.is_empty() {
+            vec!["fn main() {}".to_string()]
+        } else {
+            self.scratch_lines.clone()
+        };
+        let mut scratch = TextArea::new(lines);
+        scratch.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta))
+                .title(t!("scratch-title")),
+        );
+        scratch.set_line_number_style(Style::default().fg(Color::DarkGray));
+        self.room_buffer = Some(RoomBuffer {
+            editor: std::mem::replace(&mut self.editor, scratch),
+            locked_lines: std::mem::take(&mut self.locked_lines),
+            last_run_code: std::mem::take(&mut self.last_run_code),
+        });
+        self.last_run_code = self.editor.lines().join("\n");
+        self.error_markers.clear();
+        self.message = t!("scratch-opened");
+        self.message_style = Style::default().fg(Color::Magenta);
+        self.message_scroll = 0;
+    }
+
+    /// Puts the room's code back, keeping the scratch pad for next time.
+    pub(crate) fn close_scratch(&mut self) {
+        let Some(room) = self.room_buffer.take() else {
+            return;
+        };
+        self.scratch_lines = self.editor.lines().to_vec();
+        self.editor = room.editor;
+        self.locked_lines = room.locked_lines;
+        self.last_run_code = room.last_run_code;
+        self.error_markers.clear();
+        self.message = t!("scratch-closed");
+        self.message_style = Style::default().fg(Color::Yellow);
+        self.message_scroll = 0;
+    }
+
+    /// F5 in the scratch pad: compiles and runs it with the room's validator,
+    /// and shows what it printed. Costs nothing and counts as no attempt.
+    fn run_scratch(&mut self) {
+        let code = self.editor.lines().join("\n");
+        self.last_run_code = code.clone();
+        self.start_unscored(RunPurpose::Scratch, code);
+    }
+
+    fn finish_scratch(&mut self, result: anyhow::Result<ValidationResult>) {
+        match Self::unscored(result) {
+            Ok(Ok(output)) if output.is_empty() => {
+                self.message = t!("scratch-no-output");
+                self.message_style = Style::default().fg(Color::Green);
+            }
+            Ok(Ok(output)) => {
+                self.message = format!("{}\n\n```\n{}\n```", t!("scratch-output"), output);
+                self.message_style = Style::default().fg(Color::Green);
+            }
+            Ok(Err(err)) => {
+                self.message = format!("{}\n\n```\n{}\n```", t!("scratch-compile-error"), err);
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Err(e) => {
+                self.message = t!("scratch-failed", error = e.to_string());
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+    }
+
+    /// `:eval <expr>`: prints the expression's `{:?}` in the message pane, a
+    /// quick answer to "what does this return?".
+    pub(crate) fn eval(&mut self, expr: &str) {
+        let expr = expr.trim().trim_end_matches(';');
+        let code = format!(
+            "fn main() {{\n    println!(\"{{:?}}\", {{ {} }});\n}}",
+            expr
+        );
+        self.start_unscored(RunPurpose::Eval(expr.to_string()), code);
+    }
+
+    fn finish_eval(&mut self, expr: &str, result: anyhow::Result<ValidationResult>) {
+        match Self::unscored(result) {
+            Ok(Ok(output)) => {
+                self.message = t!("eval-result", expr = expr, value = output);
+                self.message_style = Style::default().fg(Color::Cyan);
+            }
+            Ok(Err(err)) => {
+                self.message = format!(
+                    "{}\n\n```\n{}\n```",
+                    t!("eval-compile-error", expr = expr),
+                    err
+                );
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Err(e) => {
+                self.message = t!("scratch-failed", error = e.to_string());
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+    }
+
+    /// Compiles and runs `code` with the game's validator, outside any room.
+    fn start_unscored(&mut self, purpose: RunPurpose, code: String) {
+        let validator = Arc::clone(&self.validator);
+        // Against an empty expected output, anything printed comes back as "got"
+        self.start_run(purpose, move || validator.validate(&code, ""));
+    }
+
+    /// What an unscored run printed, or why it didn't compile.
+    fn unscored(
+        result: anyhow::Result<ValidationResult>,
+    ) -> anyhow::Result<Result<String, String>> {
+        Ok(match result? {
+            ValidationResult::Success => Ok(String::new()),
+            ValidationResult::WrongOutput { got, .. } => Ok(got),
+            ValidationResult::CompileError(err) | ValidationResult::LintError(err) => Err(err.full),
+            ValidationResult::Rejected(err)
+            | ValidationResult::HiddenTestFailed { report: err, .. } => Err(err),
+            ValidationResult::Timeout(limit) => Err(t!("run-timed-out", seconds = limit.as_secs())),
+        })
+    }
+
+    pub(crate) fn advance_room(&mut self) {
+        if self.state == GameState::RoomComplete {
+            if let Some(item) = &self.pending_item {
+                let needed = self.needed_items();
+                self.drop_menu = drop_menu(&self.inventory, &needed, !needed.contains(&item.name));
+                self.state = GameState::InventoryFull;
+                return;
+            }
+            // Ahead of the altar and the event deck, so nothing is repeated
+            // while the player goes back for what the door needs
+            if self.door_locked() {
+                return;
+            }
+            if let Some(altar) = self.altar_here() {
+                self.altar_menu = altar_menu(altar, self.hp, self.max_hp(), self.gold);
+                self.state = GameState::Altar;
+                return;
+            }
+        }
+        if matches!(self.state, GameState::RoomComplete | GameState::Altar) && self.shop_here() {
+            self.open_shop("shop-welcome-between");
+            return;
+        }
+        if matches!(
+            self.state,
+            GameState::RoomComplete | GameState::Altar | GameState::Shop
+        ) && self.current_room + 1 < self.rooms.len()
+        {
+            let room = (self.current_level * 100 + self.current_room) as u64;
+            if let Some(card) = self
+                .deck
+                .draw(anim::scramble(self.seed ^ EVENT_SALT ^ room))
+            {
+                self.event = Some(card.clone());
+                self.state = GameState::Event;
+                return;
+            }
+        }
+        if self.door_locked() {
+            return;
+        }
+        let door_msg = self.pass_door();
+        if self.current_room + 1 < self.rooms.len() {
+            let next_room = &self.rooms[self.current_room + 1];
+            // Check if next room has entry narrative for transition
+            if let Some(entry) = &next_room.narrative.entry {
+                self.state = GameState::RoomTransition;
+                self.message = format!(
+                    "{}{}\n\n─────────────────────────────────\n{}",
+                    door_msg,
+                    entry.trim(),
+                    t!("press-enter-continue")
+                );
+                self.message_style = Style::default().fg(Color::Cyan);
+                self.message_scroll = 0;
+                self.start_typewriter();
+            } else {
+                self.load_room(self.current_room + 1);
+                self.message.insert_str(0, &door_msg);
+            }
+        } else {
+            if self.settings.autosave == Autosave::EveryFloor {
+                self.save_profile();
+            }
+            self.state = GameState::LevelComplete;
+            self.check_achievements(Moment::Floor, self.floor_times.iter().sum());
+            if self.victory() {
+                self.record_win();
+                self.check_achievements(Moment::Run, self.run_clock.elapsed().as_secs());
+            }
+            self.message_scroll = 0;
+            self.start_celebration(t!("celebration-level"));
+            let perfect = self.hints_used_total == 0 && self.compile_errors_total == 0;
+            let inventory_display = if self.inventory.is_empty() {
+                format!("  {}", t!("inventory-empty"))
+            } else {
+                self.inventory
+                    .iter()
+                    .map(|i| format!("  - {}", i.name))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+
+            let level_name = self.puzzles.floor_name(self.current_level);
+            let mut next_action = self.next_floor_prompt();
+            if let Some(win) = self
+                .campaign
+                .win
+                .as_ref()
+                .filter(|_| self.current_level >= self.puzzles.floor_count())
+            {
+                let verdict = match win.when.holds(&self.standing()) {
+                    true => "campaign-won",
+                    false => "campaign-lost",
+                };
+                next_action = format!(
+                    "{}\n\n{}",
+                    t!(verdict, goal = win.description.as_str()),
+                    next_action
+                );
+            }
+            let medals = self.medal_tally(Some(self.current_level));
+            let time = |secs: Option<u64>| {
+                secs.map(|secs| format_duration(std::time::Duration::from_secs(secs)))
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            let floor_time = (!self.floor_times.is_empty()).then(|| self.floor_times.iter().sum());
+            let stats = [
+                ("stats-rooms-cleared", self.rooms.len().to_string()),
+                (
+                    "stats-compile-errors",
+                    self.compile_errors_total.to_string(),
+                ),
+                ("stats-hints-used", self.hints_used_total.to_string()),
+                ("stats-floor-time", time(floor_time)),
+                (
+                    "stats-fastest-room",
+                    time(self.floor_times.iter().min().copied()),
+                ),
+                ("stats-under-par", self.floor_under_par.to_string()),
+                ("stats-gold-medals", medals[0].to_string()),
+                ("stats-silver-medals", medals[1].to_string()),
+                ("stats-bronze-medals", medals[2].to_string()),
+                ("stats-gold-earned", self.gold.to_string()),
+                ("stats-hp-remaining", self.hp.to_string()),
+            ]
+            .iter()
+            .map(|(id, value)| format!("║  {:<15}{:>7}  ║", t!(*id), value))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+            self.message = format!(
+                "{}{}\n\n{}\n\n\
+                ╔══════════════════════════╗\n\
+                ║  {:<24}║\n\
+                ╠══════════════════════════╣\n\
+                {}\n\
+                ╚══════════════════════════╝\n\n\
+                {}\n{}\n\n\
+                {}",
+                door_msg,
+                t!("level-complete-banner", level = self.current_level),
+                if perfect {
+                    t!("level-mastered-perfect", name = level_name)
+                } else {
+                    t!("level-mastered", name = level_name)
+                },
+                t!("stats-title"),
+                stats,
+                t!("inventory-heading"),
+                inventory_display,
+                next_action
+            );
+            self.message_style = Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD);
+        }
+    }
+
+    /// The altar waiting after the current room, if the floor has one there.
+    pub(crate) fn altar_here(&self) -> Option<&Altar> {
+        self.floor
+            .altar
+            .as_ref()
+            .filter(|altar| altar.after_room == self.current_room + 1)
+    }
+
+    /// Makes one trade at the altar, or walks on to the next room.
+    pub(crate) fn use_altar(&mut self, option: AltarOption) {
+        let Some(altar) = self.altar_here().cloned() else {
+            return;
+        };
+        match option {
+            AltarOption::OfferGold => {
+                let hp = altar
+                    .hp_per_offering
+                    .min(self.max_hp().saturating_sub(self.hp));
+                let gold = hp * altar.gold_per_hp;
+                self.gold -= gold;
+                self.hp += hp;
+                self.message = t!("altar-healed", hp = hp, gold = gold);
+                self.message_style = Style::default().fg(Color::Green);
+            }
+            AltarOption::OfferBlood => {
+                let hp = altar.hp_per_offering;
+                let gold = hp * altar.gold_per_hp;
+                self.hp -= hp;
+                self.gold += gold;
+                self.message = t!("altar-bled", hp = hp, gold = gold);
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            AltarOption::Leave => {
+                self.advance_room();
+                return;
+            }
+        }
+        self.sound.play(sound::Effect::Item);
+        self.message_scroll = 0;
+        self.altar_menu = altar_menu(&altar, self.hp, self.max_hp(), self.gold);
+    }
+
+    /// Whether the floor's shop opens after the room just cleared, rather
+    /// than after its last room.
+    pub(crate) fn shop_here(&self) -> bool {
+        !self.floor.shop.is_empty()
+            && self.floor.shop_after_room == Some(self.current_room + 1)
+            && self.current_room + 1 < self.rooms.len()
+    }
+
+    fn open_shop(&mut self, welcome: &str) {
+        self.restock();
+        self.message = t!(welcome);
+        self.message_style = Style::default().fg(Color::Yellow);
+        self.message_scroll = 0;
+        self.state = GameState::Shop;
+    }
+
+    /// Rebuilds the shop's menu after a purchase, keeping the cursor on the
+    /// same ware so a supply can be bought again.
+    fn restock(&mut self) {
+        let selected = self.shop_menu.selected();
+        let leave = if self.shop_here() {
+            "shop-walk-on"
+        } else {
+            "shop-leave"
+        };
+        self.shop_menu = shop_menu(
+            &self.floor.shop,
+            &self.inventory,
+            self.gold,
+            (self.hp, self.max_hp()),
+            leave,
+        );
+        if let Some(option) = selected.filter(|_| self.state == GameState::Shop) {
+            self.shop_menu.select(option);
+        }
+    }
+
+    /// Enter at the end of a floor: its shop first, if it has one that
+    /// didn't open between rooms, then a recap of the floor, then the floor
+    /// below.
+    pub(crate) fn descend(&mut self) -> Result<(), String> {
+        let between_rooms = matches!(
+            self.floor.shop_after_room,
+            Some(after) if after < self.rooms.len()
+        );
+        if self.state == GameState::LevelComplete && !self.floor.shop.is_empty() && !between_rooms {
+            self.open_shop("shop-welcome");
+            return Ok(());
+        }
+        if self.state != GameState::FloorRecap {
+            self.recap_ahead = self.load_manifest(self.current_level + 1).concepts;
+            self.recap_scroll = 0;
+            self.state = GameState::FloorRecap;
+            return Ok(());
+        }
+        self.load_level(self.current_level + 1)?;
+        let notes = self.apply_carryover(self.floor.carryover);
+        if !notes.is_empty() {
+            self.message = format!("{}\n\n{}", notes.join("\n"), self.message);
+        }
+        Ok(())
+    }
+
+    /// Resets whatever `carryover` doesn't let into the floor. Returns a line
+    /// for each thing that was reset.
+    fn apply_carryover(&mut self, carryover: Carryover) -> Vec<String> {
+        let mut notes = Vec::new();
+        if !carryover.gold && self.gold > 0 {
+            self.gold = 0;
+            notes.push(t!("carryover-gold"));
+        }
+        if !carryover.items && (!self.inventory.is_empty() || !self.supplies.is_empty()) {
+            for item in self.inventory.iter() {
+                self.modifiers.remove(&item.name, Source::Item);
+            }
+            self.inventory.clear();
+            self.supplies = Supplies::default();
+            self.pending_item = None;
+            notes.push(t!("carryover-items"));
+        }
+        if !carryover.hp && self.hp != self.max_hp() {
+            self.hp = self.max_hp();
+            notes.push(t!("carryover-hp", hp = self.hp));
+        }
+        notes
+    }
+
+    /// Buys one of the floor's wares, or leaves the shop for the next room
+    /// or the floor below.
+    pub(crate) fn use_shop(&mut self, option: ShopOption) -> Result<(), String> {
+        let ware = match option {
+            ShopOption::Buy(index) => match self.floor.shop.get(index) {
+                Some(ware) => ware.clone(),
+                None => return Ok(()),
+            },
+            ShopOption::Leave if self.shop_here() => {
+                self.advance_room();
+                return Ok(());
+            }
+            ShopOption::Leave => return self.descend(),
+        };
+        if let Some(effect) = ware.effect {
+            self.buy_supply(&ware, effect);
+            return Ok(());
+        }
+        let item = Item::from_ware(&ware);
+        let (name, adjustments) = (item.name.clone(), item.modifiers.clone());
+        let description = item.description.clone();
+        if self.gold < ware.price || self.inventory.add(item).is_err() {
+            return Ok(());
+        }
+        self.gold -= ware.price;
+        self.modifiers.add(name, Source::Item, adjustments);
+        self.sound.play(sound::Effect::Item);
+        self.message = t!(
+            "shop-bought",
+            item = ware.name.as_str(),
+            gold = ware.price,
+            description = description.trim()
+        );
+        self.message_style = Style::default().fg(Color::Green);
+        self.message_scroll = 0;
+        self.restock();
+        Ok(())
+    }
+
+    /// Buys a ware that's used up rather than carried: a potion works at
+    /// once, the rest are kept until they're needed.
+    fn buy_supply(&mut self, ware: &Ware, effect: Consumable) {
+        if self.gold < ware.price {
+            return;
+        }
+        self.gold -= ware.price;
+        let note = match effect {
+            Consumable::Potion { hp } => {
+                let hp = hp.min(self.max_hp().saturating_sub(self.hp));
+                self.hp += hp;
+                t!("shop-potion", hp = hp)
+            }
+            Consumable::Hint => {
+                self.supplies.hints += 1;
+                t!("shop-hint-scroll")
+            }
+            Consumable::IntentScroll => {
+                self.supplies.intent_scrolls += 1;
+                t!("shop-intent-scroll")
+            }
+            Consumable::Shield => {
+                self.supplies.shields += 1;
+                t!("shop-shield")
+            }
+        };
+        self.sound.play(sound::Effect::Item);
+        self.message = t!(
+            "shop-bought-supply",
+            item = ware.name.as_str(),
+            gold = ware.price,
+            note = note
+        );
+        self.message_style = Style::default().fg(Color::Green);
+        self.message_scroll = 0;
+        self.restock();
+    }
+
+    /// Blessings and curses always happen; a trade needs the HP and gold it asks for.
+    pub(crate) fn can_accept(&self, card: &EventCard) -> bool {
+        card.kind != EventKind::Trade
+            || (i64::from(self.hp) + i64::from(card.hp) > 0
+                && i64::from(self.gold) + i64::from(card.gold) >= 0
+                && card.takes_item.as_ref().is_none_or(|item| {
+                    self.inventory.contains(item) && !self.needed_items().contains(item)
+                }))
+    }
+
+    /// Applies the drawn card (a trade only if `accept`) and moves on. Curses
+    /// can weaken the player but never finish them off.
+    pub(crate) fn resolve_event(&mut self, accept: bool) {
+        let Some(card) = self.event.take() else {
+            return;
+        };
+        if (accept || card.kind != EventKind::Trade) && self.can_accept(&card) {
+            self.hp =
+                (i64::from(self.hp) + i64::from(card.hp)).clamp(1, i64::from(self.max_hp())) as u32;
+            // A curse on an empty purse takes what there is
+            self.gold = (i64::from(self.gold) + i64::from(card.gold)).max(0) as u32;
+            self.modifiers
+                .add(card.title.clone(), Source::Event, card.modifiers.clone());
+            if let Some(item) = &card.takes_item {
+                self.drop_item(item);
+            }
+            self.sound.play(match card.kind {
+                EventKind::Curse => sound::Effect::Failure,
+                _ => sound::Effect::Item,
+            });
+        }
+        self.advance_room();
+    }
+
+    pub(crate) fn run_solution(&mut self) {
+        if self.pending_run.is_some() {
+            return;
+        }
+        if let Some(test) = self.focused_test {
+            self.run_test(test);
+            return;
+        }
+        let code = self.editor.lines().join("\n");
+        self.last_run_code = code.clone();
+        self.last_output = None;
+        self.attempts_room += 1;
+        if let Some((_, verdict)) = self
+            .echo
+            .as_ref()
+            .filter(|(hash, _)| *hash == hash_code(&code))
+        {
+            let verdict = verdict.clone();
+            self.message_scroll = 0;
+            self.mark_errors(&verdict);
+            self.finish_solution(Ok(verdict), std::time::Duration::ZERO);
+            self.message = format!("{}\n\n{}", t!("run-echo"), self.message);
+            return;
+        }
+        let challenge = &self.room().challenge;
+        let check = challenge.check.clone();
+        let spec = challenge.run_spec();
+        let sandbox = challenge.sandbox.clone();
+        let clippy_clean = challenge.require_clippy_clean;
+        let validator = Arc::clone(&self.validator);
+        self.start_run(RunPurpose::Room, move || {
+            check.run(&*validator, &code, &spec, &sandbox, clippy_clean)
+        });
+    }
+
+    /// Runs the solution against one hidden test only. It costs nothing and
+    /// can't clear the room, so it doesn't count as an attempt.
+    fn run_test(&mut self, test: usize) {
+        let code = self.editor.lines().join("\n");
+        self.last_run_code = code.clone();
+        self.last_output = None;
+        let challenge = &self.room().challenge;
+        let check = challenge.check.clone();
+        let spec = RunSpec {
+            only_test: Some(test),
+            ..challenge.run_spec()
+        };
+        let sandbox = challenge.sandbox.clone();
+        let validator = Arc::clone(&self.validator);
+        self.start_run(RunPurpose::Test(test), move || {
+            check.run(&*validator, &code, &spec, &sandbox, false)
+        });
+    }
+
+    fn finish_test(&mut self, test: usize, result: anyhow::Result<ValidationResult>) {
+        let (number, total) = (test + 1, self.room().challenge.tests.len());
+        match result {
+            Ok(ValidationResult::Success) => {
+                self.focused_test = None;
+                self.failed_test = None;
+                self.message = t!("test-focus-passed", number = number, total = total);
+                self.message_style = Style::default().fg(Color::Green);
+            }
+            Ok(ValidationResult::CompileError(err)) => {
+                self.last_compile_output = Some(err);
+                self.compile_errors_expanded = false;
+                self.show_compile_errors();
+            }
+            Ok(ValidationResult::Timeout(limit)) => {
+                self.message = t!("run-timed-out", seconds = limit.as_secs());
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Ok(
+                ValidationResult::HiddenTestFailed { report, .. }
+                | ValidationResult::Rejected(report),
+            ) => {
+                self.message = format!(
+                    "{}\n\n```\n{}\n```",
+                    t!("test-focus-failed", number = number, total = total),
+                    report
+                );
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Ok(ValidationResult::LintError(_) | ValidationResult::WrongOutput { .. }) => {
+                self.message = t!("test-focus-failed", number = number, total = total);
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Err(e) => {
+                self.message = t!("system-error", error = e.to_string());
+                self.message_style = Style::default().fg(Color::Magenta);
+            }
+        }
+    }
+
+    /// Ctrl+T: pins the last failed hidden test's input under the editor so
+    /// F5 runs only that test, or unpins it so F5 runs every check again.
+    pub(crate) fn toggle_test_focus(&mut self) {
+        let total = self.room().challenge.tests.len();
+        if self.focused_test.take().is_some() {
+            self.message = t!("test-focus-off");
+        } else if let Some(test) = self.failed_test.filter(|test| *test < total) {
+            self.focused_test = Some(test);
+            self.message = t!("test-focus-on", number = test + 1, total = total);
+        } else {
+            self.message = t!("test-focus-none");
+        }
+        self.message_style = Style::default().fg(Color::DarkGray);
+        self.message_scroll = 0;
+    }
+
+    /// Hands `work` to a worker thread and shows the spinner until
+    /// [`poll_run`](Self::poll_run) picks up its verdict.
+    fn start_run(
+        &mut self,
+        purpose: RunPurpose,
+        work: impl FnOnce() -> anyhow::Result<ValidationResult> + Send + 'static,
+    ) {
+        self.message_scroll = 0;
+        self.pending_run = Some(PendingRun {
+            purpose,
+            job: compiler::worker::Job::spawn(work),
+            started: std::time::Instant::now(),
+            return_to: self.state,
+        });
+        self.state = GameState::Compiling;
+        self.needs_redraw = true;
+    }
+
+    /// Finishes the pending run if its verdict is in. Returns whether it was.
+    pub(crate) fn poll_run(&mut self) -> bool {
+        let Some(result) = self
+            .pending_run
+            .as_ref()
+            .and_then(|run| run.job.try_result())
+        else {
+            return false;
+        };
+        let Some(run) = self.pending_run.take() else {
+            return false;
+        };
+        self.state = run.return_to;
+        self.needs_redraw = true;
+        // What `:eval` compiles isn't in the editor
+        if !matches!(run.purpose, RunPurpose::Eval(_)) {
+            match &result {
+                Ok(verdict) => self.mark_errors(verdict),
+                Err(_) => self.error_markers.clear(),
+            }
+        }
+        match run.purpose {
+            RunPurpose::Room => self.finish_solution(result, run.started.elapsed()),
+            RunPurpose::Test(test) => self.finish_test(test, result),
+            RunPurpose::Scratch => self.finish_scratch(result),
+            RunPurpose::Eval(expr) => self.finish_eval(&expr, result),
+        }
+        true
+    }
+
+    /// Marks where `verdict`'s errors are in the editor, or clears the marks
+    /// of the run before.
+    fn mark_errors(&mut self, verdict: &ValidationResult) {
+        self.error_markers = match verdict {
+            ValidationResult::CompileError(errors) | ValidationResult::LintError(errors) => {
+                errors.markers.clone()
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    /// Esc while compiling: kills the run. A room run cancelled this way
+    /// doesn't count as an attempt.
+    pub(crate) fn cancel_run(&mut self) {
+        let Some(run) = self.pending_run.take() else {
+            return;
+        };
+        run.job.cancel();
+        self.state = run.return_to;
+        if matches!(run.purpose, RunPurpose::Room) {
+            self.attempts_room = self.attempts_room.saturating_sub(1);
+        }
+        self.message = t!("run-cancelled");
+        self.message_style = Style::default().fg(Color::DarkGray);
+    }
+
+    fn finish_solution(
+        &mut self,
+        result: anyhow::Result<ValidationResult>,
+        elapsed: std::time::Duration,
+    ) {
+        let failed =
+            matches!(&result, Ok(verdict) if !matches!(verdict, ValidationResult::Success));
+        if elapsed >= notify::SLOW_RUN {
+            self.slow_verdict = Some(t!(match &result {
+                Ok(ValidationResult::Success) => "verdict-cleared",
+                Ok(ValidationResult::CompileError(_)) => "verdict-compile-error",
+                Ok(ValidationResult::LintError(_)) => "verdict-lint-error",
+                Ok(ValidationResult::WrongOutput { .. }) => "verdict-wrong-output",
+                Ok(ValidationResult::Rejected(_) | ValidationResult::HiddenTestFailed { .. }) => {
+                    "verdict-rejected"
+                }
+                Ok(ValidationResult::Timeout(_)) => "verdict-timeout",
+                Err(_) => "verdict-failed",
+            }));
+        }
+        if matches!(&result, Ok(verdict) if !matches!(verdict, ValidationResult::CompileError(_))) {
+            self.compile_streak = 0;
+        }
+        // Verdicts the same code would get again; a timeout might not recur,
+        // and output a room matches loosely may differ from run to run
+        let exact = self.room().challenge.output_match == OutputMatch::Exact;
+        self.echo = match &result {
+            Ok(
+                verdict @ (ValidationResult::CompileError(_)
+                | ValidationResult::LintError(_)
+                | ValidationResult::Rejected(_)
+                | ValidationResult::HiddenTestFailed { .. }),
+            ) => Some((hash_code(&self.last_run_code), verdict.clone())),
+            Ok(verdict @ ValidationResult::WrongOutput { .. }) if exact => {
+                Some((hash_code(&self.last_run_code), verdict.clone()))
+            }
+            _ => None,
+        };
+
+        match result {
+            Ok(ValidationResult::Success) => {
+                self.state = GameState::RoomComplete;
+                self.run_save_due |= self.settings.autosave == Autosave::EveryRoom;
+                self.run_marks
+                    .push(if self.hints_used_room + self.oracle_hints_room == 0 {
+                        card::RoomMark::Perfect
+                    } else {
+                        card::RoomMark::Hinted
+                    });
+                self.record_telemetry("cleared");
+                self.run_splits.push(ghost::Split {
+                    room_id: self.room().meta.id.clone(),
+                    title: self.room().meta.title.clone(),
+                    secs: self.run_clock.elapsed().as_secs(),
+                });
+                let elapsed = self.room_clock.elapsed();
+                let earned = self.room_reward();
+                self.gold += earned;
+                let mut time_msg = self.record_clear_time(elapsed);
+                if let Some(bonus) = self.par_bonus(elapsed.as_secs()) {
+                    self.gold += bonus;
+                    self.floor_under_par += 1;
+                    time_msg.push_str(&format!("  {}", t!("par-bonus", gold = bonus)));
+                }
+                self.floor_times.push(elapsed.as_secs());
+                self.check_achievements(Moment::Room, elapsed.as_secs());
+                self.archive_solution();
+
+                // Collect item if room grants one; clearing the room again
+                // mustn't hand it out twice
+                let item = self
+                    .room()
+                    .rewards
+                    .as_ref()
+                    .and_then(Item::from_rewards)
+                    .filter(|item| !self.inventory.contains(&item.name));
+                self.sound.play(if item.is_some() {
+                    sound::Effect::Item
+                } else {
+                    sound::Effect::Success
+                });
+                let item_msg = item
+                    .map(|item| format!("\n\n{}", self.take_item(item)))
+                    .unwrap_or_default();
+
+                // Collect codex entry if room has one
+                let codex_msg = if let Some(entry) = self.room().codex.clone() {
+                    let title = entry.title.clone();
+                    let concept = self.room().meta.concept.clone();
+                    // Only add if not already in codex (avoid duplicates on replay)
+                    if self.campaign_progress().codex.insert(title.clone()) {
+                        self.profile_changed();
+                    }
+                    if self.codex.add(entry, self.current_level, &concept) {
+                        format!("\n\n{}", t!("codex-updated", title = title))
+                    } else {
+                        String::new()
+                    }
+                } else {
+                    String::new()
+                };
+
+                let alt = self
+                    .room()
+                    .narrative
+                    .alternative_solution
+                    .as_ref()
+                    .map(|s| format!("\n\n{}", t!("alternative-approach", solution = s.as_str())))
+                    .unwrap_or_default();
+
+                self.message = format!(
+                    "{}\n{}\n\n{}{}{}{}",
+                    if self.hints_used_room == 0 {
+                        t!("room-cleared-perfect", gold = earned)
+                    } else {
+                        t!("room-cleared", gold = earned)
+                    },
+                    time_msg,
+                    self.room().narrative.success,
+                    item_msg,
+                    codex_msg,
+                    alt
+                );
+                self.message_style = Style::default().fg(Color::Yellow);
+                self.start_celebration(t!("celebration-room"));
+                self.start_typewriter();
+            }
+            Ok(ValidationResult::CompileError(err)) => {
+                self.sound.play(sound::Effect::Failure);
+                self.compile_errors_total += 1;
+                self.run_compile_errors += 1;
+                for code in telemetry::error_codes(&err.full) {
+                    if !self.room_error_codes.contains(&code) {
+                        self.room_error_codes.push(code);
+                    }
+                }
+                self.last_compile_output = Some(err);
+                self.compile_errors_expanded = false;
+                let shielded = self.supplies.shields > 0;
+                if shielded {
+                    self.supplies.shields -= 1;
+                } else {
+                    self.hp = self.hp.saturating_sub(self.wrong_answer_cost());
+                }
+                self.compile_streak += 1;
+                let granted = !self.mercy
+                    && self
+                        .modifiers
+                        .difficulty()
+                        .mercy_after()
+                        .is_some_and(|after| self.compile_streak >= after);
+                self.mercy |= granted;
+                self.show_compile_errors();
+                if granted {
+                    self.message = format!("{}\n\n{}", t!("mercy-granted"), self.message);
+                }
+                if shielded {
+                    let note = t!("shield-used", count = self.supplies.shields);
+                    self.message = format!("{}\n\n{}", note, self.message);
+                }
+            }
+            Ok(ValidationResult::LintError(lints)) => {
+                self.sound.play(sound::Effect::Failure);
+                self.hp = self.hp.saturating_sub(self.wrong_answer_cost());
+                self.message = format!("{}\n\n```\n{}\n```", t!("lint-failed"), lints.full);
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Ok(ValidationResult::WrongOutput { expected, got }) => {
+                self.sound.play(sound::Effect::Failure);
+                self.hp = self.hp.saturating_sub(self.wrong_answer_cost());
+                // Line counts and the first difference only mean something
+                // when the output has to match word for word
+                let output_match = self.room().challenge.output_match;
+                let exact = output_match == OutputMatch::Exact;
+                let expected_lines = expected.lines().count();
+                let got_lines = got.lines().count();
+                let line_hint = if !exact {
+                    String::new()
+                } else if got_lines > expected_lines {
+                    format!(
+                        "\n\n{}",
+                        t!(
+                            "output-too-long",
+                            got = got_lines,
+                            expected = expected_lines
+                        )
+                    )
+                } else if got_lines < expected_lines {
+                    format!(
+                        "\n\n{}",
+                        t!(
+                            "output-too-short",
+                            got = got_lines,
+                            expected = expected_lines
+                        )
+                    )
+                } else {
+                    String::new()
+                };
+                let pinpoint = match mismatch::find(&expected, &got).filter(|_| exact) {
+                    Some(mismatch) => {
+                        let hint = self
+                            .room()
+                            .challenge
+                            .output_hints
+                            .iter()
+                            .find(|h| h.line == mismatch.line + 1)
+                            .map(|h| format!("\n{}", t!("mismatch-hint", hint = h.hint.as_str())))
+                            .unwrap_or_default();
+                        format!(
+                            "\n\n{}\n```\n{}\n```{}",
+                            t!(
+                                "mismatch-at",
+                                line = mismatch.line + 1,
+                                column = mismatch.column + 1
+                            ),
+                            mismatch.excerpt(),
+                            hint
+                        )
+                    }
+                    None => String::new(),
+                };
+                self.message = format!(
+                    "{}{}\n\n{}\n```\n{}\n```\n\n{}\n```\n{}\n```{}\n\n{}",
+                    self.room().narrative.failure_output,
+                    pinpoint,
+                    t!(match output_match {
+                        OutputMatch::Exact => "output-expected",
+                        OutputMatch::Regex => "output-expected-regex",
+                        OutputMatch::Contains => "output-expected-contains",
+                        OutputMatch::LinesUnordered => "output-expected-unordered",
+                    }),
+                    expected,
+                    t!("output-got"),
+                    got,
+                    line_hint,
+                    t!("output-compare-hint")
+                );
+                self.message_style = Style::default().fg(Color::Red);
+                self.last_output = Some((expected, got));
+            }
+            Ok(ValidationResult::Rejected(reason)) => {
+                self.sound.play(sound::Effect::Failure);
+                self.hp = self.hp.saturating_sub(self.wrong_answer_cost());
+                self.message = format!(
+                    "{}\n\n```\n{}\n```",
+                    self.room().narrative.failure_output,
+                    reason
+                );
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Ok(ValidationResult::HiddenTestFailed { test, report }) => {
+                self.sound.play(sound::Effect::Failure);
+                self.hp = self.hp.saturating_sub(self.wrong_answer_cost());
+                self.failed_test = Some(test);
+                self.message = format!(
+                    "{}\n\n```\n{}\n```\n\n{}",
+                    self.room().narrative.failure_output,
+                    report,
+                    t!("test-focus-offer")
+                );
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Ok(ValidationResult::Timeout(limit)) => {
+                self.sound.play(sound::Effect::Failure);
+                self.hp = self.hp.saturating_sub(self.wrong_answer_cost());
+                let narrative = match &self.room().narrative.failure_timeout {
+                    Some(text) => text.clone(),
+                    None => t!("timeout-narrative"),
+                };
+                self.message = format!(
+                    "{}\n\n{}",
+                    narrative,
+                    t!("run-timed-out", seconds = limit.as_secs())
+                );
+                self.message_style = Style::default().fg(Color::Red);
+            }
+            Err(e) => {
+                self.message = t!("system-error", error = e.to_string());
+                self.message_style = Style::default().fg(Color::Magenta);
+            }
+        }
+        if failed {
+            self.punish_extra_attempt();
+            self.offer_variant();
+        }
+    }
+
+    /// Points a player who keeps failing at `:summon`, if the room has
+    /// another incarnation to give.
+    fn offer_variant(&mut self) {
+        if self.attempts_room >= VARIANT_OFFER_AFTER
+            && !self.room().challenge.variants.is_empty()
+            && self.coop.is_none()
+        {
+            self.message = format!("{}\n\n{}", self.message, t!("summon-offer"));
+        }
+    }
+
+    /// The room's `max_attempts`, if it sets one.
+    pub(crate) fn max_attempts(&self) -> Option<u32> {
+        self.room().scoring.as_ref().and_then(|s| s.max_attempts)
+    }
+
+    /// Applies the room's `out_of_attempts` once a failed run goes past its
+    /// `max_attempts`, adding what happened to the message.
+    fn punish_extra_attempt(&mut self) {
+        if self
+            .max_attempts()
+            .is_none_or(|max| self.attempts_room <= max)
+        {
+            return;
+        }
+        let consequence = self
+            .room()
+            .scoring
+            .as_ref()
+            .map(|s| s.out_of_attempts.clone())
+            .unwrap_or_default();
+        let note = match consequence {
+            OutOfAttempts::ExtraHp { hp } => {
+                let hp = self.modifiers.apply(Stat::WrongAnswerHp, hp);
+                self.hp = self.hp.saturating_sub(hp);
+                t!("attempts-extra-hp", hp = hp)
+            }
+            OutOfAttempts::ForcedHint => match self.pay_for_hint() {
+                Some(hint) => t!("attempts-forced-hint", hint = hint),
+                None => {
+                    let hp = self.hint_cost();
+                    self.hp = self.hp.saturating_sub(hp);
+                    t!("attempts-extra-hp", hp = hp)
+                }
+            },
+            OutOfAttempts::Counterattack { hp, text } => {
+                let hp = self.modifiers.apply(Stat::WrongAnswerHp, hp);
+                self.hp = self.hp.saturating_sub(hp);
+                let code = self.room().challenge.code.trim().to_string();
+                self.set_editor_lines(code.lines().map(String::from).collect());
+                format!(
+                    "{}\n{}",
+                    text.unwrap_or_else(|| t!("attempts-counterattack")),
+                    t!("attempts-counterattack-cost", hp = hp)
+                )
+            }
+        };
+        self.message = format!("{}\n\n{}", note, self.message);
+    }
+
+    /// Puts the last compile errors in the message pane, folded unless F8
+    /// expanded them.
+    fn show_compile_errors(&mut self) {
+        let Some(errors) = &self.last_compile_output else {
+            return;
+        };
+        let (text, toggle) = match &errors.folded {
+            Some(folded) if !self.compile_errors_expanded => (
+                folded.as_str(),
+                format!("\n\n{}", t!("compile-errors-folded")),
+            ),
+            Some(_) => (
+                errors.full.as_str(),
+                format!("\n\n{}", t!("compile-errors-expanded")),
+            ),
+            None => (errors.full.as_str(), String::new()),
+        };
+        // Under mercy, what the errors mean comes before what rustc said
+        let explained = if self.mercy {
+            explain_errors(&errors.full)
+        } else {
+            String::new()
+        };
+        self.message = format!(
+            "{}{}\n\n```\n{}\n```{}",
+            explained,
+            self.room().narrative.failure_compile,
+            text,
+            toggle
+        );
+        self.message_style = Style::default().fg(Color::Red);
+    }
+
+    /// F8: shows the notes and help that folding left out, or hides them again.
+    pub(crate) fn toggle_compile_errors(&mut self) {
+        if self.last_compile_output.is_none() {
+            self.message = t!("compile-errors-none");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        self.compile_errors_expanded = !self.compile_errors_expanded;
+        self.message_scroll = 0;
+        self.show_compile_errors();
+    }
+
+    /// Stows a new item and turns on its effect. If there's no slot for it,
+    /// it waits until the player chooses what to leave behind on the way out.
+    fn take_item(&mut self, item: Item) -> String {
+        let acquired = t!(
+            "item-acquired",
+            item = item.name.as_str(),
+            description = item.description.as_str()
+        );
+        let (name, adjustments) = (item.name.clone(), item.modifiers.clone());
+        match self.inventory.add(item) {
+            Ok(()) => {
+                self.modifiers.add(name, Source::Item, adjustments);
+                acquired
+            }
+            Err(item) => {
+                self.pending_item = Some(item);
+                format!(
+                    "{}\n{}",
+                    acquired,
+                    t!("inventory-full", capacity = self.inventory.capacity())
+                )
+            }
+        }
+    }
+
+    /// Leaves an item behind, along with its effect. Returns false if nothing
+    /// droppable goes by that name.
+    fn drop_item(&mut self, name: &str) -> bool {
+        match self.inventory.remove(name) {
+            Some(item) => {
+                self.modifiers.remove(&item.name, Source::Item);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The door into the next room, or the floor's exit after the last room.
+    fn next_door(&self) -> Option<&Door> {
+        match self.rooms.get(self.current_room + 1) {
+            Some(next) => next.meta.door.as_ref(),
+            None => self.floor.exit.as_ref(),
+        }
+    }
+
+    /// Says why the door ahead won't open, if it won't.
+    fn door_locked(&mut self) -> bool {
+        let Some(door) = self.next_door().cloned() else {
+            return false;
+        };
+        if self.inventory.contains(&door.requires_item) {
+            return false;
+        }
+        self.message = door.locked_text.map_or_else(
+            || t!("door-locked", item = door.requires_item.as_str()),
+            |text| text.trim().to_string(),
+        );
+        self.message_style = Style::default().fg(Color::Magenta);
+        true
+    }
+
+    /// Goes through the door ahead, leaving its item in the lock if the door
+    /// keeps it. Returns a paragraph saying what happened, or nothing.
+    fn pass_door(&mut self) -> String {
+        let Some(door) = self.next_door().cloned() else {
+            return String::new();
+        };
+        let item = door.requires_item.as_str();
+        if door.consumes {
+            self.drop_item(item);
+            format!("{}\n\n", t!("door-takes-item", item = item))
+        } else {
+            format!("{}\n\n", t!("door-opens", item = item))
+        }
+    }
+
+    /// Items a door still ahead on this floor needs, including its exit.
+    /// They can't be dropped or traded away.
+    fn needed_items(&self) -> Vec<String> {
+        self.rooms
+            .iter()
+            .skip(self.current_room + 1)
+            .filter_map(|room| room.meta.door.as_ref())
+            .chain(self.floor.exit.as_ref())
+            .map(|door| door.requires_item.clone())
+            .collect()
+    }
+
+    /// Settles the full-pack dialog, then carries on to the next room.
+    pub(crate) fn make_room(&mut self, option: DropOption) {
+        let Some(item) = self.pending_item.take() else {
+            return;
+        };
+        if let DropOption::Drop(index) = option {
+            if let Some(name) = self.inventory.get(index).map(|i| i.name.clone()) {
+                self.drop_item(&name);
+                self.take_item(item);
+            }
+        }
+        self.state = GameState::RoomComplete;
+        self.advance_room();
+    }
+
+    /// `:drop <item>`: frees a slot.
+    pub(crate) fn drop_command(&mut self, name: &str) {
+        let name = name.trim();
+        if self
+            .needed_items()
+            .iter()
+            .any(|needed| needed.eq_ignore_ascii_case(name))
+        {
+            self.message = t!("inventory-drop-needed", item = name);
+            self.message_style = Style::default().fg(Color::Magenta);
+        } else if self.drop_item(name) {
+            self.message = t!("inventory-dropped", item = name);
+            self.message_style = Style::default().fg(Color::DarkGray);
+        } else {
+            self.message = t!("inventory-no-such-item", item = name);
+            self.message_style = Style::default().fg(Color::DarkGray);
+        }
+    }
+
+    pub(crate) fn show_inventory(&mut self) {
+        self.message_scroll = 0;
+        let glyphs = self.glyphs();
+        if self.inventory.is_empty() {
+            self.message = format!(
+                "{} {}\n\n  {}\n\n  {}",
+                glyphs.bag,
+                t!("inventory-title"),
+                t!("inventory-empty"),
+                t!("inventory-empty-hint")
+            );
+        } else {
+            let items: Vec<String> = self
+                .inventory
+                .iter()
+                .map(|item| {
+                    format!(
+                        "  {} {}  {}",
+                        glyphs.item_icon(&item.name),
+                        item.name,
+                        t!(match item.kind {
+                            ItemKind::Relic => "item-kind-relic",
+                            ItemKind::Key => "item-kind-key",
+                            ItemKind::Pack => "item-kind-pack",
+                        })
+                    )
+                })
+                .collect();
+            self.message = format!(
+                "{} {}\n\n{}\n\n  {}\n  {}",
+                glyphs.bag,
+                t!("inventory-title"),
+                items.join("\n"),
+                t!(
+                    "inventory-count",
+                    used = self.inventory.used(),
+                    capacity = self.inventory.capacity()
+                ),
+                t!("inventory-drop-hint")
+            );
+        }
+        if !self.supplies.is_empty() {
+            let supplies = [
+                ("inventory-hint-scrolls", self.supplies.hints),
+                ("inventory-intent-scrolls", self.supplies.intent_scrolls),
+                ("inventory-shields", self.supplies.shields),
+            ];
+            let lines: Vec<String> = supplies
+                .into_iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(key, count)| format!("  {}", t!(key, count = count)))
+                .collect();
+            self.message = format!(
+                "{}\n\n  {}\n{}",
+                self.message,
+                t!("inventory-supplies"),
+                lines.join("\n")
+            );
+        }
+        self.message_style = Style::default().fg(Color::Cyan);
+    }
+
+    /// `:intent`: why the room's locked lines are as they are. The first read
+    /// in a room spends an intent scroll; after that it's free.
+    pub(crate) fn read_intent(&mut self) {
+        self.message_scroll = 0;
+        let Some(intent) = self.room().challenge.locked_intent.clone() else {
+            self.message = t!("intent-none");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        };
+        if !self.intent_read {
+            if self.supplies.intent_scrolls == 0 {
+                self.message = t!("intent-no-scroll");
+                self.message_style = Style::default().fg(Color::DarkGray);
+                return;
+            }
+            self.supplies.intent_scrolls -= 1;
+            self.intent_read = true;
+        }
+        self.message = t!("intent", intent = intent.trim());
+        self.message_style = Style::default().fg(Color::Cyan);
+    }
+
+    /// `:errors`: moves the cursor to the next place the last run's compile
+    /// errors point at, wrapping around, and lists them all.
+    pub(crate) fn next_error(&mut self) {
+        self.message_scroll = 0;
+        let mut markers = self.error_markers.clone();
+        if markers.is_empty() {
+            self.message = t!("errors-none");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        markers.sort_by_key(|marker| (marker.line, marker.column));
+        let (row, col) = self.editor.cursor();
+        let current = markers
+            .iter()
+            .position(|marker| (marker.line, marker.column) > (row + 1, col + 1))
+            .unwrap_or(0);
+        self.jump_to_error(&markers[current]);
+        let lines: Vec<String> = markers
+            .iter()
+            .enumerate()
+            .map(|(i, marker)| {
+                format!(
+                    "{} {}:{}  {}",
+                    if i == current { "▸" } else { " " },
+                    marker.line,
+                    marker.column,
+                    marker.message
+                )
+            })
+            .collect();
+        self.message = format!(
+            "{}\n\n{}",
+            t!("errors-title", current = current + 1, total = markers.len()),
+            lines.join("\n")
+        );
+        self.message_style = Style::default().fg(Color::Red);
+    }
+
+    /// `:last-error`: moves the cursor to where the last run's first compile
+    /// error points, and shows what it says.
+    pub(crate) fn goto_last_error(&mut self) {
+        self.message_scroll = 0;
+        let Some(marker) = self.error_markers.first().cloned() else {
+            self.message = t!("errors-none");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        };
+        self.jump_to_error(&marker);
+        self.message = format!("{}:{}  {}", marker.line, marker.column, marker.message);
+        self.message_style = Style::default().fg(Color::Red);
+    }
+
+    fn jump_to_error(&mut self, marker: &compiler::Marker) {
+        self.editor.move_cursor(tui_textarea::CursorMove::Jump(
+            marker.line.saturating_sub(1) as u16,
+            marker.column.saturating_sub(1) as u16,
+        ));
+    }
+
+    /// `:map`: the floor's rooms, and what each locked door ahead needs.
+    pub(crate) fn show_map(&mut self) {
+        self.message_scroll = 0;
+        let mut lines = Vec::new();
+        for (i, room) in self.rooms.iter().enumerate() {
+            let status = match i.cmp(&self.current_room) {
+                std::cmp::Ordering::Less => t!("map-cleared"),
+                std::cmp::Ordering::Equal => t!("map-here"),
+                std::cmp::Ordering::Greater => String::new(),
+            };
+            let line = format!("  {}. {}  {}", i + 1, room.meta.title, status);
+            lines.push(line.trim_end().to_string());
+            if let Some(door) = room.meta.door.as_ref().filter(|_| i > self.current_room) {
+                lines.push(self.map_door(door));
+            }
+        }
+        lines.push(format!("  {}", t!("map-exit")));
+        if let Some(door) = &self.floor.exit {
+            lines.push(self.map_door(door));
+        }
+        self.message = format!(
+            "{}\n\n{}",
+            t!(
+                "map-title",
+                level = self.current_level,
+                name = self.puzzles.floor_name(self.current_level)
+            ),
+            lines.join("\n")
+        );
+        self.message_style = Style::default().fg(Color::Cyan);
+    }
+
+    /// A locked door's line on the map: what it needs, and whether it's carried.
+    fn map_door(&self, door: &Door) -> String {
+        let item = door.requires_item.as_str();
+        let id = match (self.inventory.contains(item), door.consumes) {
+            (true, false) => "map-door-open",
+            (true, true) => "map-door-open-keeps",
+            (false, _) => "map-door-locked",
+        };
+        format!("     {} {}", self.glyphs().key, t!(id, item = item))
+    }
+
+    /// Opens the codex on the entry `term` names, or says why it can't.
+    pub(crate) fn open_codex_at(&mut self, term: &str) {
+        if self.codex.open_at(term) {
+            self.state = GameState::ViewingCodex;
+        } else {
+            self.message = t!("codex-not-found", term = term.trim());
+            self.message_style = Style::default().fg(Color::DarkGray);
+        }
+    }
+
+    /// Jumps to the codex entry highlighted in the message pane, or failing
+    /// that in the room's narrative.
+    pub(crate) fn look_up_mention(&mut self) {
+        let mention = [self.message.as_str(), self.room().narrative.intro.as_str()]
+            .into_iter()
+            .find_map(|text| self.codex.first_mention(text))
+            .map(|(_, title)| title.to_string());
+        match mention {
+            Some(title) => self.open_codex_at(&title),
+            None => {
+                self.message = t!("codex-nothing-mentioned");
+                self.message_style = Style::default().fg(Color::DarkGray);
+            }
+        }
+    }
+
+    pub(crate) fn show_keys(&mut self) {
+        self.message_scroll = 0;
+        let scroll_key = if cfg!(target_os = "macos") {
+            "Fn+↑/↓"
+        } else {
+            "PgUp/Dn"
+        };
+        let oracle = if cfg!(feature = "ai-hints") {
+            format!("\n  {}", t!("keys-help-oracle"))
+        } else {
+            String::new()
+        };
+        let keyboard = match self.key_protocol {
+            input::KeyProtocol::Enhanced => t!("keys-protocol-enhanced"),
+            input::KeyProtocol::Legacy if std::env::var_os("TMUX").is_some() => {
+                t!("keys-protocol-tmux")
+            }
+            input::KeyProtocol::Legacy => t!("keys-protocol-legacy"),
+            input::KeyProtocol::Unknown => t!("keys-protocol-unknown"),
+        };
+        self.message = t!(
+            "keys-help",
+            keyboard = keyboard,
+            scroll = scroll_key,
+            oracle = oracle
+        );
+        self.message_style = Style::default().fg(Color::Cyan);
+        self.reading_keys = true;
+    }
+
+    pub(crate) fn delete_line(&mut self) {
+        let (row, _) = self.editor.cursor();
+        if self.is_line_locked(row) {
+            self.message = t!("line-sealed");
+            self.message_style = Style::default().fg(Color::Magenta);
+            return;
+        }
+        // Move to start of line, select to end, delete
+        self.editor.move_cursor(tui_textarea::CursorMove::Head);
+        self.editor.move_cursor(tui_textarea::CursorMove::End);
+        self.editor.start_selection();
+        self.editor.move_cursor(tui_textarea::CursorMove::Head);
+        self.editor.cut();
+        // Remove the now-empty line if not the only line
+        if self.editor.lines().len() > 1 {
+            self.editor.delete_newline();
+        }
+    }
+
+    pub(crate) fn goto_line(&mut self, line: usize) {
+        let max_line = self.editor.lines().len();
+        let target = line.min(max_line).saturating_sub(1);
+        // Move to top first, then down to target
+        self.editor.move_cursor(tui_textarea::CursorMove::Top);
+        for _ in 0..target {
+            self.editor.move_cursor(tui_textarea::CursorMove::Down);
+        }
+        self.editor.move_cursor(tui_textarea::CursorMove::Head);
+        self.message = t!("goto-line", line = target + 1, lines = max_line);
+        self.message_style = Style::default().fg(Color::DarkGray);
+    }
+
+    pub(crate) fn goto_top(&mut self) {
+        self.editor.move_cursor(tui_textarea::CursorMove::Top);
+        self.editor.move_cursor(tui_textarea::CursorMove::Head);
+    }
+
+    pub(crate) fn goto_bottom(&mut self) {
+        self.editor.move_cursor(tui_textarea::CursorMove::Bottom);
+        self.editor.move_cursor(tui_textarea::CursorMove::Head);
+    }
+
+    pub(crate) fn yank_line(&mut self) {
+        let (row, _) = self.editor.cursor();
+        if let Some(line) = self.editor.lines().get(row) {
+            self.yank_buffer = line.clone();
+            let shown = if self.yank_buffer.len() > 40 {
+                format!("{}...", &self.yank_buffer[..40])
+            } else {
+                self.yank_buffer.clone()
+            };
+            self.message = t!("yank-done", line = shown);
+            self.message_style = Style::default().fg(Color::DarkGray);
+        }
+    }
+
+    pub(crate) fn paste_line(&mut self) {
+        if self.yank_buffer.is_empty() {
+            self.message = t!("paste-empty");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        let (row, _) = self.editor.cursor();
+        if self.is_line_locked(row) {
+            self.message = t!("paste-locked");
+            self.message_style = Style::default().fg(Color::Magenta);
+            return;
+        }
+        // Go to end of current line, insert newline, then insert yanked content
+        self.editor.move_cursor(tui_textarea::CursorMove::End);
+        self.editor.insert_newline();
+        self.editor.insert_str(&self.yank_buffer);
+        self.message = t!("paste-done");
+        self.message_style = Style::default().fg(Color::DarkGray);
+    }
+
+    /// Notes a profile change, writing it now if autosave is after every room.
+    fn profile_changed(&mut self) {
+        self.profile_unsaved = true;
+        if self.settings.autosave == Autosave::EveryRoom {
+            self.save_profile();
+        }
+    }
+
+    /// Writes any profile changes still waiting; the frontend calls it on quit.
+    pub fn save_profile(&mut self) {
+        if !self.profile_unsaved {
+            return;
+        }
+        // Not fatal: worst case a best time or the onboarding is lost
+        match self.profile.save(self.settings.save_backups) {
+            Ok(()) => self.profile_unsaved = false,
+            Err(e) => tracing::warn!(error = %e, "could not save profile"),
+        }
+    }
+
+    /// The run as it stands, for Continue to pick up; None when no run is
+    /// going or it has ended.
+    fn snapshot(&self) -> Option<SaveGame> {
+        if !self.run_live {
+            return None;
+        }
+        let (level, room, cleared) = match self.state {
+            GameState::GameOver | GameState::TitleScreen | GameState::Attract => return None,
+            GameState::LevelComplete if self.current_level >= self.puzzles.floor_count() => {
+                return None
+            }
+            // Past the exit, so its door has already had its item. The shop
+            // is skipped on the way back in
+            GameState::Shop if self.shop_here() => (self.current_level, self.current_room, true),
+            GameState::LevelComplete | GameState::Shop | GameState::FloorRecap => {
+                (self.current_level + 1, 0, false)
+            }
+            // Likewise past the door into the next room
+            GameState::RoomTransition => (self.current_level, self.current_room + 1, false),
+            GameState::RoomComplete
+            | GameState::Altar
+            | GameState::Event
+            | GameState::InventoryFull => (self.current_level, self.current_room, true),
+            _ => (self.current_level, self.current_room, false),
+        };
+        let editor = match (&self.room_buffer, cleared || room != self.current_room) {
+            (_, true) => Vec::new(),
+            (Some(room), false) => room.editor.lines().to_vec(),
+            (None, false) => self.editor.lines().to_vec(),
+        };
+        // A run saved on the way down resumes on the floor below, so that
+        // floor's carryover has to be settled here
+        let carryover = if level == self.current_level {
+            Carryover::default()
+        } else {
+            self.load_manifest(level).carryover
+        };
+        Some(SaveGame {
+            level,
+            room,
+            cleared,
+            hp: if carryover.hp { self.hp } else { self.max_hp() },
+            gold: if carryover.gold { self.gold } else { 0 },
+            difficulty: self.modifiers.difficulty(),
+            items: match carryover.items {
+                true => self.inventory.iter().map(|i| i.name.clone()).collect(),
+                false => Vec::new(),
+            },
+            supplies: match carryover.items {
+                true => self.supplies,
+                false => Supplies::default(),
+            },
+            pending_item: self
+                .pending_item
+                .as_ref()
+                .filter(|_| carryover.items)
+                .map(|i| i.name.clone()),
+            events: self
+                .modifiers
+                .iter()
+                .filter(|m| m.source == Source::Event)
+                .map(|m| m.name.clone())
+                .collect(),
+            codex: self.codex.titles(),
+            secrets: self.secrets_found.clone(),
+            editor,
+            room_secs: self.room_elapsed().as_secs(),
+            run_secs: self.run_elapsed().as_secs(),
+            attempts_room: self.attempts_room,
+            incarnation: if cleared || room != self.current_room {
+                0
+            } else {
+                self.room().incarnation
+            },
+            hints_used_room: self.hints_used_room,
+            intent_read: self.intent_read,
+            hints_used_total: self.hints_used_total,
+            compile_errors_total: self.compile_errors_total,
+            run_hints: self.run_hints,
+            run_compile_errors: self.run_compile_errors,
+            medals: self.medals.clone(),
+            marks: self.run_marks.clone(),
+        })
+    }
+
+    /// Writes the run for Continue, or forgets it once the run is over. The
+    /// frontend calls it on quit; autosaves come through [`App::tick`].
+    pub fn save_run(&mut self) {
+        // Playback and co-op sessions aren't the player's own run to resume
+        if self.replay_label.is_some() || self.coop.is_some() {
+            return;
+        }
+        let result = match self.snapshot() {
+            Some(save) => save.save(self.settings.save_backups).map(|_| ()),
+            None if self.run_live => {
+                self.run_live = false;
+                SaveGame::delete()
+            }
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "could not save the run");
+        }
+    }
+
+    /// Writes the run now, or None if it isn't the player's own to resume or
+    /// there is nothing left to save.
+    fn save_now(&self) -> Option<anyhow::Result<std::path::PathBuf>> {
+        if self.replay_label.is_some() || self.coop.is_some() {
+            return None;
+        }
+        self.snapshot()
+            .map(|save| save.save(self.settings.save_backups))
+    }
+
+    /// `:w`: saves the run now and says where it went.
+    pub(crate) fn write_run(&mut self) {
+        (self.message, self.message_style) = match self.save_now() {
+            Some(Ok(path)) => (
+                t!("save-written", path = path.display().to_string()),
+                Style::default().fg(Color::Green),
+            ),
+            Some(Err(e)) => (
+                t!("save-failed", error = e.to_string()),
+                Style::default().fg(Color::Red),
+            ),
+            None => (t!("save-nothing"), Style::default().fg(Color::Yellow)),
+        };
+    }
+
+    /// Save & Quit: writes the run before leaving. False, with the error
+    /// shown, if it couldn't be written, so the player isn't sent off
+    /// thinking it was.
+    pub(crate) fn save_before_quit(&mut self) -> bool {
+        let Some(Err(e)) = self.save_now() else {
+            return true;
+        };
+        self.message = t!("save-failed", error = e.to_string());
+        self.message_style = Style::default().fg(Color::Red);
+        false
+    }
+
+    /// Picks up the run kept by [`App::save_run`].
+    pub(crate) fn continue_run(&mut self) {
+        let Some(save) = self.saved_run.take() else {
+            return;
+        };
+        if let Err(e) = self.start_at(save.level, save.room) {
+            tracing::warn!(level = save.level, room = save.room, error = %e, "saved run does not fit this pack");
+            self.message = t!("save-unusable");
+            self.message_style = Style::default().fg(Color::Red);
+            return;
+        }
+        // A resumed run's times can't be raced against an unbroken one
+        self.racing = false;
+        self.hp = save.hp;
+        self.gold = save.gold;
+        self.supplies = save.supplies;
+        self.modifiers = Modifiers::new(save.difficulty);
+
+        // Items and codex entries come back from the rooms and shops that
+        // grant them
+        let mut pack_items = Vec::new();
+        for level in 1..=self.puzzles.floor_count() {
+            for room in self.puzzles.load_floor(level).unwrap_or_default() {
+                if let Some(entry) = room.codex.clone() {
+                    if save.codex.contains(&entry.title) {
+                        self.codex.add(entry, level, &room.meta.concept);
+                    }
+                }
+                pack_items.extend(room.rewards.as_ref().and_then(Item::from_rewards));
+            }
+            let shop = self.load_manifest(level).shop;
+            let wares = shop.iter().filter(|ware| ware.effect.is_none());
+            pack_items.extend(wares.map(Item::from_ware));
+        }
+        let find = |name: &String| pack_items.iter().find(|item| item.name == *name).cloned();
+        // Packs first, so everything after them has the room
+        let mut items: Vec<Item> = save.items.iter().filter_map(find).collect();
+        items.sort_by_key(|item| item.kind != ItemKind::Pack);
+        for item in items {
+            let (name, adjustments) = (item.name.clone(), item.modifiers.clone());
+            if self.inventory.add(item).is_ok() {
+                self.modifiers.add(name, Source::Item, adjustments);
+            }
+        }
+        self.pending_item = save.pending_item.as_ref().and_then(find);
+        for title in &save.events {
+            if let Some(card) = self.deck.cards.iter().find(|card| card.title == *title) {
+                self.modifiers
+                    .add(card.title.clone(), Source::Event, card.modifiers.clone());
+            }
+        }
+
+        if save.incarnation > 0 {
+            for _ in 0..save.incarnation {
+                self.rooms[self.current_room].summon_variant();
+            }
+            self.load_room(self.current_room);
+        }
+        if !save.editor.is_empty() {
+            self.set_editor_lines(save.editor);
+        }
+        self.room_clock = Stopwatch::resume_from(std::time::Duration::from_secs(save.room_secs));
+        self.run_clock = Stopwatch::resume_from(std::time::Duration::from_secs(save.run_secs));
+        self.attempts_room = save.attempts_room;
+        self.hints_used_room = save.hints_used_room;
+        self.intent_read = save.intent_read;
+        self.hints_used_total = save.hints_used_total;
+        self.compile_errors_total = save.compile_errors_total;
+        self.run_hints = save.run_hints;
+        self.run_compile_errors = save.run_compile_errors;
+        self.medals = save.medals;
+        self.run_marks = save.marks;
+        self.secrets_found = save.secrets;
+        self.toasts.reset(self.hp, self.gold, &self.inventory);
+
+        // Onboarding was seen the first time round
+        if save.cleared {
+            self.state = GameState::RoomComplete;
+            self.message = t!("save-resumed-cleared");
+        } else {
+            self.state = GameState::Playing;
+            self.message = t!("save-resumed");
+        }
+        self.message_style = Style::default().fg(Color::Cyan);
+    }
+
+    /// Adds the code that just cleared the room to the archive and writes it.
+    fn archive_solution(&mut self) {
+        let Some(archive) = self.archive.as_mut() else {
+            return;
+        };
+        let room = &self.rooms[self.current_room];
+        archive.solutions.push(archive::Solution {
+            room_id: room.meta.id.clone(),
+            title: room.meta.title.clone(),
+            level: self.current_level,
+            solved_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            code: self.last_run_code.clone(),
+            secs: self.room_clock.elapsed().as_secs(),
+            attempts: self.attempts_room,
+            hints: self.hints_used_room,
+        });
+        // Not fatal: the clear still counts, only its code is lost
+        if let Err(e) = archive.save(self.settings.save_backups) {
+            tracing::warn!(error = %e, "could not save the solution archive");
+        }
+    }
+
+    /// `:archive`: lists the solutions that cleared rooms before.
+    pub(crate) fn show_archive(&mut self) {
+        match self.archive.as_mut() {
+            Some(archive) if !archive.is_empty() => {
+                archive.open();
+                self.state = GameState::ViewingArchive;
+            }
+            _ => {
+                self.message = t!("archive-empty");
+                self.message_style = Style::default().fg(Color::DarkGray);
+            }
+        }
+    }
+
+    /// Copies the highlighted archived solution into the scratch pad and
+    /// opens it there, leaving the room's code alone.
+    pub(crate) fn archive_to_scratch(&mut self) {
+        let Some(solution) = self.archive.as_ref().and_then(|a| a.selected()) else {
+            return;
+        };
+        let lines = solution.code.lines().map(String::from).collect();
+        let title = solution.title.clone();
+        self.close_scratch();
+        self.scratch_lines = lines;
+        self.toggle_scratch();
+        self.state = GameState::Playing;
+        self.message = t!("archive-copied", title = title);
+    }
+
+    /// Keeps the room's best time in the profile and awards its medal.
+    /// Returns the line about it for the victory message.
+    fn record_clear_time(&mut self, elapsed: std::time::Duration) -> String {
+        let secs = elapsed.as_secs();
+        let id = self.room().meta.id.clone();
+        let previous = self.profile.best_times.get(&id).copied();
+        if previous.is_none_or(|best| secs < best) {
+            self.profile.best_times.insert(id.clone(), secs);
+            self.profile_changed();
+        }
+        if self.campaign_progress().cleared.insert(id) {
+            self.profile_changed();
+        }
+
+        let time = format_duration(elapsed);
+        let time_msg = match previous {
+            Some(best) if secs < best => t!("room-time-new-best", time = time.as_str()),
+            Some(best) => {
+                let best = format_duration(std::time::Duration::from_secs(best));
+                t!("room-time", time = time.as_str(), best = best.as_str())
+            }
+            None => t!("room-time-first", time = time.as_str()),
+        };
+        let medal = self.room().scoring.as_ref().and_then(|s| s.medal(secs));
+        match medal {
+            Some(medal) => {
+                self.medals.push((self.current_level, medal));
+                format!("{}  {}", medal_name(medal), time_msg)
+            }
+            None => time_msg,
+        }
+    }
+
+    /// `:xyzzy`: the room's secret, if it has one.
+    pub(crate) fn find_secret(&mut self) {
+        if !self.room().meta.secret {
+            self.message = t!("secret-nothing");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        let id = self.room().meta.id.clone();
+        if !self.secrets_found.contains(&id) {
+            self.secrets_found.push(id);
+        }
+        self.message = t!("secret-room");
+        self.message_style = Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD);
+    }
+
+    /// The run's numbers, for the campaign's win condition.
+    pub(crate) fn standing(&self) -> Standing {
+        let rooms: Vec<Room> = (1..=self.puzzles.floor_count())
+            .filter_map(|level| self.puzzles.load_floor(level).ok())
+            .flatten()
+            .collect();
+        Standing {
+            hp: self.hp,
+            max_hp: self.max_hp(),
+            gold: self.gold,
+            codex: self.codex.len(),
+            codex_total: rooms.iter().filter(|room| room.codex.is_some()).count(),
+            items: self.inventory.len(),
+            secrets: self.secrets_found.len(),
+            secrets_total: rooms.iter().filter(|room| room.meta.secret).count(),
+            hints: self.run_hints,
+            errors: self.run_compile_errors,
+            floor_hints: self.hints_used_total,
+            floor_errors: self.compile_errors_total,
+            attempts: self.attempts_room,
+            time: self.run_clock.elapsed().as_secs(),
+        }
+    }
+
+    /// Whether the run is over and won: the last floor cleared, with the
+    /// campaign's win condition met if it has one.
+    pub(crate) fn victory(&self) -> bool {
+        self.state == GameState::LevelComplete
+            && self.current_level >= self.puzzles.floor_count()
+            && self
+                .campaign
+                .win
+                .as_ref()
+                .is_none_or(|win| win.when.holds(&self.standing()))
+    }
+
+    /// This campaign's entry in the profile.
+    fn campaign_progress(&mut self) -> &mut CampaignProgress {
+        let name = self.campaign.name.clone().unwrap_or_default();
+        self.profile.campaigns.entry(name).or_default()
+    }
+
+    /// Keeps the run's time as the campaign's best if it beats it.
+    fn record_win(&mut self) {
+        let secs = self.run_clock.elapsed().as_secs();
+        let progress = self.campaign_progress();
+        if progress.best_run.is_none_or(|best| secs < best) {
+            progress.best_run = Some(secs);
+            self.profile_changed();
+        }
+    }
+
+    /// Earns the achievements met at `moment`, with `time` the seconds the
+    /// room, floor or run took, and announces each one.
+    fn check_achievements(&mut self, moment: Moment, time: u64) {
+        let standing = Standing {
+            time,
+            ..self.standing()
+        };
+        let earned = achievements::check(
+            moment,
+            self.current_level,
+            &standing,
+            &self.profile.achievements,
+        );
+        for achievement in &earned {
+            self.profile.achievements.insert(achievement.id.clone());
+            self.toasts.announce(
+                t!("toast-achievement", title = achievement.title.as_str()),
+                ToastKind::Achievement,
+            );
+        }
+        if !earned.is_empty() {
+            self.profile_changed();
+        }
+    }
+
+    /// `:achievements`: every badge, earned ones first.
+    pub(crate) fn show_achievements(&mut self) {
+        self.message_scroll = 0;
+        let all = achievements::all();
+        let (earned, locked): (Vec<_>, Vec<_>) = all
+            .iter()
+            .partition(|achievement| self.profile.achievements.contains(&achievement.id));
+        let line = |mark: &str, achievement: &achievements::Achievement| {
+            format!(
+                "  {} {}  {}",
+                mark, achievement.title, achievement.description
+            )
+        };
+        let mut lines = vec![t!(
+            "achievements-heading",
+            earned = earned.len(),
+            total = all.len()
+        )];
+        lines.push(String::new());
+        lines.extend(earned.iter().map(|achievement| line("★", achievement)));
+        lines.extend(locked.iter().map(|achievement| line("·", achievement)));
+        self.message = lines.join("\n");
+        self.message_style = Style::default().fg(Color::Magenta);
+    }
+
+    /// `:campaigns` (or the title screen): every installed pack's progress.
+    pub(crate) fn show_campaigns(&mut self) {
+        self.campaigns = campaigns::summarize_all(self.puzzles.as_ref(), &self.profile);
+        self.campaigns_return = self.state;
+        self.state = GameState::ViewingCampaigns;
+    }
+
+    /// The credits, from the title screen, with each pack's author and
+    /// license from its manifest.
+    pub(crate) fn show_credits(&mut self) {
+        self.pack_credits = campaigns::credits(self.puzzles.as_ref());
+        self.state = GameState::Credits;
+    }
+
+    /// `:goal`: what the campaign asks for, and how the run measures up.
+    pub(crate) fn show_goal(&mut self) {
+        let Some(win) = &self.campaign.win else {
+            self.message = t!("goal-none");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        };
+        let standing = self.standing();
+        self.message = format!(
+            "{}\n\n{}",
+            t!(
+                "goal",
+                goal = win.description.as_str(),
+                condition = win.when.to_string()
+            ),
+            t!(
+                "goal-standing",
+                hp = standing.hp,
+                max_hp = standing.max_hp,
+                gold = standing.gold,
+                codex = standing.codex,
+                codex_total = standing.codex_total,
+                secrets = standing.secrets,
+                secrets_total = standing.secrets_total,
+                items = standing.items,
+                hints = standing.hints,
+                errors = standing.errors
+            )
+        );
+        self.message_style = Style::default().fg(if win.when.holds(&standing) {
+            Color::Green
+        } else {
+            Color::Yellow
+        });
+    }
+
+    /// How many of each medal (gold, silver, bronze) were won on `level`, or
+    /// over the whole run.
+    pub(crate) fn medal_tally(&self, level: Option<usize>) -> [usize; 3] {
+        let count = |medal| {
+            self.medals
+                .iter()
+                .filter(|&&(l, m)| m == medal && level.is_none_or(|level| level == l))
+                .count()
+        };
+        [
+            count(Medal::Gold),
+            count(Medal::Silver),
+            count(Medal::Bronze),
+        ]
+    }
+
+    /// HP a run starts with and can't be healed past, from the campaign.
+    pub(crate) fn max_hp(&self) -> u32 {
+        self.campaign.economy.starting_hp
+    }
+
+    /// The room's HP price for one hint, after modifiers.
+    pub(crate) fn hint_cost(&self) -> u32 {
+        let base = self
+            .room()
+            .scoring
+            .as_ref()
+            .and_then(|s| s.hint_penalty_hp)
+            .unwrap_or(self.campaign.economy.hint_hp);
+        self.modifiers.apply(Stat::HintHp, base)
+    }
+
+    /// What the next hint will actually cost: nothing while a hint scroll is left.
+    pub(crate) fn next_hint_cost(&self) -> u32 {
+        if self.supplies.hints > 0 {
+            0
+        } else {
+            self.hint_cost()
+        }
+    }
+
+    fn wrong_answer_cost(&self) -> u32 {
+        let base = self
+            .room()
+            .scoring
+            .as_ref()
+            .and_then(|s| s.wrong_answer_penalty_hp)
+            .unwrap_or(self.campaign.economy.wrong_answer_hp);
+        let cost = self.modifiers.apply(Stat::WrongAnswerHp, base);
+        if self.mercy {
+            cost.div_ceil(2)
+        } else {
+            cost
+        }
+    }
+
+    /// Gold for clearing the room: the campaign's `room_gold`, less
+    /// `gold_per_hint` per hint but at least `min_room_gold`, then modifiers.
+    fn room_reward(&self) -> u32 {
+        let economy = &self.campaign.economy;
+        let base = economy
+            .room_gold
+            .saturating_sub(self.hints_used_room as u32 * economy.gold_per_hint)
+            .max(economy.min_room_gold);
+        self.modifiers.apply(Stat::Gold, base)
+    }
+
+    /// Gold for clearing the room within its par time, after modifiers.
+    fn par_bonus(&self, secs: u64) -> Option<u32> {
+        let bonus = self.room().scoring.as_ref()?.par_bonus(secs)?;
+        Some(self.modifiers.apply(Stat::Gold, bonus))
+    }
+
+    /// `:hints`: the room's hints, to read the revealed ones again for free.
+    pub(crate) fn show_hints(&mut self) {
+        let total = self.room().narrative.hints.len();
+        if total == 0 {
+            self.message = t!("hint-index-none");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        self.hints_cursor = self.hints_used_room.min(total - 1);
+        self.state = GameState::ViewingHints;
+    }
+
+    /// Asks before spending HP on the next hint; once they run out, offers the
+    /// compiler spirit instead.
+    pub(crate) fn show_hint(&mut self) {
+        self.message_scroll = 0;
+        if self.hints_used_room < self.room().narrative.hints.len() {
+            self.ask_confirmation(PendingAction::RevealHint);
+        } else {
+            self.offer_spirit();
+        }
+    }
+
+    /// Pays for and shows the next hint; confirmed through `show_hint`.
+    pub(crate) fn reveal_hint(&mut self) {
+        let Some(hint) = self.pay_for_hint() else {
+            return;
+        };
+        self.message = t!("hint", hint = hint);
+        self.message_style = Style::default().fg(Color::Cyan);
+    }
+
+    /// Charges for the next hint and returns it, if the room has one left.
+    fn pay_for_hint(&mut self) -> Option<String> {
+        let hint = self
+            .room()
+            .narrative
+            .hints
+            .get(self.hints_used_room)
+            .cloned()?;
+        if self.supplies.hints > 0 {
+            self.supplies.hints -= 1;
+        } else {
+            self.hp = self.hp.saturating_sub(self.hint_cost());
+        }
+        self.hints_used_room += 1;
+        self.hints_used_total += 1;
+        self.run_hints += 1;
+        Some(hint)
+    }
+
+    /// The last hint tier: rustc's own suggested fix, for three times a
+    /// hint's HP. rustc works it out in the background; the player is asked
+    /// before paying once it has.
+    fn offer_spirit(&mut self) {
+        if let Some(suggestion) = &self.spirit_suggestion {
+            let lines = self.editor.lines().to_vec();
+            self.message = t!("spirit-waiting", suggestion = suggestion.describe(&lines));
+            self.message_style = Style::default().fg(Color::Magenta);
+            return;
+        }
+        if self.spirit_job.is_none() {
+            let code = self.editor.lines().join("\n");
+            self.spirit_job = Some(compiler::worker::Job::spawn(move || {
+                compiler::suggest::suggestions(&code)
+            }));
+        }
+        self.message = t!("spirit-summoning");
+        self.message_style = Style::default().fg(Color::Magenta);
+    }
+
+    fn finish_spirit(&mut self, result: anyhow::Result<Vec<compiler::suggest::Suggestion>>) {
+        let suggestion = match result {
+            Ok(suggestions) => suggestions.into_iter().next(),
+            Err(e) => {
+                tracing::warn!(error = %e, "could not ask rustc for suggestions");
+                None
+            }
+        };
+        let Some(suggestion) = suggestion else {
+            self.message = t!("hints-exhausted");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        };
+        self.spirit_offer = Some(suggestion);
+        self.ask_confirmation(PendingAction::SummonSpirit);
+    }
+
+    fn spirit_cost(&self) -> u32 {
+        3 * self.hint_cost()
+    }
+
+    /// Pays for and shows the spirit's fix; confirmed through `offer_spirit`.
+    pub(crate) fn buy_spirit(&mut self) {
+        let Some(suggestion) = self.spirit_offer.take() else {
+            return;
+        };
+        let lines = self.editor.lines().to_vec();
+        let penalty = self.spirit_cost();
+        self.hp = self.hp.saturating_sub(penalty);
+        self.hints_used_total += 1;
+        self.run_hints += 1;
+        self.message = t!(
+            "spirit-offer",
+            hp = penalty,
+            suggestion = suggestion.describe(&lines)
+        );
+        self.message_style = Style::default().fg(Color::Magenta);
+        self.spirit_suggestion = Some(suggestion);
+    }
+
+    /// `:accept`: applies the spirit's suggestion to the buffer.
+    pub(crate) fn accept_spirit(&mut self) {
+        let Some(suggestion) = self.spirit_suggestion.take() else {
+            self.message = t!("spirit-nothing");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        };
+        if let Some(&line) = suggestion
+            .lines()
+            .iter()
+            .find(|&&line| self.is_line_locked(line - 1))
+        {
+            self.message = t!("spirit-locked", line = line);
+            self.message_style = Style::default().fg(Color::Red);
+            return;
+        }
+        match suggestion.apply(self.editor.lines()) {
+            Some(lines) => {
+                self.set_editor_lines(lines);
+                self.message = t!("spirit-applied");
+                self.message_style = Style::default().fg(Color::Magenta);
+            }
+            None => {
+                self.message = t!("spirit-stale");
+                self.message_style = Style::default().fg(Color::DarkGray);
+            }
+        }
+    }
+
+    /// `:oracle`: an AI hint, charged only once it arrives.
+    #[cfg(feature = "ai-hints")]
+    pub(crate) fn start_oracle(&mut self) {
+        if self.oracle_rx.is_some() {
+            self.message = t!("oracle-busy");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        let Some(error) = self.last_compile_output.as_ref().map(|e| e.full.clone()) else {
+            self.message = t!("oracle-no-error");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        };
+        let question = crate::oracle::Question {
+            room_title: self.room().meta.title.clone(),
+            concept: self.room().meta.concept.clone(),
+            compiler_error: error,
+        };
+        self.oracle_rx = Some(crate::oracle::spawn_ask(
+            question,
+            self.settings.ai_hint_endpoint.clone(),
+            self.settings.ai_hint_model.clone(),
+        ));
+        self.message = t!("oracle-asking");
+        self.message_style = Style::default().fg(Color::Cyan);
+    }
+
+    #[cfg(feature = "ai-hints")]
+    pub(crate) fn finish_oracle(&mut self, result: Result<String, String>) {
+        self.message_scroll = 0;
+        match result {
+            Ok(hint) => {
+                // Twice a regular hint, because it reads your actual error
+                let penalty = 2 * self.hint_cost();
+                let gold = self.gold.min(ORACLE_GOLD);
+                self.hp = self.hp.saturating_sub(penalty);
+                self.gold -= gold;
+                self.oracle_hints_room += 1;
+                self.hints_used_total += 1;
+                self.run_hints += 1;
+                self.message = t!("oracle-answer", hp = penalty, gold = gold, hint = hint);
+                self.message_style = Style::default().fg(Color::Magenta);
+            }
+            Err(e) => {
+                self.message = t!("oracle-failed", error = e);
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+    }
+
+    pub(crate) fn toggle_side_by_side(&mut self) {
+        if self.last_output.is_none() {
+            self.message = t!("diff-nothing");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        self.side_by_side = !self.side_by_side;
+        self.message_scroll = 0;
+    }
+
+    pub(crate) fn ask_confirmation(&mut self, action: PendingAction) {
+        self.pending_action = action;
+        self.confirm_return = self.state;
+        self.state = GameState::Confirm;
+    }
+
+    /// Title and body lines describing what confirming the pending action will cost.
+    pub(crate) fn confirmation_prompt(&self) -> (String, Vec<String>) {
+        match self.pending_action {
+            PendingAction::Quit => {
+                let mut kept = vec![t!(
+                    "quit-keep-progress",
+                    level = self.current_level,
+                    room = self.current_room + 1,
+                    rooms = self.rooms.len()
+                )];
+                if self.gold > 0 {
+                    kept.push(t!("quit-keep-gold", gold = self.gold));
+                }
+                if !self.inventory.is_empty() {
+                    kept.push(t!("quit-keep-items", count = self.inventory.len()));
+                }
+                if !self.codex.is_empty() {
+                    kept.push(t!("quit-keep-codex", count = self.codex.len()));
+                }
+                let mut body = vec![t!("quit-body")];
+                body.extend(kept.into_iter().map(|l| format!("  - {}", l)));
+                body.push(String::new());
+                body.push(t!("quit-continue"));
+                (t!("quit-title"), body)
+            }
+            PendingAction::RestartRoom => (
+                t!("restart-title"),
+                vec![
+                    t!("restart-body", room = self.room().meta.title.as_str()),
+                    String::new(),
+                    format!("  - {}", t!("restart-lose-edits")),
+                    format!("  - {}", t!("restart-lose-hints")),
+                    format!("  - {}", t!("restart-lose-hp")),
+                ],
+            ),
+            PendingAction::RevealHint => {
+                let total = self.room().narrative.hints.len();
+                let cost = self.next_hint_cost();
+                let mut body = vec![
+                    t!(
+                        "hint-ask",
+                        number = self.hints_used_room + 1,
+                        total = total,
+                        hp = cost
+                    ),
+                    String::new(),
+                    format!(
+                        "  - {}",
+                        t!("hint-ask-left", count = total - self.hints_used_room - 1)
+                    ),
+                ];
+                if self.supplies.hints > 0 {
+                    body.push(format!(
+                        "  - {}",
+                        t!("hint-ask-scroll", count = self.supplies.hints)
+                    ));
+                }
+                if cost >= self.hp {
+                    body.push(format!("  - {}", t!("hint-ask-fatal")));
+                }
+                (t!("hint-ask-title"), body)
+            }
+            PendingAction::SummonSpirit => {
+                let cost = self.spirit_cost();
+                let mut body = vec![t!("spirit-ask", hp = cost)];
+                if cost >= self.hp {
+                    body.push(String::new());
+                    body.push(format!("  - {}", t!("hint-ask-fatal")));
+                }
+                (t!("spirit-ask-title"), body)
+            }
+        }
+    }
+
+    pub(crate) fn restart_room(&mut self) {
+        self.load_room(self.current_room);
+        self.message = t!("restart-done");
+    }
+
+    /// `:summon`: swaps the room's puzzle for its next variant. The clock,
+    /// attempts and hints paid for carry on; the editor starts over.
+    pub(crate) fn summon_variant(&mut self) {
+        let refusal = if self.room().challenge.variants.is_empty() {
+            Some(t!("summon-none"))
+        } else if self.coop.is_some() {
+            Some(t!("summon-coop"))
+        } else if self.attempts_room < VARIANT_OFFER_AFTER {
+            Some(t!(
+                "summon-too-soon",
+                attempts = VARIANT_OFFER_AFTER - self.attempts_room
+            ))
+        } else {
+            None
+        };
+        if let Some(refusal) = refusal {
+            self.message = refusal;
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        if self.room_buffer.is_some() {
+            self.toggle_scratch();
+        }
+        let index = self.current_room;
+        self.rooms[index].summon_variant();
+        let challenge = &self.rooms[index].challenge;
+        let code = challenge.code.trim().to_string();
+        self.locked_lines = challenge.locked_lines.clone();
+        self.last_run_code = code.clone();
+        self.set_editor_lines(code.lines().map(String::from).collect());
+        self.last_output = None;
+        self.last_compile_output = None;
+        self.echo = None;
+        self.intent_read = false;
+        self.failed_test = None;
+        self.focused_test = None;
+        if let Some(job) = self.spirit_job.take() {
+            job.cancel();
+        }
+        self.spirit_offer = None;
+        self.spirit_suggestion = None;
+        self.message_scroll = 0;
+        self.message = t!("summon-done");
+        self.message_style = Style::default().fg(Color::Magenta);
+    }
+
+    pub(crate) fn open_settings(&mut self, return_to: GameState) {
+        self.settings_return = return_to;
+        self.settings_selection = 0;
+        self.state = GameState::Settings;
+    }
+
+    /// Labels and current values for the settings screen, in display order.
+    pub(crate) fn settings_entries(&self) -> Vec<(String, String)> {
+        let on_off = |v: bool| t!(if v { "setting-on" } else { "setting-off" });
+        vec![
+            (t!("setting-typewriter"), on_off(self.settings.typewriter)),
+            (
+                t!("setting-celebrations"),
+                on_off(self.settings.celebrations),
+            ),
+            (
+                t!("setting-ascii-only"),
+                if !self.unicode_supported {
+                    t!("setting-on-auto")
+                } else {
+                    on_off(self.settings.ascii_only)
+                },
+            ),
+            (t!("setting-cheat-sheet"), on_off(self.settings.cheat_sheet)),
+            (t!("setting-theme"), self.theme.name().to_string()),
+            (
+                t!("setting-check-for-updates"),
+                on_off(self.settings.check_for_updates),
+            ),
+            (t!("setting-telemetry"), on_off(self.settings.telemetry)),
+            (
+                t!("setting-verdict-alert"),
+                self.settings.verdict_alert.name(),
+            ),
+            (t!("setting-autosave"), self.settings.autosave.name()),
+            (
+                t!("setting-break-reminder"),
+                match self.settings.break_reminder_minutes {
+                    0 => t!("setting-off"),
+                    minutes => t!("setting-minutes", minutes = minutes),
+                },
+            ),
+            (
+                t!("setting-pause-clock-while-reading"),
+                on_off(self.settings.pause_clock_while_reading),
+            ),
+            #[cfg(feature = "discord")]
+            (
+                t!("setting-discord"),
+                on_off(self.settings.discord_presence),
+            ),
+            #[cfg(feature = "sound")]
+            (t!("setting-sound"), on_off(self.settings.sound)),
+            #[cfg(feature = "sound")]
+            (t!("setting-volume"), format!("{}%", self.settings.volume)),
+        ]
+    }
+
+    pub(crate) fn toggle_setting(&mut self, index: usize) {
+        // Optional entries come after the fixed ones, in the order they're shown
+        #[cfg(feature = "sound")]
+        let sound_index = 11 + usize::from(cfg!(feature = "discord"));
+        match index {
+            0 => self.settings.typewriter = !self.settings.typewriter,
+            1 => self.settings.celebrations = !self.settings.celebrations,
+            2 => self.settings.ascii_only = !self.settings.ascii_only,
+            3 => self.settings.cheat_sheet = !self.settings.cheat_sheet,
+            4 => {
+                self.theme = self.theme.next();
+                self.settings.theme = self.theme;
+            }
+            5 => self.settings.check_for_updates = !self.settings.check_for_updates,
+            6 => self.settings.telemetry = !self.settings.telemetry,
+            7 => self.settings.verdict_alert = self.settings.verdict_alert.next(),
+            8 => self.settings.autosave = self.settings.autosave.next(),
+            9 => {
+                let minutes = &mut self.settings.break_reminder_minutes;
+                *minutes = match *minutes {
+                    0 => 25,
+                    25 => 45,
+                    45 => 60,
+                    _ => 0,
+                };
+                // Counting from now, not from when the session began
+                self.last_break = std::time::Instant::now();
+            }
+            10 => {
+                self.settings.pause_clock_while_reading = !self.settings.pause_clock_while_reading
+            }
+            #[cfg(feature = "discord")]
+            11 => self.settings.discord_presence = !self.settings.discord_presence,
+            #[cfg(feature = "sound")]
+            i if i == sound_index => self.settings.sound = !self.settings.sound,
+            #[cfg(feature = "sound")]
+            i if i == sound_index + 1 => self.settings.volume = self.settings.volume % 100 + 10,
+            _ => return,
+        }
+        self.sound
+            .configure(self.settings.sound, self.settings.volume);
+        if let Err(e) = self.settings.save() {
+            self.message = t!("settings-save-failed", error = e.to_string());
+            self.message_style = Style::default().fg(Color::Red);
+        }
+    }
+
+    pub(crate) fn export_codex(&mut self) {
+        if self.codex.is_empty() {
+            self.message = t!("codex-empty");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        let Some(home) = paths::home_dir() else {
+            self.message = t!("codex-no-home");
+            self.message_style = Style::default().fg(Color::Red);
+            return;
+        };
+        let path = home.join("rust-raid-codex.md");
+        match std::fs::write(
+            &path,
+            self.codex
+                .to_markdown(|level| self.puzzles.floor_name(level)),
+        ) {
+            Ok(()) => {
+                self.message = t!(
+                    "codex-exported",
+                    path = path.display().to_string(),
+                    count = self.codex.len()
+                );
+                self.message_style = Style::default().fg(Color::Green);
+            }
+            Err(e) => {
+                self.message = t!(
+                    "write-failed",
+                    path = path.display().to_string(),
+                    error = e.to_string()
+                );
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+    }
+
+    pub(crate) fn record_telemetry(&self, outcome: &str) {
+        if self.settings.telemetry {
+            telemetry::record(&self.room_outcome(outcome));
+        }
+    }
+
+    fn room_outcome(&self, outcome: &str) -> telemetry::RoomOutcome {
+        telemetry::RoomOutcome {
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            floor: self.current_level,
+            room_id: self.room().meta.id.clone(),
+            outcome: outcome.to_string(),
+            attempts: self.attempts_room,
+            hints: self.hints_used_room,
+            error_codes: self.room_error_codes.clone(),
+            solve_secs: self.room_clock.elapsed().as_secs(),
+            rating: None,
+            comment: None,
+        }
+    }
+
+    /// `:feedback`: a form to rate the room and say why.
+    pub(crate) fn open_feedback(&mut self) {
+        self.feedback = feedback::Draft::default();
+        self.state = GameState::Feedback;
+    }
+
+    /// Keeps the filled-in form in the feedback file, and in telemetry for
+    /// players who record it.
+    pub(crate) fn send_feedback(&mut self) {
+        let draft = std::mem::take(&mut self.feedback);
+        let comment = draft.comment.trim().to_string();
+        let entry = feedback::Feedback {
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            campaign: self.campaign.name.clone().unwrap_or_default(),
+            floor: self.current_level,
+            room_id: self.room().meta.id.clone(),
+            rating: draft.rating,
+            comment: comment.clone(),
+            at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        };
+        self.state = GameState::Playing;
+        self.message_scroll = 0;
+        match feedback::record(&entry) {
+            Ok(path) => {
+                self.message = t!("feedback-saved", path = path.display().to_string());
+                self.message_style = Style::default().fg(Color::Green);
+            }
+            Err(e) => {
+                self.message = t!("feedback-failed", error = e.to_string());
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+        if self.settings.telemetry {
+            telemetry::record(&telemetry::RoomOutcome {
+                rating: Some(draft.rating.id().to_string()),
+                comment: Some(comment).filter(|c| !c.is_empty()),
+                ..self.room_outcome("rated")
+            });
+        }
+    }
+
+    pub(crate) fn result_card(&self, victory: bool) -> String {
+        let mut rooms = self.run_marks.clone();
+        if !victory {
+            rooms.push(card::RoomMark::Fell);
+        }
+        card::render(&card::RunSummary {
+            victory,
+            floor: self.current_level,
+            seed: self.seed,
+            time: format_duration(self.run_clock.elapsed()),
+            gold: self.gold,
+            hints: self.run_hints,
+            errors: self.run_compile_errors,
+            rooms,
+        })
+    }
+
+    pub(crate) fn feed_snapshot(&self) -> feed::Snapshot {
+        let room = self.room();
+        feed::Snapshot {
+            screen: format!("{:?}", self.state),
+            floor: self.current_level,
+            floor_name: self.puzzles.floor_name(self.current_level),
+            room: self.current_room + 1,
+            room_count: self.rooms.len(),
+            room_id: room.meta.id.clone(),
+            room_title: room.meta.title.clone(),
+            hp: self.hp,
+            gold: self.gold,
+            attempts: self.attempts_room,
+            hints_used: self.hints_used_room,
+            elapsed_secs: self.room_clock.elapsed().as_secs(),
+        }
+    }
+
+    /// What Discord should show for the current screen.
+    pub(crate) fn presence_status(&self) -> presence::Status {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let (details, state) = match self.state {
+            GameState::TitleScreen
+            | GameState::Attract
+            | GameState::Credits
+            | GameState::Onboarding => (t!("presence-gate"), t!("presence-main-menu")),
+            _ => (
+                t!(
+                    "presence-floor",
+                    floor = self.current_level,
+                    name = self.puzzles.floor_name(self.current_level)
+                ),
+                t!(
+                    "presence-room",
+                    room = self.current_room + 1,
+                    rooms = self.rooms.len(),
+                    title = self.room().meta.title.as_str()
+                ),
+            ),
+        };
+        presence::Status {
+            details,
+            state,
+            started_at: now.saturating_sub(self.room_clock.elapsed().as_secs()) as i64,
+        }
+    }
+
+    pub(crate) fn start_sync(&mut self, direction: sync::Direction) {
+        if self.sync_rx.is_some() {
+            self.message = t!("sync-busy");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        self.sync_rx = Some(sync::spawn(direction, self.settings.sync_gist_id.clone()));
+        self.message = t!("sync-started");
+        self.message_style = Style::default().fg(Color::Cyan);
+    }
+
+    pub(crate) fn finish_sync(&mut self, result: Result<sync::Outcome, String>) {
+        match result {
+            Ok(outcome) => {
+                let id = match outcome.direction {
+                    sync::Direction::Push => "sync-pushed",
+                    sync::Direction::Pull => {
+                        // Pick up whatever progress just arrived
+                        self.profile = Profile::load();
+                        "sync-pulled"
+                    }
+                };
+                self.message = t!(id, files = outcome.files, gist = outcome.gist_id.as_str());
+                self.message_style = Style::default().fg(Color::Green);
+                if self.settings.sync_gist_id.as_deref() != Some(outcome.gist_id.as_str()) {
+                    self.settings.sync_gist_id = Some(outcome.gist_id);
+                    let _ = self.settings.save();
+                }
+            }
+            Err(e) => {
+                self.message = t!("sync-failed", error = e);
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+    }
+
+    /// Shows the bundled summary for a std item, e.g. `:doc Vec::push`.
+    pub(crate) fn show_doc(&mut self, query: &str) {
+        self.message_scroll = 0;
+        match stddoc::lookup(query) {
+            stddoc::Lookup::Found(item) => {
+                self.message = format!("{}\n\n  {}\n\n{}", item.path, item.signature, item.summary);
+                self.message_style = Style::default().fg(Color::Cyan);
+            }
+            stddoc::Lookup::Ambiguous(items) => {
+                let paths: Vec<&str> = items.iter().map(|item| item.path.as_str()).collect();
+                self.message = format!("{}\n\n  {}", t!("doc-ambiguous"), paths.join("\n  "));
+                self.message_style = Style::default().fg(Color::Yellow);
+            }
+            stddoc::Lookup::NotFound => {
+                self.message = t!("doc-not-found", query = query.trim());
+                self.message_style = Style::default().fg(Color::DarkGray);
+            }
+        }
+    }
+
+    /// F9 or `:explain [code]`: rustc's long explanation of an error code,
+    /// by default the first one in the last compile errors.
+    pub(crate) fn explain_error(&mut self, code: Option<&str>) {
+        self.message_scroll = 0;
+        let seen = self
+            .last_compile_output
+            .as_ref()
+            .map(|errors| telemetry::error_codes(&errors.full))
+            .unwrap_or_default();
+        let code = match code {
+            Some(code) => match compiler::explain::normalize(code) {
+                Some(code) => code,
+                None => {
+                    self.message = t!("explain-invalid", code = code.trim());
+                    self.message_style = Style::default().fg(Color::Red);
+                    return;
+                }
+            },
+            None => match seen.first() {
+                Some(code) => code.clone(),
+                None => {
+                    self.message = t!("explain-nothing");
+                    self.message_style = Style::default().fg(Color::DarkGray);
+                    return;
+                }
+            },
+        };
+        if self.explain_rx.is_some() {
+            self.message = t!("explain-busy");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        self.message = t!("explain-asking", code = code.as_str());
+        self.message_style = Style::default().fg(Color::DarkGray);
+        self.explain_rx = Some((code.clone(), compiler::explain::spawn_explain(code)));
+    }
+
+    fn finish_explain(&mut self, code: &str, result: Result<String, String>) {
+        self.message_scroll = 0;
+        match result {
+            Ok(text) => {
+                let seen = self
+                    .last_compile_output
+                    .as_ref()
+                    .map(|errors| telemetry::error_codes(&errors.full))
+                    .unwrap_or_default();
+                let others: Vec<&str> = seen
+                    .iter()
+                    .filter(|other| *other != code)
+                    .map(String::as_str)
+                    .collect();
+                let others = if others.is_empty() {
+                    String::new()
+                } else {
+                    format!("\n{}", t!("explain-others", codes = others.join(" ")))
+                };
+                self.message = format!(
+                    "# {}{}\n\n{}",
+                    t!("explain-title", code = code),
+                    others,
+                    text
+                );
+                self.message_style = Style::default().fg(Color::Cyan);
+            }
+            Err(e) => {
+                self.message = t!("explain-failed", code = code, error = e);
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+    }
+
+    /// Uploads the editor contents to the Rust Playground so the player can ask for help.
+    pub(crate) fn start_share(&mut self) {
+        if self.share_rx.is_some() {
+            self.message = t!("share-busy");
+            self.message_style = Style::default().fg(Color::DarkGray);
+            return;
+        }
+        let code = self.editor.lines().join("\n");
+        self.share_rx = Some(compiler::playground::spawn_share(code));
+        self.message = t!("share-started");
+        self.message_style = Style::default().fg(Color::Cyan);
+    }
+
+    pub(crate) fn finish_share(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(url) => {
+                self.message = if card::copy_to_clipboard(&url) {
+                    t!("share-done-copied", url = url)
+                } else {
+                    t!("share-done", url = url)
+                };
+                self.message_style = Style::default().fg(Color::Green);
+            }
+            Err(e) => {
+                self.message = t!("share-failed", error = e);
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+    }
+
+    pub(crate) fn write_bug_report(&mut self) {
+        let context = bugreport::Context {
+            room_id: Some(self.room().meta.id.clone()),
+            level: Some(self.current_level),
+            last_compile_output: self.last_compile_output.as_ref().map(|e| e.full.clone()),
+        };
+        match bugreport::write(&context) {
+            Ok(path) => {
+                self.message = t!("bugreport-written", path = path.display().to_string());
+                self.message_style = Style::default().fg(Color::Green);
+            }
+            Err(e) => {
+                self.message = t!("bugreport-failed", error = e.to_string());
+                self.message_style = Style::default().fg(Color::Red);
+            }
+        }
+    }
+
+    pub(crate) fn toggle_cheat_sheet(&mut self) {
+        self.settings.cheat_sheet = !self.settings.cheat_sheet;
+        // Remember the choice; failing to write it is harmless
+        let _ = self.settings.save();
+    }
+
+    /// The bindings most relevant to what the player is doing right now, as
+    /// (key, Fluent id of what it does).
+    pub(crate) fn cheat_sheet_entries(&self) -> Vec<(&'static str, &'static str)> {
+        if self.command_mode {
+            return vec![
+                ("Enter", "cheat-run-command"),
+                ("Esc", "cheat-cancel"),
+                ("Tab", "cheat-complete"),
+                ("↑/↓", "cheat-command-history"),
+                (":q", "cheat-quit"),
+                (":codex", "cheat-codex"),
+                (":inv", "cheat-inventory"),
+                (":map", "cheat-map"),
+                (":hint", "cheat-hint"),
+                (":diff", "cheat-compare-output"),
+                (":expected", "cheat-expected-output"),
+                (":5", "cheat-go-to-line-5"),
+                (":top :bot", "cheat-start-end"),
+                (":keys", "cheat-all-shortcuts"),
+            ];
+        }
+        match self.state {
+            GameState::RoomComplete => vec![
+                ("Enter", "cheat-next-room"),
+                ("PgUp/PgDn", "cheat-scroll-story"),
+                ("Esc", "cheat-back-to-editor"),
+            ],
+            GameState::RoomTransition => {
+                vec![
+                    ("Enter", "cheat-enter-room"),
+                    ("PgUp/PgDn", "cheat-scroll-story"),
+                ]
+            }
+            GameState::Shop => vec![
+                ("Up/Down", "cheat-choose-ware"),
+                ("Enter", "cheat-buy-ware"),
+                (
+                    "Esc",
+                    if self.shop_here() {
+                        "cheat-walk-on"
+                    } else {
+                        "cheat-leave-shop"
+                    },
+                ),
+            ],
+            GameState::Altar => vec![
+                ("Up/Down", "cheat-choose-offering"),
+                ("Enter", "cheat-make-offering"),
+                ("Esc", "cheat-leave-altar"),
+            ],
+            GameState::Event => vec![
+                ("Enter", "cheat-accept-event"),
+                ("Esc", "cheat-decline-event"),
+            ],
+            GameState::InventoryFull => vec![
+                ("Up/Down", "cheat-choose-item"),
+                ("Enter", "cheat-leave-item"),
+                ("Esc", "cheat-leave-new-item"),
+            ],
+            GameState::LevelComplete => vec![
+                ("Enter", "cheat-descend"),
+                ("PgUp/PgDn", "cheat-scroll-stats"),
+            ],
+            _ => {
+                let mut entries = vec![
+                    match self.focused_test {
+                        Some(_) => ("F5", "cheat-run-pinned-test"),
+                        None => ("F5", "cheat-run-code"),
+                    },
+                    ("F1", "cheat-hint-hp"),
+                    ("F3", "cheat-compare-output"),
+                    ("F4", "cheat-expected-output"),
+                    ("F7", "cheat-look-up-codex"),
+                    ("F8", "cheat-expand-errors"),
+                    ("F9", "cheat-explain-error"),
+                    ("Esc", "cheat-pause-menu"),
+                    (":", "cheat-command-mode"),
+                    ("Ctrl+Z", "cheat-undo"),
+                    ("Ctrl+D", "cheat-delete-line"),
+                    ("Ctrl+Y/P", "cheat-yank-paste"),
+                    ("PgUp/PgDn", "cheat-scroll-messages"),
+                    ("Shift+PgUp", "cheat-scroll-story"),
+                    ("F6", "cheat-hide-this-panel"),
+                ];
+                // Only worth the room once a hidden test has failed
+                if self.focused_test.is_some() {
+                    entries.insert(7, ("Ctrl+T", "cheat-unpin-test"));
+                } else if self.failed_test.is_some() {
+                    entries.insert(7, ("Ctrl+T", "cheat-pin-test"));
+                }
+                entries
+            }
+        }
+    }
+
+    pub(crate) fn toggle_expected(&mut self) {
+        self.show_expected = !self.show_expected;
+        self.message = if self.show_expected {
+            t!("expected-shown")
+        } else {
+            t!("expected-hidden")
+        };
+        self.message_style = Style::default().fg(Color::DarkGray);
+    }
+}
+
+fn medal_name(medal: Medal) -> String {
+    t!(match medal {
+        Medal::Gold => "medal-gold",
+        Medal::Silver => "medal-silver",
+        Medal::Bronze => "medal-bronze",
+    })
+}
+
+/// What each error code in `compiler_output` means, in plain words, for the
+/// codes the interface text explains.
+fn explain_errors(compiler_output: &str) -> String {
+    let explained: Vec<String> = telemetry::error_codes(compiler_output)
+        .into_iter()
+        .filter_map(|code| {
+            let id = format!("explain-{}", code);
+            i18n::has(&id).then(|| format!("{}: {}", code, t!(&id)))
+        })
+        .collect();
+    if explained.is_empty() {
+        return String::new();
+    }
+    format!("{}\n{}\n\n", t!("mercy-explained"), explained.join("\n"))
+}
+
+/// A hash of a room's code, to tell whether it changed since the last run.
+fn hash_code(code: &str) -> u64 {
+    let mut hasher = std::hash::DefaultHasher::new();
+    code.hash(&mut hasher);
+    hasher.finish()
+}
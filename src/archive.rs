@@ -0,0 +1,108 @@
+//! Every solution that cleared a room, kept in `archive.toml` under the data
+//! dir, and the state of the `:archive` screen that browses them.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::paths;
+use crate::store::{self, Versioned};
+
+/// The code that cleared a room, and how the clear went.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Solution {
+    pub room_id: String,
+    pub title: String,
+    pub level: usize,
+    /// Seconds since the Unix epoch
+    pub solved_at: u64,
+    pub code: String,
+    /// Time spent in the room
+    pub secs: u64,
+    pub attempts: u32,
+    pub hints: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Archive {
+    /// Oldest first
+    pub solutions: Vec<Solution>,
+    /// Highlighted row, counting from the newest solution
+    #[serde(skip)]
+    pub cursor: usize,
+    /// Whether the highlighted solution is open, and how far it's scrolled
+    #[serde(skip)]
+    pub reading: Option<u16>,
+}
+
+impl Versioned for Archive {
+    const VERSION: u32 = 1;
+}
+
+impl Archive {
+    pub fn load() -> Self {
+        archive_path()
+            .map(|path| store::load(&path))
+            .unwrap_or_default()
+    }
+
+    /// Writes the archive, keeping `backups` earlier copies.
+    pub fn save(&self, backups: usize) -> Result<()> {
+        let path = archive_path().ok_or_else(|| anyhow::anyhow!("No home directory found"))?;
+        store::save(&path, self, backups)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+
+    /// Solutions newest first, the order the screen lists them in.
+    pub fn newest_first(&self) -> impl Iterator<Item = &Solution> {
+        self.solutions.iter().rev()
+    }
+
+    /// The highlighted solution.
+    pub fn selected(&self) -> Option<&Solution> {
+        self.newest_first().nth(self.cursor)
+    }
+
+    /// Resets the view when the screen is opened.
+    pub fn open(&mut self) {
+        self.cursor = 0;
+        self.reading = None;
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        let last = self.solutions.len().saturating_sub(1) as i32;
+        self.cursor = (self.cursor as i32 + delta).clamp(0, last) as usize;
+    }
+}
+
+/// `solved_at` as `YYYY-MM-DD HH:MM`, in UTC.
+pub fn date(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let minutes = secs % 86_400 / 60;
+    // Howard Hinnant's civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+fn archive_path() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("archive.toml"))
+}
@@ -0,0 +1,196 @@
+//! The attract demo that plays when the title screen sits idle: the logo,
+//! a few codex entries, then one room being solved, over and over.
+
+use std::time::{Duration, Instant};
+
+use crate::puzzle::{CodexEntry, Room};
+
+/// How long the title screen waits for a key before the demo starts.
+pub const IDLE_AFTER: Duration = Duration::from_secs(120);
+
+const LOGO: Duration = Duration::from_secs(8);
+const FACT: Duration = Duration::from_secs(7);
+/// Time per deleted line in the replay, and typing speed for inserted ones.
+const DELETE_LINE: Duration = Duration::from_millis(400);
+const TYPE_CHARS_PER_SECOND: u128 = 30;
+/// How long the replay holds the finished code and "cleared" banner.
+const CLEARED: Duration = Duration::from_secs(4);
+/// Pause on the starter code before the first edit.
+const READ_PAUSE: Duration = Duration::from_secs(2);
+const FACTS_SHOWN: usize = 3;
+
+/// One line-level step from a room's starter code to its solution.
+#[derive(Debug, Clone, PartialEq)]
+enum Edit {
+    Keep(String),
+    Delete(String),
+    Insert(String),
+}
+
+/// A room solved on screen: the starter code edited into its solution.
+pub struct Replay {
+    title: String,
+    edits: Vec<Edit>,
+}
+
+/// What the demo shows at one moment.
+pub enum Phase<'a> {
+    Logo,
+    Fact(&'a CodexEntry),
+    Replay {
+        title: &'a str,
+        /// The code as far as it has been edited, with the row being typed on
+        lines: Vec<String>,
+        cursor: Option<usize>,
+        /// Set once the solution is complete and "run"
+        cleared: bool,
+    },
+}
+
+pub struct Demo {
+    started: Instant,
+    facts: Vec<CodexEntry>,
+    replay: Option<Replay>,
+}
+
+impl Demo {
+    /// Picks facts and a room from `floors`; `seed` decides which, so
+    /// consecutive demos don't repeat.
+    pub fn new(floors: &[Room], seed: u64) -> Self {
+        let entries: Vec<&CodexEntry> = floors.iter().filter_map(|r| r.codex.as_ref()).collect();
+        let facts = (0..entries.len().min(FACTS_SHOWN))
+            .map(|i| entries[(seed as usize + i) % entries.len()].clone())
+            .collect();
+        let solvable: Vec<&Room> = floors
+            .iter()
+            .filter(|r| r.challenge.solution.is_some())
+            .collect();
+        let replay = (!solvable.is_empty()).then(|| {
+            let room = solvable[seed as usize % solvable.len()];
+            Replay {
+                title: room.meta.title.clone(),
+                edits: diff_lines(
+                    &room.challenge.code,
+                    room.challenge.solution.as_deref().unwrap_or_default(),
+                ),
+            }
+        });
+        Demo {
+            started: Instant::now(),
+            facts,
+            replay,
+        }
+    }
+
+    /// Time since the demo started, for animations within a phase.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn phase(&self) -> Phase<'_> {
+        let replay_length = self.replay.as_ref().map_or(Duration::ZERO, Replay::length);
+        let cycle = LOGO + FACT * self.facts.len() as u32 + replay_length;
+        let mut at = Duration::from_nanos((self.elapsed().as_nanos() % cycle.as_nanos()) as u64);
+        if at < LOGO {
+            return Phase::Logo;
+        }
+        at -= LOGO;
+        if let Some(fact) = self.facts.get((at.as_nanos() / FACT.as_nanos()) as usize) {
+            return Phase::Fact(fact);
+        }
+        at -= FACT * self.facts.len() as u32;
+        match &self.replay {
+            Some(replay) => replay.at(at),
+            None => Phase::Logo,
+        }
+    }
+}
+
+impl Replay {
+    fn length(&self) -> Duration {
+        let edits: Duration = self.edits.iter().map(edit_time).sum();
+        READ_PAUSE + edits + CLEARED
+    }
+
+    fn at(&self, at: Duration) -> Phase<'_> {
+        let mut lines = Vec::new();
+        let mut cursor = None;
+        // Time left for edits; None once an edit is still under way
+        let mut left = at.checked_sub(READ_PAUSE);
+        for edit in &self.edits {
+            let needed = edit_time(edit);
+            match (edit, left) {
+                (Edit::Keep(line), _) => lines.push(line.clone()),
+                (Edit::Delete(_) | Edit::Insert(_), Some(time)) if time >= needed => {
+                    if let Edit::Insert(line) = edit {
+                        lines.push(line.clone());
+                    }
+                    left = Some(time - needed);
+                }
+                (Edit::Delete(line), Some(_)) => {
+                    cursor = Some(lines.len());
+                    lines.push(line.clone());
+                    left = None;
+                }
+                (Edit::Insert(line), Some(time)) => {
+                    let typed = time.as_millis() * TYPE_CHARS_PER_SECOND / 1000;
+                    cursor = Some(lines.len());
+                    lines.push(line.chars().take(typed as usize).collect());
+                    left = None;
+                }
+                (Edit::Delete(line), None) => lines.push(line.clone()),
+                (Edit::Insert(_), None) => {}
+            }
+        }
+        Phase::Replay {
+            title: &self.title,
+            lines,
+            cursor,
+            cleared: left.is_some(),
+        }
+    }
+}
+
+fn edit_time(edit: &Edit) -> Duration {
+    match edit {
+        Edit::Keep(_) => Duration::ZERO,
+        Edit::Delete(_) => DELETE_LINE,
+        Edit::Insert(line) => Duration::from_millis(
+            (line.chars().count() as u128 * 1000 / TYPE_CHARS_PER_SECOND) as u64 + 150,
+        ),
+    }
+}
+
+/// Line diff by longest common subsequence; rooms are short enough for the
+/// quadratic table.
+fn diff_lines(before: &str, after: &str) -> Vec<Edit> {
+    let a: Vec<&str> = before.trim_end().lines().collect();
+    let b: Vec<&str> = after.trim_end().lines().collect();
+    let mut common = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            common[i][j] = if a[i] == b[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut edits = Vec::new();
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            edits.push(Edit::Keep(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || common[i + 1][j] >= common[i][j + 1]) {
+            // Deleting first reads as "replace this line" on screen
+            edits.push(Edit::Delete(a[i].to_string()));
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(b[j].to_string()));
+            j += 1;
+        }
+    }
+    edits
+}
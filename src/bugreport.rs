@@ -0,0 +1,120 @@
+use anyhow::Result;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::{logging, paths};
+
+/// Game state worth including in a report; empty when run from the command line.
+#[derive(Default)]
+pub struct Context {
+    pub room_id: Option<String>,
+    pub level: Option<usize>,
+    pub last_compile_output: Option<String>,
+}
+
+/// How many trailing lines of the newest debug log go into a report.
+const LOG_LINES: usize = 200;
+
+/// Assembles everything a maintainer usually asks for into one plain-text report.
+pub fn build(context: &Context) -> String {
+    let mut out = String::from("# rust-raid bug report\n\n");
+    out.push_str(&format!(
+        "version:   {} (build {}, {})\n",
+        env!("CARGO_PKG_VERSION"),
+        env!("RUST_RAID_GIT_HASH"),
+        env!("RUST_RAID_BUILD_PROFILE")
+    ));
+    out.push_str(&format!(
+        "os:        {} {} (built for {})\n",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        env!("RUST_RAID_BUILD_TARGET")
+    ));
+    out.push_str(&format!("rustc:     {}\n", rustc_version()));
+    out.push_str(&format!("terminal:  {}\n", terminal_description()));
+    if let Some(level) = context.level {
+        out.push_str(&format!("floor:     {}\n", level));
+    }
+    if let Some(room) = &context.room_id {
+        out.push_str(&format!("room:      {}\n", room));
+    }
+
+    if let Some(output) = &context.last_compile_output {
+        out.push_str("\n## Last compile output\n\n");
+        out.push_str(output.trim_end());
+        out.push('\n');
+    }
+
+    out.push_str("\n## Recent log\n\n");
+    match recent_log() {
+        Some(log) => out.push_str(&log),
+        None => out.push_str("(no debug log found; run with --debug to record one)\n"),
+    }
+
+    redact(&out)
+}
+
+/// Writes the report next to the codex export (`~/rust-raid-bugreport.txt`).
+pub fn write(context: &Context) -> Result<PathBuf> {
+    let home = paths::home_dir().ok_or_else(|| anyhow::anyhow!("No home directory found"))?;
+    let path = home.join("rust-raid-bugreport.txt");
+    std::fs::write(&path, build(context))?;
+    Ok(path)
+}
+
+fn rustc_version() -> String {
+    Command::new("rustc")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_else(|| "not found on PATH".to_string())
+}
+
+fn terminal_description() -> String {
+    let var = |name: &str| std::env::var(name).unwrap_or_else(|_| "-".to_string());
+    let size = crossterm::terminal::size()
+        .map(|(w, h)| format!("{}x{}", w, h))
+        .unwrap_or_else(|_| "unknown size".to_string());
+    format!(
+        "TERM={} TERM_PROGRAM={} COLORTERM={} {}",
+        var("TERM"),
+        var("TERM_PROGRAM"),
+        var("COLORTERM"),
+        size
+    )
+}
+
+fn recent_log() -> Option<String> {
+    let dir = logging::log_dir()?;
+    let newest = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("rust-raid"))
+        .max_by_key(|e| e.file_name())?;
+    let content = std::fs::read_to_string(newest.path()).ok()?;
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.len().saturating_sub(LOG_LINES);
+    Some(lines[start..].join("\n") + "\n")
+}
+
+/// Strips the home directory and user name, which show up in paths and logs.
+fn redact(text: &str) -> String {
+    let mut text = text.to_string();
+    if let Some(home) = paths::home_dir() {
+        let home = home.to_string_lossy().to_string();
+        if home.len() > 1 {
+            text = text.replace(&home, "~");
+        }
+    }
+    for var in ["USER", "USERNAME"] {
+        if let Ok(user) = std::env::var(var) {
+            // Very short names would mangle unrelated words
+            if user.len() >= 3 {
+                text = text.replace(&user, "<user>");
+            }
+        }
+    }
+    text
+}
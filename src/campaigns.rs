@@ -0,0 +1,182 @@
+//! Every installed puzzle pack side by side, for the `:campaigns` dashboard:
+//! how much of each has been cleared, its best times and how much of its
+//! codex has been found, all from the profile. Also the same for each floor
+//! of one pack, for the title screen's floor select, and who made each pack,
+//! for the credits.
+
+use std::path::PathBuf;
+
+use crate::engine::{PuzzleDir, PuzzleSource};
+use crate::paths;
+use crate::profile::Profile;
+
+/// One campaign's row on the dashboard.
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub name: String,
+    /// Whether it's the pack being played
+    pub current: bool,
+    pub rooms: usize,
+    pub cleared: usize,
+    pub codex_total: usize,
+    pub codex_found: usize,
+    /// Fastest won run in seconds
+    pub best_run: Option<u64>,
+    /// The room best times added up, once every room has one
+    pub sum_of_best: Option<u64>,
+}
+
+impl Summary {
+    /// Rooms cleared at least once, as a whole percentage.
+    pub fn percent(&self) -> usize {
+        (self.cleared * 100).checked_div(self.rooms).unwrap_or(0)
+    }
+}
+
+/// One floor's row on the floor select.
+#[derive(Debug, Clone)]
+pub struct FloorProgress {
+    pub level: usize,
+    pub name: String,
+    pub rooms: usize,
+    /// Rooms cleared by any run
+    pub cleared: usize,
+    /// Whether a run may start here: the first floor, a floor after one
+    /// that has been cleared, or one already played
+    pub unlocked: bool,
+}
+
+/// One pack's lines in the credits, from its `campaign.toml`.
+#[derive(Debug, Clone)]
+pub struct Credit {
+    pub name: String,
+    pub author: Option<String>,
+    pub license: Option<String>,
+}
+
+/// Packs installed besides the one given on the command line: each directory
+/// under `packs` in the data dir, laid out like the bundled `puzzles`.
+pub fn installed() -> Vec<PathBuf> {
+    let Some(dir) = paths::data_dir().map(|dir| dir.join("packs")) else {
+        return Vec::new();
+    };
+    let mut packs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    packs.sort();
+    packs
+}
+
+/// The pack being played first, then the installed ones by name. A pack
+/// installed under the same name as the current one is only listed once.
+pub fn summarize_all(current: &dyn PuzzleSource, profile: &Profile) -> Vec<Summary> {
+    let mut summaries = vec![summarize(current, profile, true)];
+    let mut others: Vec<Summary> = installed()
+        .into_iter()
+        .map(|path| summarize(&PuzzleDir::new(path), profile, false))
+        .filter(|other| other.rooms > 0)
+        .collect();
+    others.sort_by(|a, b| a.name.cmp(&b.name));
+    for other in others {
+        if summaries.iter().all(|known| known.name != other.name) {
+            summaries.push(other);
+        }
+    }
+    summaries
+}
+
+/// The pack being played, then the installed ones by name, as
+/// [`summarize_all`] lists them.
+pub fn credits(current: &dyn PuzzleSource) -> Vec<Credit> {
+    let credit = |source: &dyn PuzzleSource| {
+        let campaign = source.load_campaign().unwrap_or_default();
+        Credit {
+            name: campaign.name.unwrap_or_default(),
+            author: campaign.author,
+            license: campaign.license,
+        }
+    };
+    let mut credits = vec![credit(current)];
+    let mut others: Vec<Credit> = installed()
+        .into_iter()
+        .map(|path| credit(&PuzzleDir::new(path)))
+        .collect();
+    others.sort_by(|a, b| a.name.cmp(&b.name));
+    for other in others {
+        if credits.iter().all(|known| known.name != other.name) {
+            credits.push(other);
+        }
+    }
+    credits
+}
+
+/// Each floor of `source`, in order, with how much of it has been cleared.
+pub fn floors(source: &dyn PuzzleSource, profile: &Profile) -> Vec<FloorProgress> {
+    let progress = profile
+        .campaigns
+        .get(&campaign_name(source))
+        .cloned()
+        .unwrap_or_default();
+    let mut floors: Vec<FloorProgress> = Vec::new();
+    for level in 1..=source.floor_count() {
+        let rooms = source.load_floor(level).unwrap_or_default();
+        let cleared = rooms
+            .iter()
+            .filter(|room| progress.cleared.contains(&room.meta.id))
+            .count();
+        let above_cleared = floors
+            .last()
+            .is_none_or(|above| above.rooms > 0 && above.cleared == above.rooms);
+        floors.push(FloorProgress {
+            level,
+            name: source.floor_name(level),
+            rooms: rooms.len(),
+            cleared,
+            unlocked: above_cleared || cleared > 0,
+        });
+    }
+    floors
+}
+
+fn campaign_name(source: &dyn PuzzleSource) -> String {
+    source
+        .load_campaign()
+        .ok()
+        .and_then(|campaign| campaign.name)
+        .unwrap_or_default()
+}
+
+fn summarize(source: &dyn PuzzleSource, profile: &Profile, current: bool) -> Summary {
+    let name = campaign_name(source);
+    let progress = profile.campaigns.get(&name).cloned().unwrap_or_default();
+    let rooms: Vec<_> = (1..=source.floor_count())
+        .filter_map(|level| source.load_floor(level).ok())
+        .flatten()
+        .collect();
+    let sum_of_best = rooms
+        .iter()
+        .map(|room| profile.best_times.get(&room.meta.id).copied())
+        .sum::<Option<u64>>()
+        .filter(|_| !rooms.is_empty());
+    Summary {
+        current,
+        rooms: rooms.len(),
+        cleared: rooms
+            .iter()
+            .filter(|room| progress.cleared.contains(&room.meta.id))
+            .count(),
+        codex_total: rooms.iter().filter(|room| room.codex.is_some()).count(),
+        codex_found: rooms
+            .iter()
+            .filter_map(|room| room.codex.as_ref())
+            .filter(|entry| progress.codex.contains(&entry.title))
+            .count(),
+        best_run: progress.best_run,
+        sum_of_best,
+        name,
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::paths;
+
+/// How a room went, shown as one character on the card.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoomMark {
+    Perfect,
+    Hinted,
+    Fell,
+}
+
+impl RoomMark {
+    fn symbol(self) -> char {
+        match self {
+            RoomMark::Perfect => '#',
+            RoomMark::Hinted => 'o',
+            RoomMark::Fell => 'x',
+        }
+    }
+}
+
+pub struct RunSummary {
+    pub victory: bool,
+    pub floor: usize,
+    pub seed: u64,
+    pub time: String,
+    pub gold: u32,
+    pub hints: usize,
+    pub errors: u32,
+    pub rooms: Vec<RoomMark>,
+}
+
+const INNER_WIDTH: usize = 32;
+
+/// A small plain-ASCII box that survives being pasted into chat or an issue.
+pub fn render(run: &RunSummary) -> String {
+    let headline = if run.victory {
+        "RUST RAID  Dungeon conquered".to_string()
+    } else {
+        format!("RUST RAID  Fell on floor {}", run.floor)
+    };
+    let track: String = run.rooms.iter().map(|m| m.symbol()).collect();
+    let rows = [
+        headline,
+        track,
+        format!("Time  {:<9} Gold   {}", run.time, run.gold),
+        format!("Hints {:<9} Errors {}", run.hints, run.errors),
+        format!("Seed  {}", run.seed),
+    ];
+
+    let border = format!("+{}+", "-".repeat(INNER_WIDTH + 2));
+    let mut out = border.clone();
+    out.push('\n');
+    for row in rows {
+        out.push_str(&format!("| {:<width$} |\n", row, width = INNER_WIDTH));
+    }
+    out.push_str(&border);
+    out.push('\n');
+    out
+}
+
+/// Saves the card as `~/rust-raid-card.txt`, next to the other exports.
+pub fn save(card: &str) -> Result<PathBuf> {
+    let home = paths::home_dir().ok_or_else(|| anyhow::anyhow!("No home directory found"))?;
+    let path = home.join("rust-raid-card.txt");
+    std::fs::write(&path, card)?;
+    Ok(path)
+}
+
+/// Tries the usual clipboard tools for each platform. Returns false if none worked.
+pub fn copy_to_clipboard(text: &str) -> bool {
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if cfg!(windows) {
+        &[("clip", &[])]
+    } else {
+        &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    };
+    candidates
+        .iter()
+        .any(|(program, args)| pipe_to(program, args, text))
+}
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> bool {
+    let Ok(mut child) = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    let written = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    child.wait().is_ok_and(|status| status.success()) && written
+}
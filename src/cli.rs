@@ -0,0 +1,102 @@
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use rust_raid::compiler::Backend;
+use rust_raid::headless::ReportFormat;
+use rust_raid::sync::Direction;
+use rust_raid::theme::Theme;
+
+/// A terminal dungeon crawler where every lock is a Rust compiler error.
+#[derive(Debug, Parser)]
+#[command(name = "rust-raid", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Directory containing the floor_NN_* puzzle folders.
+    #[arg(long, value_name = "DIR", default_value = "puzzles", global = true)]
+    pub puzzles_dir: PathBuf,
+
+    /// Skip the title screen and start on this floor (1-based).
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pub level: Option<u16>,
+
+    /// Skip the title screen and start in this room of the floor (1-based).
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pub room: Option<u16>,
+
+    /// Seed for anything random, so a run can be reproduced.
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Color scheme for this session (overrides the saved setting).
+    #[arg(long, value_enum)]
+    pub theme: Option<Theme>,
+
+    /// No animations, ASCII-only decorations and a high-contrast palette.
+    #[arg(long)]
+    pub accessible: bool,
+
+    /// Where to compile solutions: the local rustc or the Rust Playground.
+    #[arg(long, value_enum, default_value = "local")]
+    pub compiler: Backend,
+
+    /// Keep a JSON file with the current room, HP, gold, attempts and timer
+    /// up to date, for stream overlays.
+    #[arg(long, value_name = "FILE")]
+    pub live_feed: Option<PathBuf>,
+
+    /// Record every key press to FILE so the session can be played back with --replay.
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Play back a session recorded with --record (space pauses, +/- change speed, q quits).
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["level", "room", "seed", "host", "join"])]
+    pub replay: Option<PathBuf>,
+
+    /// Playback speed for --replay, e.g. 2 for double speed.
+    #[arg(long, value_name = "X", default_value_t = 1.0, requires = "replay")]
+    pub replay_speed: f64,
+
+    /// Host a co-op session: wait for a partner on ADDR (e.g. 0.0.0.0:7878) and share the editor.
+    #[arg(long, value_name = "ADDR", conflicts_with = "join")]
+    pub host: Option<String>,
+
+    /// Join a co-op session hosted at ADDR (e.g. 192.168.1.20:7878).
+    #[arg(long, value_name = "ADDR")]
+    pub join: Option<String>,
+
+    /// Write a debug log to the data directory (attach it to bug reports).
+    #[arg(long, global = true)]
+    pub debug: bool,
+
+    /// Write a redacted bug report (versions, terminal, recent log) and exit.
+    #[arg(long)]
+    pub bug_report: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Check a solution against a room without starting the game. Exits nonzero on failure.
+    Validate {
+        /// The room file, e.g. puzzles/floor_01_ownership/room_01_torch.toml
+        room: PathBuf,
+        /// The solution source, or `-` to read it from stdin
+        solution: PathBuf,
+    },
+    /// Check every room of a puzzle pack: reference solutions, locked lines and hints.
+    Test {
+        #[arg(long, value_enum, default_value = "tap")]
+        format: ReportFormat,
+    },
+    /// Export, submit or clear the anonymous room outcomes recorded with `telemetry = true`.
+    Telemetry {
+        #[command(subcommand)]
+        action: rust_raid::telemetry::Action,
+    },
+    /// Copy progress to or from a private GitHub gist (needs RUST_RAID_GITHUB_TOKEN).
+    Sync {
+        #[arg(value_enum)]
+        direction: Direction,
+    },
+}
@@ -0,0 +1,210 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use crate::puzzle::CodexEntry;
+
+/// A codex entry together with where it was learned.
+pub struct CodexPage {
+    pub entry: CodexEntry,
+    pub level: usize,
+    pub concept: String,
+}
+
+/// The player's collected knowledge plus the state of the codex screen.
+#[derive(Default)]
+pub struct Codex {
+    pages: Vec<CodexPage>,
+    pub filter: String,
+    pub filtering: bool,
+    collapsed: BTreeSet<usize>,
+    pub cursor: usize,
+}
+
+/// One visible line item in the grouped codex view.
+pub enum CodexRow<'a> {
+    Header {
+        level: usize,
+        count: usize,
+        collapsed: bool,
+    },
+    Entry(&'a CodexPage),
+}
+
+impl Codex {
+    /// Records a new entry. Returns false if it was already known (e.g. on replay).
+    pub fn add(&mut self, entry: CodexEntry, level: usize, concept: &str) -> bool {
+        if self.pages.iter().any(|p| p.entry.title == entry.title) {
+            return false;
+        }
+        self.pages.push(CodexPage {
+            entry,
+            level,
+            concept: concept.to_string(),
+        });
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Titles of the entries collected so far, in the order they were found.
+    pub fn titles(&self) -> Vec<String> {
+        self.pages.iter().map(|p| p.entry.title.clone()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// Entries learned on floor `level`, in the order they were found.
+    pub fn on_floor(&self, level: usize) -> impl Iterator<Item = &CodexPage> {
+        self.pages.iter().filter(move |p| p.level == level)
+    }
+
+    /// Resets the view state when the codex screen is opened.
+    pub fn open(&mut self) {
+        self.cursor = 0;
+        self.filter.clear();
+        self.filtering = false;
+    }
+
+    /// The earliest unlocked entry title in `text`, matched as whole words and
+    /// ignoring case: its byte range in `text` and the entry's title.
+    pub fn first_mention(&self, text: &str) -> Option<(Range<usize>, &str)> {
+        let haystack = text.to_ascii_lowercase();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        self.pages
+            .iter()
+            .filter_map(|page| {
+                let title = &page.entry.title;
+                let needle = title.to_ascii_lowercase();
+                let found = haystack.match_indices(&needle).find(|&(start, _)| {
+                    let end = start + needle.len();
+                    !haystack[..start].chars().next_back().is_some_and(is_word)
+                        && !haystack[end..].chars().next().is_some_and(is_word)
+                })?;
+                Some((found.0..found.0 + needle.len(), title.as_str()))
+            })
+            // Prefer the longer title when two start at the same place
+            .min_by_key(|(range, _)| (range.start, std::cmp::Reverse(range.end)))
+    }
+
+    /// Opens the codex on the first entry whose title mentions `term` (or whose
+    /// concept is `term`), expanding its floor. Returns false if none does.
+    pub fn open_at(&mut self, term: &str) -> bool {
+        let needle = term.trim().to_lowercase();
+        let wanted =
+            |p: &CodexPage| p.entry.title.to_lowercase().contains(&needle) || p.concept == needle;
+        let Some(level) = self.pages.iter().find(|p| wanted(p)).map(|p| p.level) else {
+            return false;
+        };
+        self.open();
+        self.collapsed.remove(&level);
+        self.cursor = self
+            .rows()
+            .iter()
+            .position(|row| matches!(row, CodexRow::Entry(p) if wanted(p)))
+            .unwrap_or(0);
+        true
+    }
+
+    fn matches(&self, page: &CodexPage) -> bool {
+        if self.filter.is_empty() {
+            return true;
+        }
+        let needle = self.filter.to_lowercase();
+        page.entry.title.to_lowercase().contains(&needle)
+            || page.concept.to_lowercase().contains(&needle)
+            || page.entry.description.to_lowercase().contains(&needle)
+    }
+
+    /// Matching entries grouped under one header per floor, in floor order.
+    pub fn rows(&self) -> Vec<CodexRow<'_>> {
+        let levels: BTreeSet<usize> = self.pages.iter().map(|p| p.level).collect();
+        let mut rows = Vec::new();
+        for level in levels {
+            let pages: Vec<&CodexPage> = self
+                .pages
+                .iter()
+                .filter(|p| p.level == level && self.matches(p))
+                .collect();
+            if pages.is_empty() {
+                continue;
+            }
+            // An active search always shows its hits, even inside collapsed groups
+            let collapsed = self.collapsed.contains(&level) && self.filter.is_empty();
+            rows.push(CodexRow::Header {
+                level,
+                count: pages.len(),
+                collapsed,
+            });
+            if !collapsed {
+                rows.extend(pages.into_iter().map(CodexRow::Entry));
+            }
+        }
+        rows
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let count = self.rows().len();
+        if count == 0 {
+            self.cursor = 0;
+            return;
+        }
+        self.cursor = self.cursor.saturating_add_signed(delta).min(count - 1);
+    }
+
+    /// Collapses or expands the group under the cursor (or the entry's own group).
+    pub fn toggle_group(&mut self) {
+        let level = match self.rows().get(self.cursor) {
+            Some(CodexRow::Header { level, .. }) => *level,
+            Some(CodexRow::Entry(page)) => page.level,
+            None => return,
+        };
+        if !self.collapsed.remove(&level) {
+            self.collapsed.insert(level);
+        }
+        // Keep the cursor on the header of the group that just changed
+        if let Some(index) = self
+            .rows()
+            .iter()
+            .position(|r| matches!(r, CodexRow::Header { level: l, .. } if *l == level))
+        {
+            self.cursor = index;
+        }
+    }
+
+    /// Renders every unlocked entry as a Markdown study sheet, grouped by floor.
+    pub fn to_markdown(&self, level_name: impl Fn(usize) -> String) -> String {
+        let mut out = String::from("# Rust Raid: Adventurer's Codex\n\n");
+        out.push_str("Knowledge gained from the depths of the Borrow Dungeon.\n");
+
+        let levels: BTreeSet<usize> = self.pages.iter().map(|p| p.level).collect();
+        for level in levels {
+            out.push_str(&format!("\n## Floor {}: {}\n", level, level_name(level)));
+            for page in self.pages.iter().filter(|p| p.level == level) {
+                out.push_str(&format!("\n### {}\n\n", page.entry.title));
+                out.push_str(&format!("*Concept: `{}`*\n\n", page.concept));
+                out.push_str(page.entry.description.trim());
+                out.push('\n');
+                if let Some(example) = &page.entry.example {
+                    out.push_str("\n```rust\n");
+                    out.push_str(example.trim_matches('\n'));
+                    out.push_str("\n```\n");
+                }
+            }
+        }
+        out
+    }
+
+    pub fn push_filter(&mut self, c: char) {
+        self.filter.push(c);
+        self.cursor = 0;
+    }
+
+    pub fn pop_filter(&mut self) {
+        self.filter.pop();
+        self.cursor = 0;
+    }
+}
@@ -0,0 +1,180 @@
+//! What command mode knows about its commands: their names for Tab
+//! completion, a usage line for those that take arguments, and the commands
+//! already run this session for Up and Down to bring back.
+
+/// One command as the player types it after `:`.
+pub struct Command {
+    pub name: &'static str,
+    /// Fluent id of its usage line, for commands that take arguments
+    pub usage: Option<&'static str>,
+    /// Whether it does nothing without an argument, so completing it adds
+    /// the space before one
+    pub needs_argument: bool,
+}
+
+const fn bare(name: &'static str) -> Command {
+    Command {
+        name,
+        usage: None,
+        needs_argument: false,
+    }
+}
+
+const fn with_argument(name: &'static str, usage: &'static str, needs_argument: bool) -> Command {
+    Command {
+        name,
+        usage: Some(usage),
+        needs_argument,
+    }
+}
+
+/// The commands offered for completion, in the order the README lists them.
+/// Short aliases such as `:q` and `:j` still work but aren't offered, and
+/// neither is anything meant to be found.
+const COMMANDS: [Command; 36] = [
+    bare("quit"),
+    bare("keys"),
+    bare("help"),
+    bare("inv"),
+    bare("hint"),
+    bare("hints"),
+    bare("feedback"),
+    bare("intent"),
+    with_argument("drop", "usage-drop", true),
+    bare("map"),
+    with_argument("codex", "usage-codex", false),
+    bare("goal"),
+    bare("achievements"),
+    bare("archive"),
+    bare("campaigns"),
+    with_argument("sync", "usage-sync", false),
+    bare("bugreport"),
+    bare("share"),
+    with_argument("doc", "usage-doc", true),
+    bare("errors"),
+    bare("last-error"),
+    with_argument("explain", "usage-explain", false),
+    bare("accept"),
+    bare("howto"),
+    bare("restart-room"),
+    bare("summon"),
+    bare("restart"),
+    bare("scratch"),
+    with_argument("eval", "usage-eval", true),
+    bare("diff"),
+    bare("expected"),
+    bare("cheat"),
+    with_argument("goto", "usage-goto", true),
+    bare("top"),
+    bare("bot"),
+    bare("oracle"),
+];
+
+/// Previous commands kept for this session.
+const HISTORY_LEN: usize = 50;
+
+fn offered() -> impl Iterator<Item = &'static Command> {
+    COMMANDS
+        .iter()
+        .filter(|command| cfg!(feature = "ai-hints") || command.name != "oracle")
+}
+
+/// Commands whose name starts with what has been typed, while the name is
+/// still being typed.
+pub fn candidates(buffer: &str) -> Vec<&'static Command> {
+    if buffer.is_empty() || buffer.contains(' ') {
+        return Vec::new();
+    }
+    offered()
+        .filter(|command| command.name.starts_with(buffer))
+        .collect()
+}
+
+/// `buffer` with its command name completed as far as the candidates agree,
+/// or None if Tab has nothing to add.
+pub fn complete(buffer: &str) -> Option<String> {
+    let candidates = candidates(buffer);
+    let completed = match candidates.as_slice() {
+        [] => return None,
+        [only] if only.needs_argument => format!("{} ", only.name),
+        [only] => only.name.to_string(),
+        [first, rest @ ..] => {
+            let mut common = first.name;
+            for command in rest {
+                let agreed = common
+                    .char_indices()
+                    .zip(command.name.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(common.len().min(command.name.len()), |((i, _), _)| i);
+                common = &common[..agreed];
+            }
+            common.to_string()
+        }
+    };
+    (completed != buffer).then_some(completed)
+}
+
+/// The usage line's Fluent id once a command that takes arguments has been
+/// typed in full.
+pub fn usage(buffer: &str) -> Option<&'static str> {
+    let name = buffer.split(' ').next()?;
+    offered()
+        .find(|command| command.name == name)
+        .and_then(|command| command.usage)
+}
+
+/// Commands run this session, oldest first, and where Up and Down have got
+/// to in them.
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<String>,
+    /// The entry shown, while browsing
+    browsing: Option<usize>,
+    /// What was typed before browsing started, for Down to come back to
+    draft: String,
+}
+
+impl History {
+    /// Keeps a command that was run, unless it's the one run last.
+    pub fn record(&mut self, command: &str) {
+        self.browsing = None;
+        if command.is_empty() || self.entries.last().is_some_and(|last| last == command) {
+            return;
+        }
+        if self.entries.len() == HISTORY_LEN {
+            self.entries.remove(0);
+        }
+        self.entries.push(command.to_string());
+    }
+
+    /// Stops browsing, as when the buffer is edited or command mode left.
+    pub fn reset(&mut self) {
+        self.browsing = None;
+    }
+
+    /// The command before the one shown, starting from the last one run.
+    pub fn previous(&mut self, buffer: &str) -> Option<&str> {
+        let index = match self.browsing {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = buffer.to_string();
+                self.entries.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.browsing = Some(index);
+        Some(&self.entries[index])
+    }
+
+    /// The command after the one shown, then what was being typed.
+    pub fn next(&mut self) -> Option<&str> {
+        let index = self.browsing? + 1;
+        if index < self.entries.len() {
+            self.browsing = Some(index);
+            Some(&self.entries[index])
+        } else {
+            self.browsing = None;
+            Some(&self.draft)
+        }
+    }
+}
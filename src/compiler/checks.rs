@@ -0,0 +1,316 @@
+//! The ways a room can check a solution, picked with `[puzzle.check]` in the
+//! room file. Rooms without one are compiled, run and compared against
+//! `expected_output`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use syn::visit::Visit;
+use tempfile::TempDir;
+
+use super::runner::{compile, output_with_timeout, timeout, write_fixtures};
+use super::{lint, Fixture, RunSpec, SandboxPolicy, ValidationResult, Validator};
+use crate::i18n::t;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Check {
+    /// Compile, run and diff against `expected_output`
+    #[default]
+    Output,
+    /// Append `#[test]` functions to the solution and require them all to pass
+    Tests(UnitTests),
+    /// Require or forbid calls and constructs, then diff the output as usual
+    Ast(AstRules),
+    /// Hand the solution to an external program
+    Script(Script),
+}
+
+impl Check {
+    /// Checks `code` for this room. `default` is the compile-and-diff validator
+    /// the game was started with (local rustc, the Playground, or whatever an
+    /// embedder supplied); the solution runs under the room's `sandbox` with
+    /// what `spec` gives it, and again for each hidden test case when the
+    /// output is diffed. With `clippy_clean`, a solution that passes must
+    /// also get through Clippy without warnings.
+    pub fn run(
+        &self,
+        default: &dyn Validator,
+        code: &str,
+        spec: &RunSpec,
+        sandbox: &SandboxPolicy,
+        clippy_clean: bool,
+    ) -> Result<ValidationResult> {
+        match self.check(default, code, spec, sandbox)? {
+            ValidationResult::Success if clippy_clean => lint::clippy(code),
+            verdict => Ok(verdict),
+        }
+    }
+
+    fn check(
+        &self,
+        default: &dyn Validator,
+        code: &str,
+        spec: &RunSpec,
+        sandbox: &SandboxPolicy,
+    ) -> Result<ValidationResult> {
+        match self {
+            Check::Output => default.validate_with_tests(code, spec, sandbox),
+            Check::Tests(tests) => tests.run(code, &spec.files, sandbox),
+            Check::Ast(rules) => match rules.validate(code, &spec.expected_output)? {
+                ValidationResult::Success => default.validate_with_tests(code, spec, sandbox),
+                rejected => Ok(rejected),
+            },
+            Check::Script(script) => script.validate(code, &spec.expected_output),
+        }
+    }
+
+    /// Makes a relative script path relative to the room file's directory.
+    pub(crate) fn resolve_paths(&mut self, room_dir: &Path) {
+        if let Check::Script(script) = self {
+            if script.command.is_relative() && script.command.components().count() > 1 {
+                script.command = room_dir.join(&script.command);
+            }
+        }
+    }
+}
+
+/// `kind = "tests"`: the room's `tests` are compiled into the solution with
+/// `rustc --test`. Needs a local toolchain, even when playing on the Playground.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UnitTests {
+    /// `#[test]` functions; they can use the solution's items directly
+    pub tests: String,
+}
+
+impl Validator for UnitTests {
+    fn validate(&self, code: &str, expected_output: &str) -> Result<ValidationResult> {
+        self.validate_sandboxed(code, expected_output, &SandboxPolicy::default())
+    }
+
+    fn validate_sandboxed(
+        &self,
+        code: &str,
+        _expected_output: &str,
+        sandbox: &SandboxPolicy,
+    ) -> Result<ValidationResult> {
+        self.run(code, &[], sandbox)
+    }
+}
+
+impl UnitTests {
+    /// Compiles the tests into `code` and runs them with `files` beside them.
+    fn run(
+        &self,
+        code: &str,
+        files: &[Fixture],
+        sandbox: &SandboxPolicy,
+    ) -> Result<ValidationResult> {
+        let source = format!(
+            "{}\n\n#[cfg(test)]\nmod room_tests {{\n    #[allow(unused_imports)]\n    use super::*;\n\n{}\n}}\n",
+            code, self.tests
+        );
+        let temp_dir = TempDir::new()?;
+        let binary_path = match compile(&temp_dir, &source, &["--test"])? {
+            Ok(path) => path,
+            Err(failed) => return Ok(failed),
+        };
+
+        write_fixtures(temp_dir.path(), files)?;
+        let mut command = Command::new(&binary_path);
+        sandbox.apply(&mut command, temp_dir.path());
+        let Some(run_output) = output_with_timeout(
+            command
+                .args(["--test-threads=1", "--color=never"])
+                .env("RUST_BACKTRACE", "0"),
+        )?
+        else {
+            return Ok(ValidationResult::Timeout(timeout()));
+        };
+        tracing::debug!(status = %run_output.status, "room tests ran");
+        if run_output.status.success() {
+            return Ok(ValidationResult::Success);
+        }
+        let stdout = String::from_utf8_lossy(&run_output.stdout)
+            .replace(&format!("{}/", temp_dir.path().display()), "");
+        Ok(ValidationResult::Rejected(test_failures(&stdout)))
+    }
+}
+
+/// The panic messages of the failed tests, without the harness noise around them.
+fn test_failures(stdout: &str) -> String {
+    let failures = stdout
+        .split_once("\nfailures:\n")
+        .map(|(_, rest)| rest)
+        .and_then(|rest| rest.split_once("\nfailures:\n").map(|(details, _)| details))
+        .unwrap_or(stdout);
+    failures
+        .lines()
+        .filter(|line| !line.starts_with("note: run with `RUST_BACKTRACE"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// `kind = "ast"`: names are method names (`clone`), function paths (`Rc::new`
+/// or just `new`), macros (`println!`) and the keywords `match`, `if let`,
+/// `for`, `while`, `loop` and `unsafe`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AstRules {
+    pub forbid: Vec<String>,
+    pub require: Vec<String>,
+    /// Shown instead of the generated explanation when a rule is broken
+    pub message: Option<String>,
+}
+
+impl Validator for AstRules {
+    /// Only checks the rules; [`Check::run`] still diffs the output afterwards.
+    fn validate(&self, code: &str, _expected_output: &str) -> Result<ValidationResult> {
+        // Code that doesn't parse won't compile either, and rustc explains that better
+        let Ok(file) = syn::parse_file(code) else {
+            return Ok(ValidationResult::Success);
+        };
+        let mut used = UsedNames::default();
+        used.visit_file(&file);
+
+        let reason = if let Some(name) = self.forbid.iter().find(|name| used.contains(name)) {
+            t!("check-forbidden", name = name.as_str())
+        } else if let Some(name) = self.require.iter().find(|name| !used.contains(name)) {
+            t!("check-required", name = name.as_str())
+        } else {
+            return Ok(ValidationResult::Success);
+        };
+        Ok(ValidationResult::Rejected(
+            self.message.clone().unwrap_or(reason),
+        ))
+    }
+}
+
+#[derive(Default)]
+struct UsedNames(Vec<String>);
+
+impl UsedNames {
+    fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|used| used == name)
+    }
+
+    fn add(&mut self, name: impl Into<String>) {
+        self.0.push(name.into());
+    }
+
+    fn add_path(&mut self, path: &syn::Path) {
+        let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        if let Some(last) = segments.last() {
+            self.add(last.clone());
+        }
+        if segments.len() > 1 {
+            self.add(segments.join("::"));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for UsedNames {
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        self.add(node.method.to_string());
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = &*node.func {
+            self.add_path(&func.path);
+        }
+        syn::visit::visit_expr_call(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        if let Some(last) = node.path.segments.last() {
+            self.add(format!("{}!", last.ident));
+        }
+        syn::visit::visit_macro(self, node);
+    }
+
+    fn visit_expr_match(&mut self, node: &'ast syn::ExprMatch) {
+        self.add("match");
+        syn::visit::visit_expr_match(self, node);
+    }
+
+    fn visit_expr_if(&mut self, node: &'ast syn::ExprIf) {
+        if matches!(&*node.cond, syn::Expr::Let(_)) {
+            self.add("if let");
+        }
+        syn::visit::visit_expr_if(self, node);
+    }
+
+    fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+        self.add("for");
+        syn::visit::visit_expr_for_loop(self, node);
+    }
+
+    fn visit_expr_while(&mut self, node: &'ast syn::ExprWhile) {
+        self.add("while");
+        syn::visit::visit_expr_while(self, node);
+    }
+
+    fn visit_expr_loop(&mut self, node: &'ast syn::ExprLoop) {
+        self.add("loop");
+        syn::visit::visit_expr_loop(self, node);
+    }
+
+    fn visit_expr_unsafe(&mut self, node: &'ast syn::ExprUnsafe) {
+        self.add("unsafe");
+        syn::visit::visit_expr_unsafe(self, node);
+    }
+
+    fn visit_signature(&mut self, node: &'ast syn::Signature) {
+        if node.unsafety.is_some() {
+            self.add("unsafe");
+        }
+        syn::visit::visit_signature(self, node);
+    }
+}
+
+/// `kind = "script"`: runs `command` with the path of the solution file as
+/// its only argument and the expected output in `RAID_EXPECTED_OUTPUT`. Exit
+/// status 0 clears the room; otherwise whatever it printed is shown.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Script {
+    /// Relative paths with a directory part are relative to the room file
+    pub command: PathBuf,
+}
+
+impl Validator for Script {
+    fn validate(&self, code: &str, expected_output: &str) -> Result<ValidationResult> {
+        let temp_dir = TempDir::new()?;
+        let source_path = temp_dir.path().join("solution.rs");
+        std::fs::write(&source_path, code)?;
+
+        let output = output_with_timeout(
+            Command::new(&self.command)
+                .arg(&source_path)
+                .env("RAID_EXPECTED_OUTPUT", expected_output),
+        )
+        .with_context(|| format!("Could not run check script {}", self.command.display()))?;
+        let Some(output) = output else {
+            tracing::info!(script = %self.command.display(), "check script timed out");
+            return Ok(ValidationResult::Timeout(timeout()));
+        };
+        tracing::debug!(status = %output.status, script = %self.command.display(), "check script ran");
+        if output.status.success() {
+            return Ok(ValidationResult::Success);
+        }
+        let printed = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let printed = printed.trim();
+        Ok(ValidationResult::Rejected(if printed.is_empty() {
+            t!("check-script-failed", status = output.status.to_string())
+        } else {
+            printed.to_string()
+        }))
+    }
+}
@@ -0,0 +1,200 @@
+//! rustc's JSON diagnostics, folded down to what fits the message pane: each
+//! error's message and its primary span, with the notes and help one key away.
+//! Each error's place in the code is kept too, for the editor to mark.
+
+use serde::Deserialize;
+use std::fmt;
+
+/// What rustc said about code that didn't compile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompileErrors {
+    /// Everything, as rustc prints it
+    pub full: String,
+    /// Each error's message and primary span only; `None` when that would
+    /// leave nothing out
+    pub folded: Option<String>,
+    /// Where each error points in the code, in the order rustc gave them
+    pub markers: Vec<Marker>,
+}
+
+/// An error's place in the code, for the editor to mark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Marker {
+    /// Counting from 1, as rustc does
+    pub line: usize,
+    pub column: usize,
+    /// The error's header, such as `error[E0382]: borrow of moved value`
+    pub message: String,
+    /// What to underline: a line and the columns from and up to, all
+    /// counting from 1
+    pub underlines: Vec<(usize, usize, usize)>,
+}
+
+impl CompileErrors {
+    /// Output that came without structure, such as the Playground's.
+    pub fn plain(full: String) -> Self {
+        CompileErrors {
+            full,
+            folded: None,
+            markers: Vec::new(),
+        }
+    }
+
+    /// The folded text, or everything if there's nothing to fold.
+    pub fn short(&self) -> &str {
+        self.folded.as_deref().unwrap_or(&self.full)
+    }
+}
+
+impl fmt::Display for CompileErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.full)
+    }
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    message: String,
+    level: String,
+    #[serde(default)]
+    code: Option<Code>,
+    #[serde(default)]
+    spans: Vec<Span>,
+    #[serde(default)]
+    children: Vec<Diagnostic>,
+    #[serde(default)]
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Code {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct Span {
+    #[serde(default)]
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+    #[serde(default)]
+    text: Vec<SpanLine>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SpanLine {
+    text: String,
+    highlight_start: usize,
+    highlight_end: usize,
+}
+
+/// Reads `rustc --error-format=json` output. `clean` tidies the rendered
+/// text (temp paths and the like) before it's shown.
+pub(crate) fn parse(stderr: &str, clean: impl Fn(&str) -> String) -> CompileErrors {
+    let diagnostics: Vec<Diagnostic> = stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    if diagnostics.is_empty() {
+        // Not JSON after all, e.g. rustc failing before it got to the code
+        return CompileErrors::plain(clean(stderr));
+    }
+
+    let full = clean(
+        &diagnostics
+            .iter()
+            .filter_map(|d| d.rendered.as_deref())
+            .collect::<String>(),
+    );
+    let errors: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|d| d.level == "error" && !d.message.starts_with("aborting due to"))
+        .collect();
+    let hidden = diagnostics.iter().any(|d| d.level != "error")
+        || errors
+            .iter()
+            .any(|d| d.spans.len() > 1 || !d.children.is_empty());
+    let folded: Vec<String> = errors.iter().map(|d| fold(d)).collect();
+    CompileErrors {
+        full,
+        folded: (hidden && !folded.is_empty()).then(|| folded.join("\n\n")),
+        markers: errors.iter().filter_map(|d| marker(d)).collect(),
+    }
+}
+
+fn header(diagnostic: &Diagnostic) -> String {
+    match &diagnostic.code {
+        Some(code) => format!("error[{}]: {}", code.code, diagnostic.message),
+        None => format!("error: {}", diagnostic.message),
+    }
+}
+
+/// Where the error's primary spans fall in the player's code; None for
+/// errors that point nowhere in it, such as a missing `main`'s crate-level
+/// complaint or one inside a macro from std.
+fn marker(diagnostic: &Diagnostic) -> Option<Marker> {
+    let primary: Vec<&Span> = diagnostic
+        .spans
+        .iter()
+        .filter(|span| span.is_primary && span.file_name.ends_with("solution.rs"))
+        .collect();
+    let first = primary.first()?;
+    let underlines = primary
+        .iter()
+        .flat_map(|span| {
+            span.text.iter().enumerate().map(|(i, line)| {
+                (
+                    span.line_start + i,
+                    line.highlight_start,
+                    line.highlight_end,
+                )
+            })
+        })
+        .collect();
+    Some(Marker {
+        line: first.line_start,
+        column: first.column_start,
+        message: header(diagnostic),
+        underlines,
+    })
+}
+
+/// The header line, where it happened, and the primary span underlined.
+fn fold(diagnostic: &Diagnostic) -> String {
+    let mut text = header(diagnostic);
+    let span = diagnostic
+        .spans
+        .iter()
+        .find(|span| span.is_primary)
+        .or(diagnostic.spans.first());
+    let Some(span) = span else {
+        return text;
+    };
+    let number = span.line_start.to_string();
+    let gutter = " ".repeat(number.len());
+    text.push_str(&format!(
+        "\n{}--> solution.rs:{}:{}",
+        gutter, span.line_start, span.column_start
+    ));
+    if let Some(line) = span.text.first() {
+        let start = line.highlight_start.saturating_sub(1);
+        let width = line
+            .highlight_end
+            .saturating_sub(line.highlight_start)
+            .max(1);
+        text.push_str(&format!(
+            "\n{} |\n{} | {}\n{} | {}{} {}",
+            gutter,
+            number,
+            line.text,
+            gutter,
+            " ".repeat(start),
+            "^".repeat(width),
+            span.label.as_deref().unwrap_or_default()
+        ));
+    }
+    text.trim_end().to_string()
+}
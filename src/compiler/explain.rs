@@ -0,0 +1,43 @@
+//! `rustc --explain`: the long form of an error code, with examples, for
+//! when the short message isn't enough.
+
+use anyhow::{Context, Result};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+
+use super::runner::output_with_timeout;
+
+/// `code` as rustc knows it, e.g. `e382` or `0382` becomes `E0382`.
+pub fn normalize(code: &str) -> Option<String> {
+    let digits = code.trim().trim_start_matches(['E', 'e']);
+    if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("E{:0>4}", digits))
+}
+
+/// rustc's explanation of `code`, in Markdown.
+pub fn explain(code: &str) -> Result<String> {
+    let output = output_with_timeout(Command::new("rustc").args(["--explain", code]))
+        .context("Could not start rustc")?;
+    let Some(output) = output else {
+        anyhow::bail!("rustc took too long to answer");
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}", stderr.trim().trim_start_matches("error: "));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+/// Runs [`explain`] on a background thread, so a slow toolchain doesn't
+/// stop the interface drawing.
+pub fn spawn_explain(code: String) -> Receiver<Result<String, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(explain(&code).map_err(|e| e.to_string()));
+    });
+    rx
+}
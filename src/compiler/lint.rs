@@ -0,0 +1,50 @@
+//! Clippy as a gate, for rooms with `require_clippy_clean` that want the
+//! solution idiomatic as well as correct.
+
+use anyhow::{Context, Result};
+use std::process::Command;
+use tempfile::TempDir;
+
+use super::diagnostics;
+use super::runner::{output_with_timeout, timeout};
+use super::ValidationResult;
+
+/// Runs `clippy-driver` over `code` with every warning denied. Needs Clippy
+/// installed locally, even when playing on the Playground.
+#[tracing::instrument(skip_all, fields(code_bytes = code.len()))]
+pub fn clippy(code: &str) -> Result<ValidationResult> {
+    let temp_dir = TempDir::new()?;
+    let source_path = temp_dir.path().join("solution.rs");
+    std::fs::write(&source_path, code)?;
+
+    let output = output_with_timeout(
+        Command::new("clippy-driver")
+            .arg(&source_path)
+            .arg("--out-dir")
+            .arg(temp_dir.path())
+            .args([
+                "--edition=2021",
+                "--error-format=json",
+                "--emit=metadata",
+                "-D",
+                "warnings",
+            ]),
+    )
+    .context(
+        "Could not run clippy-driver; this room needs Clippy (`rustup component add clippy`)",
+    )?;
+    let Some(output) = output else {
+        tracing::info!("clippy timed out");
+        return Ok(ValidationResult::Timeout(timeout()));
+    };
+    tracing::debug!(status = %output.status, "clippy finished");
+    if output.status.success() {
+        return Ok(ValidationResult::Success);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let dir = format!("{}/", temp_dir.path().display());
+    Ok(ValidationResult::LintError(diagnostics::parse(
+        &stderr,
+        |text| text.replace(&dir, ""),
+    )))
+}
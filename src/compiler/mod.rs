@@ -1,3 +1,138 @@
+pub mod checks;
+pub mod diagnostics;
+pub mod explain;
+pub mod lint;
+pub mod playground;
 pub mod runner;
+pub mod sandbox;
+pub mod suggest;
+pub mod worker;
 
-pub use runner::{validate_solution, ValidationResult};
+use anyhow::Result;
+
+pub use checks::Check;
+pub use diagnostics::{CompileErrors, Marker};
+pub use runner::{
+    set_timeout, validate_solution, Fixture, OutputMatch, RunSpec, TestCase, ValidationResult,
+    DEFAULT_TIMEOUT,
+};
+pub use sandbox::SandboxPolicy;
+
+/// Checks a solution. [`Check`] picks one per room; the game's default one
+/// (a [`Backend`], unless an embedder swapped it) does compile-and-diff.
+pub trait Validator {
+    fn validate(&self, code: &str, expected_output: &str) -> Result<ValidationResult>;
+
+    /// Like [`validate`](Self::validate), running the program under a room's
+    /// `sandbox`. Validators that don't run it on this machine can ignore it.
+    fn validate_sandboxed(
+        &self,
+        code: &str,
+        expected_output: &str,
+        _sandbox: &SandboxPolicy,
+    ) -> Result<ValidationResult> {
+        self.validate(code, expected_output)
+    }
+
+    /// Like [`validate_sandboxed`](Self::validate_sandboxed), with the room's
+    /// input and files given to the program, then runs it again for each of
+    /// its hidden tests. Validators that can't give the program input only
+    /// check `expected_output`, exactly, and fail rooms that need more.
+    fn validate_with_tests(
+        &self,
+        code: &str,
+        spec: &RunSpec,
+        sandbox: &SandboxPolicy,
+    ) -> Result<ValidationResult> {
+        if spec.stdin.is_some() || !spec.files.is_empty() {
+            anyhow::bail!("This room feeds its program input, which this compiler can't do");
+        }
+        if spec.output_match != OutputMatch::Exact {
+            anyhow::bail!("This room matches output in a way this compiler can't");
+        }
+        if !spec.tests.is_empty() || spec.only_test.is_some() {
+            anyhow::bail!("This compiler can't run the room's hidden tests");
+        }
+        self.validate_sandboxed(code, &spec.expected_output, sandbox)
+    }
+}
+
+/// Where solutions are compiled and run.
+#[derive(Debug, Clone, Copy, PartialEq, Default, clap::ValueEnum)]
+pub enum Backend {
+    /// The local `rustc` on PATH
+    #[default]
+    Local,
+    /// The Rust Playground (needs network access, no toolchain)
+    Playground,
+}
+
+impl Validator for Backend {
+    fn validate(&self, code: &str, expected_output: &str) -> Result<ValidationResult> {
+        self.validate_sandboxed(code, expected_output, &SandboxPolicy::default())
+    }
+
+    fn validate_sandboxed(
+        &self,
+        code: &str,
+        expected_output: &str,
+        sandbox: &SandboxPolicy,
+    ) -> Result<ValidationResult> {
+        self.validate_with_tests(code, &RunSpec::expecting(expected_output), sandbox)
+    }
+
+    fn validate_with_tests(
+        &self,
+        code: &str,
+        spec: &RunSpec,
+        sandbox: &SandboxPolicy,
+    ) -> Result<ValidationResult> {
+        match self {
+            Backend::Local => validate_solution(code, spec, sandbox),
+            // The Playground runs everything in its own sandbox, and takes no
+            // input, so rooms that need it or hidden tests can't be checked there
+            Backend::Playground if spec.stdin.is_some() || !spec.files.is_empty() => {
+                anyhow::bail!("This room feeds its program input, which the Playground can't do")
+            }
+            Backend::Playground if !spec.tests.is_empty() || spec.only_test.is_some() => {
+                anyhow::bail!("This room has hidden tests, which the Playground can't run")
+            }
+            Backend::Playground => {
+                playground::validate_remote(code, &spec.expected_output, spec.output_match)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playground_refuses_rooms_with_hidden_tests() {
+        let spec = RunSpec {
+            tests: vec![TestCase {
+                expected_output: "42".to_string(),
+                ..TestCase::default()
+            }],
+            ..RunSpec::expecting("7")
+        };
+        let result = Backend::Playground.validate_with_tests(
+            "fn main() { println!(\"7\"); }",
+            &spec,
+            &SandboxPolicy::default(),
+        );
+        assert!(result.unwrap_err().to_string().contains("hidden tests"));
+
+        let only = RunSpec {
+            only_test: Some(0),
+            ..spec
+        };
+        let result = Backend::Playground.validate_with_tests(
+            "fn main() {}",
+            &only,
+            &SandboxPolicy::default(),
+        );
+        assert!(result.unwrap_err().to_string().contains("hidden tests"));
+    }
+}
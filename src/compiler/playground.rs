@@ -0,0 +1,105 @@
+use anyhow::{bail, Context, Result};
+use std::sync::mpsc::{self, Receiver};
+
+use super::{CompileErrors, OutputMatch, ValidationResult};
+use crate::http;
+
+const EXECUTE_URL: &str = "https://play.rust-lang.org/execute";
+const GIST_URL: &str = "https://play.rust-lang.org/meta/gist";
+
+/// Validates a solution on the Rust Playground instead of a local rustc, so
+/// people without a toolchain can play.
+pub fn validate_remote(
+    code: &str,
+    expected_output: &str,
+    output_match: OutputMatch,
+) -> Result<ValidationResult> {
+    let request = serde_json::json!({
+        "channel": "stable",
+        "mode": "debug",
+        "edition": "2021",
+        "crateType": "bin",
+        "tests": false,
+        "backtrace": false,
+        "code": code,
+    });
+
+    let headers = ["Content-Type: application/json".to_string()];
+    let body = http::send(
+        "POST",
+        EXECUTE_URL,
+        &headers,
+        Some(&request.to_string()),
+        30,
+    )
+    .context("Could not reach the Rust Playground")?;
+    let response: serde_json::Value =
+        serde_json::from_str(&body).context("Unexpected reply from the Playground")?;
+    if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+        bail!("The Playground refused the request: {}", error);
+    }
+    let stdout = response["stdout"].as_str().unwrap_or_default();
+    let stderr = response["stderr"].as_str().unwrap_or_default();
+    tracing::debug!(success = ?response["success"], "playground finished");
+
+    // Compile failures and runtime failures both report success = false
+    if stderr.contains("could not compile") {
+        return Ok(ValidationResult::CompileError(CompileErrors::plain(
+            clean_cargo_output(stderr),
+        )));
+    }
+
+    let got = stdout.trim();
+    let expected = expected_output.trim();
+    if output_match.matches(expected, got) {
+        Ok(ValidationResult::Success)
+    } else {
+        Ok(ValidationResult::WrongOutput {
+            expected: expected.to_string(),
+            got: got.to_string(),
+        })
+    }
+}
+
+/// Saves `code` as a Playground gist and returns a link that opens it there.
+pub fn share(code: &str) -> Result<String> {
+    let request = serde_json::json!({ "code": code });
+    let headers = ["Content-Type: application/json".to_string()];
+    let body = http::send("POST", GIST_URL, &headers, Some(&request.to_string()), 20)
+        .context("Could not reach the Rust Playground")?;
+    let response: serde_json::Value =
+        serde_json::from_str(&body).context("Unexpected reply from the Playground")?;
+    let Some(id) = response["id"].as_str() else {
+        bail!("The Playground did not return a gist id");
+    };
+    Ok(format!(
+        "https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist={}",
+        id
+    ))
+}
+
+/// Runs [`share`] on a background thread so the UI keeps drawing while it uploads.
+pub fn spawn_share(code: String) -> Receiver<Result<String, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(share(&code).map_err(|e| e.to_string()));
+    });
+    rx
+}
+
+/// Drops cargo's progress lines and the trailing "could not compile" summary.
+fn clean_cargo_output(stderr: &str) -> String {
+    stderr
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim_start();
+            !trimmed.starts_with("Compiling")
+                && !trimmed.starts_with("Finished")
+                && !trimmed.starts_with("Running")
+                && !trimmed.starts_with("error: could not compile")
+                && !line.trim().is_empty()
+        })
+        .map(|line| line.replace("src/main.rs", "solution.rs"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
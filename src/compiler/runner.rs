@@ -1,50 +1,361 @@
 use anyhow::Result;
-use std::process::Command;
+use regex::Regex;
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use tempfile::TempDir;
 
-#[derive(Debug)]
+use super::diagnostics::{self, CompileErrors};
+use super::{worker, SandboxPolicy};
+use crate::i18n::t;
+
+/// How long rustc, and then the compiled program, may each run before they're
+/// killed, unless [`set_timeout`] says otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+static TIMEOUT_MS: AtomicU64 = AtomicU64::new(DEFAULT_TIMEOUT.as_millis() as u64);
+
+/// Changes the limit for every compile and run from now on.
+pub fn set_timeout(limit: Duration) {
+    TIMEOUT_MS.store(limit.as_millis() as u64, Ordering::Relaxed);
+}
+
+pub(crate) fn timeout() -> Duration {
+    Duration::from_millis(TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+#[derive(Debug, Clone)]
 pub enum ValidationResult {
-    CompileError(String),
-    WrongOutput { expected: String, got: String },
+    CompileError(CompileErrors),
+    /// It works, but Clippy has warnings about it (rooms with
+    /// `require_clippy_clean` only)
+    LintError(CompileErrors),
+    WrongOutput {
+        expected: String,
+        got: String,
+    },
+    /// The room's own check turned the solution down, e.g. a failing unit test
+    Rejected(String),
+    /// One of the hidden tests, by index, printed something else; `report` is
+    /// what the player is told about it
+    HiddenTestFailed {
+        test: usize,
+        report: String,
+    },
+    /// rustc or the program ran past the time limit (given here) and was killed,
+    /// usually because of a loop that never ends
+    Timeout(Duration),
     Success,
 }
 
-pub fn validate_solution(code: &str, expected_output: &str) -> Result<ValidationResult> {
+/// One more run of the solution, with input the player never sees, so a
+/// room can't be cleared by printing `expected_output` word for word.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TestCase {
+    #[serde(default)]
+    pub stdin: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub expected_output: String,
+}
+
+/// A file put in the program's directory before each run, for rooms about
+/// reading files.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    /// Where it goes, relative to the program's directory
+    pub path: PathBuf,
+    #[serde(default)]
+    pub contents: String,
+    /// A file next to the room file to take `contents` from instead
+    #[serde(default)]
+    pub source: Option<PathBuf>,
+}
+
+/// Writes `files` into `dir`, replacing whatever an earlier run left there.
+pub(crate) fn write_fixtures(dir: &Path, files: &[Fixture]) -> Result<()> {
+    for file in files {
+        let path = dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &file.contents)?;
+    }
+    Ok(())
+}
+
+/// How what the program printed is compared with what a room expects.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMatch {
+    /// The same text
+    #[default]
+    Exact,
+    /// The expected output is a regular expression the whole output matches
+    Regex,
+    /// The expected output appears somewhere in the output
+    Contains,
+    /// The same lines, in any order
+    LinesUnordered,
+}
+
+impl OutputMatch {
+    /// Whether `got` passes for `expected`, both trimmed at either end first.
+    pub fn matches(self, expected: &str, got: &str) -> bool {
+        let (expected, got) = (expected.trim(), got.trim());
+        match self {
+            OutputMatch::Exact => got == expected,
+            // Patterns are checked when the room is loaded
+            OutputMatch::Regex => anchored(expected).is_ok_and(|re| re.is_match(got)),
+            OutputMatch::Contains => got.contains(expected),
+            OutputMatch::LinesUnordered => sorted_lines(got) == sorted_lines(expected),
+        }
+    }
+
+    /// Why `expected` can't be used with this mode: a pattern that doesn't compile.
+    pub fn check(self, expected: &str) -> std::result::Result<(), String> {
+        match self {
+            OutputMatch::Regex => anchored(expected.trim())
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn sorted_lines(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    lines.sort_unstable();
+    lines
+}
+
+fn anchored(pattern: &str) -> std::result::Result<Regex, regex::Error> {
+    Regex::new(&format!(r"\A(?:{})\z", pattern))
+}
+
+/// What a room gives its program besides the code, and what it expects back.
+#[derive(Debug, Clone, Default)]
+pub struct RunSpec {
+    pub expected_output: String,
+    pub output_match: OutputMatch,
+    /// Fed to the program when it's run for `expected_output`
+    pub stdin: Option<String>,
+    /// Further runs with their own input and output, never shown to the player
+    pub tests: Vec<TestCase>,
+    /// Written into the program's directory before each run
+    pub files: Vec<Fixture>,
+    /// Run only this one of `tests`, skipping `expected_output` and the rest
+    pub only_test: Option<usize>,
+}
+
+impl RunSpec {
+    /// Just `expected_output`, matched exactly.
+    pub fn expecting(expected_output: &str) -> Self {
+        RunSpec {
+            expected_output: expected_output.to_string(),
+            ..RunSpec::default()
+        }
+    }
+}
+
+#[tracing::instrument(skip_all, fields(code_bytes = code.len(), tests = spec.tests.len()))]
+pub fn validate_solution(
+    code: &str,
+    spec: &RunSpec,
+    sandbox: &SandboxPolicy,
+) -> Result<ValidationResult> {
     let temp_dir = TempDir::new()?;
-    let source_path = temp_dir.path().join("solution.rs");
-    let binary_path = temp_dir.path().join("solution");
+    let binary_path = match compile(&temp_dir, code, &[])? {
+        Ok(path) => path,
+        Err(failed) => return Ok(failed),
+    };
+
+    // Run the compiled binary
+    if spec.only_test.is_none() {
+        write_fixtures(temp_dir.path(), &spec.files)?;
+        let mut command = Command::new(&binary_path);
+        sandbox.apply(&mut command, temp_dir.path());
+        let Some(run_output) = output_with_input(&mut command, spec.stdin.as_deref())? else {
+            tracing::info!("solution timed out");
+            return Ok(ValidationResult::Timeout(timeout()));
+        };
+        tracing::debug!(status = %run_output.status, "solution ran");
+
+        let stdout = String::from_utf8_lossy(&run_output.stdout).to_string();
+        let stdout_trimmed = stdout.trim();
+        let expected_trimmed = spec.expected_output.trim();
+
+        if !spec.output_match.matches(expected_trimmed, stdout_trimmed) {
+            tracing::info!(
+                expected = expected_trimmed,
+                got = stdout_trimmed,
+                "wrong output"
+            );
+            return Ok(ValidationResult::WrongOutput {
+                expected: expected_trimmed.to_string(),
+                got: stdout_trimmed.to_string(),
+            });
+        }
+    }
+
+    let tests = spec
+        .tests
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| spec.only_test.is_none_or(|only| only == *i));
+    for (i, test) in tests {
+        write_fixtures(temp_dir.path(), &spec.files)?;
+        let mut command = Command::new(&binary_path);
+        sandbox.apply(&mut command, temp_dir.path());
+        command.args(&test.args);
+        let Some(run_output) = output_with_input(&mut command, test.stdin.as_deref())? else {
+            tracing::info!(test = i + 1, "hidden test timed out");
+            return Ok(ValidationResult::Timeout(timeout()));
+        };
+        let stdout = String::from_utf8_lossy(&run_output.stdout).to_string();
+        if !spec.output_match.matches(&test.expected_output, &stdout) {
+            tracing::info!(test = i + 1, got = stdout.trim(), "hidden test failed");
+            return Ok(ValidationResult::HiddenTestFailed {
+                test: i,
+                report: hidden_failure(i + 1, spec.tests.len(), test, stdout.trim()),
+            });
+        }
+    }
+    tracing::info!("output matches");
+    Ok(ValidationResult::Success)
+}
+
+/// What the player is told about a failed hidden test: its input and what
+/// their code printed, but not what it should have.
+fn hidden_failure(number: usize, total: usize, test: &TestCase, got: &str) -> String {
+    let mut text = t!("hidden-test-failed", number = number, total = total);
+    if !test.args.is_empty() {
+        let args: Vec<String> = test.args.iter().map(|arg| format!("{:?}", arg)).collect();
+        text.push('\n');
+        text.push_str(&t!("hidden-test-args", args = args.join(" ")));
+    }
+    if let Some(stdin) = &test.stdin {
+        text.push('\n');
+        text.push_str(&t!("hidden-test-input", input = stdin.trim_end()));
+    }
+    text.push('\n');
+    text.push_str(&t!("hidden-test-printed", output = got));
+    text
+}
+
+/// Compiles `code` into `dir` and returns the binary, or the verdict if it
+/// didn't compile (the cleaned-up errors) or rustc had to be killed.
+pub(crate) fn compile(
+    dir: &TempDir,
+    code: &str,
+    extra_args: &[&str],
+) -> Result<std::result::Result<PathBuf, ValidationResult>> {
+    let started = Instant::now();
+    let source_path = dir.path().join("solution.rs");
+    let binary_path = dir.path().join("solution");
 
     // Write the player's code
     std::fs::write(&source_path, code)?;
 
     // Compile with rustc
-    let compile_output = Command::new("rustc")
-        .arg(&source_path)
-        .arg("-o")
-        .arg(&binary_path)
-        .arg("--edition=2021")
-        .output()?;
+    let compile_output = output_with_timeout(
+        Command::new("rustc")
+            .arg(&source_path)
+            .arg("-o")
+            .arg(&binary_path)
+            .args(["--edition=2021", "--error-format=json"])
+            .args(extra_args),
+    )
+    .inspect_err(|e| tracing::error!(error = %e, "could not start rustc"))?;
+    let Some(compile_output) = compile_output else {
+        tracing::info!("rustc timed out");
+        return Ok(Err(ValidationResult::Timeout(timeout())));
+    };
+    tracing::debug!(
+        status = %compile_output.status,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "rustc finished"
+    );
 
     if !compile_output.status.success() {
         let stderr = String::from_utf8_lossy(&compile_output.stderr).to_string();
-        return Ok(ValidationResult::CompileError(clean_error_output(&stderr)));
+        tracing::debug!(%stderr, "compile error");
+        return Ok(Err(ValidationResult::CompileError(diagnostics::parse(
+            &stderr,
+            clean_error_output,
+        ))));
     }
+    Ok(Ok(binary_path))
+}
 
-    // Run the compiled binary
-    let run_output = Command::new(&binary_path).output()?;
-
-    let stdout = String::from_utf8_lossy(&run_output.stdout).to_string();
-    let stdout_trimmed = stdout.trim();
-    let expected_trimmed = expected_output.trim();
-
-    if stdout_trimmed == expected_trimmed {
-        Ok(ValidationResult::Success)
-    } else {
-        Ok(ValidationResult::WrongOutput {
-            expected: expected_trimmed.to_string(),
-            got: stdout_trimmed.to_string(),
+/// Like [`Command::output`], but kills the process once it has run for
+/// [`timeout`] and returns `None` instead, or fails if the job it belongs to
+/// is cancelled. Stdin is closed so a program waiting for input can't hang either.
+pub(crate) fn output_with_timeout(command: &mut Command) -> Result<Option<Output>> {
+    output_with_input(command, None)
+}
+
+/// [`output_with_timeout`], with `input` on stdin before it's closed.
+pub(crate) fn output_with_input(
+    command: &mut Command,
+    input: Option<&str>,
+) -> Result<Option<Output>> {
+    let mut child = command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
         })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Written from its own thread, as a program that doesn't read it would
+    // otherwise block us on a full pipe
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        let input = input.to_string();
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
     }
+    // Drain both pipes as it runs, or a chatty program blocks on a full one
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let deadline = Instant::now() + timeout();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if worker::cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("Cancelled");
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
 }
 
 fn clean_error_output(stderr: &str) -> String {
@@ -0,0 +1,206 @@
+//! Limits on what a compiled solution can do while it runs. A room can
+//! loosen or tighten them with `[puzzle.sandbox]`.
+//!
+//! Everywhere, the program starts in its own temp directory with an empty
+//! environment, apart from a fixed locale and timezone so that its output
+//! doesn't depend on the machine. On Unix it also gets resource limits. On Linux it loses the
+//! network (a fresh network namespace) and, where Landlock is available, the
+//! right to create, change or delete files outside that directory. Kernels
+//! without these features run the program without them rather than not at all.
+
+use serde::Deserialize;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SandboxPolicy {
+    /// Let the program open sockets to the outside
+    pub network: bool,
+    /// Let the program write anywhere the player can, not just its own directory
+    pub filesystem: bool,
+    /// Address space limit; 0 is unlimited
+    pub memory_mb: u64,
+    /// CPU time limit, on top of the wall-clock timeout; 0 is unlimited
+    pub cpu_seconds: u64,
+    /// Largest file the program can write; 0 is unlimited
+    pub file_size_mb: u64,
+    /// Environment variables passed through from the game
+    pub env: Vec<String>,
+    /// Pass the game's whole environment through, locale and timezone included
+    pub host_env: bool,
+}
+
+/// What the program sees instead of the player's locale and timezone.
+const FIXED_ENV: [(&str, &str); 3] = [("LANG", "C.UTF-8"), ("LC_ALL", "C.UTF-8"), ("TZ", "UTC")];
+
+impl Default for SandboxPolicy {
+    fn default() -> Self {
+        SandboxPolicy {
+            network: false,
+            filesystem: false,
+            memory_mb: 512,
+            cpu_seconds: 10,
+            file_size_mb: 16,
+            env: Vec::new(),
+            host_env: false,
+        }
+    }
+}
+
+impl SandboxPolicy {
+    /// Sets `command` up to run under this policy, in `dir`.
+    pub(crate) fn apply(&self, command: &mut Command, dir: &Path) {
+        command.current_dir(dir);
+        if !self.host_env {
+            command.env_clear().envs(FIXED_ENV);
+        }
+        for name in &self.env {
+            if let Some(value) = std::env::var_os(name) {
+                command.env(name, value);
+            }
+        }
+        #[cfg(unix)]
+        unix::restrict(self, command, dir);
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::process::CommandExt;
+    use std::path::Path;
+    use std::process::Command;
+
+    use super::SandboxPolicy;
+
+    const MB: u64 = 1024 * 1024;
+
+    pub(super) fn restrict(policy: &SandboxPolicy, command: &mut Command, dir: &Path) {
+        let limits = [
+            (libc::RLIMIT_AS, policy.memory_mb * MB),
+            (libc::RLIMIT_CPU, policy.cpu_seconds),
+            (libc::RLIMIT_FSIZE, policy.file_size_mb * MB),
+        ];
+        // SAFETY: getuid and getgid can't fail
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        // Allocating between fork and exec isn't safe, so this happens first
+        let id_maps = (!policy.network).then(|| {
+            (
+                format!("{} {} 1", uid, uid).into_bytes(),
+                format!("{} {} 1", gid, gid).into_bytes(),
+            )
+        });
+        let writable = (!policy.filesystem)
+            .then(|| CString::new(dir.as_os_str().as_bytes()).ok())
+            .flatten();
+        #[cfg(not(target_os = "linux"))]
+        let _ = (&id_maps, &writable);
+
+        // SAFETY: the closure runs in the forked child and only makes syscalls
+        unsafe {
+            command.pre_exec(move || {
+                for (resource, limit) in limits {
+                    if limit == 0 {
+                        continue;
+                    }
+                    let rlimit = libc::rlimit {
+                        rlim_cur: limit as libc::rlim_t,
+                        rlim_max: limit as libc::rlim_t,
+                    };
+                    if libc::setrlimit(resource, &rlimit) != 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }
+                #[cfg(target_os = "linux")]
+                {
+                    // Needs unprivileged user namespaces; without them the
+                    // network stays reachable
+                    if let Some((uid_map, gid_map)) = &id_maps {
+                        if libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) == 0 {
+                            // Unmapped, the program couldn't even create files
+                            write_file(c"/proc/self/setgroups", b"deny");
+                            write_file(c"/proc/self/uid_map", uid_map);
+                            write_file(c"/proc/self/gid_map", gid_map);
+                        }
+                    }
+                    if let Some(dir) = &writable {
+                        super::landlock::confine_writes(dir);
+                    }
+                }
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    unsafe fn write_file(path: &std::ffi::CStr, contents: &[u8]) {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        if fd >= 0 {
+            libc::write(fd, contents.as_ptr().cast(), contents.len());
+            libc::close(fd);
+        }
+    }
+}
+
+/// Landlock through raw syscalls, as libc has no bindings for its structs.
+#[cfg(target_os = "linux")]
+mod landlock {
+    use std::ffi::CStr;
+
+    /// `WRITE_FILE`, `REMOVE_DIR`, `REMOVE_FILE` and every `MAKE_*` right of ABI 1
+    const ACCESS_FS_WRITE: u64 = (1 << 1) | (0b1_1111_1111 << 4);
+    const RULE_PATH_BENEATH: libc::c_int = 1;
+
+    #[repr(C)]
+    struct RulesetAttr {
+        handled_access_fs: u64,
+    }
+
+    #[repr(C, packed)]
+    struct PathBeneathAttr {
+        allowed_access: u64,
+        parent_fd: i32,
+    }
+
+    /// Takes away the right to change anything outside `dir`. Does nothing on
+    /// kernels without Landlock.
+    ///
+    /// # Safety
+    ///
+    /// Only makes syscalls, so it can run between fork and exec.
+    pub(super) unsafe fn confine_writes(dir: &CStr) {
+        let attr = RulesetAttr {
+            handled_access_fs: ACCESS_FS_WRITE,
+        };
+        let ruleset = libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            &attr as *const RulesetAttr,
+            std::mem::size_of::<RulesetAttr>(),
+            0u32,
+        ) as libc::c_int;
+        if ruleset < 0 {
+            return;
+        }
+        let parent_fd = libc::open(dir.as_ptr(), libc::O_PATH | libc::O_CLOEXEC);
+        if parent_fd >= 0 {
+            let rule = PathBeneathAttr {
+                allowed_access: ACCESS_FS_WRITE,
+                parent_fd,
+            };
+            let added = libc::syscall(
+                libc::SYS_landlock_add_rule,
+                ruleset,
+                RULE_PATH_BENEATH,
+                &rule as *const PathBeneathAttr,
+                0u32,
+            );
+            if added == 0 && libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) == 0 {
+                libc::syscall(libc::SYS_landlock_restrict_self, ruleset, 0u32);
+            }
+            libc::close(parent_fd);
+        }
+        libc::close(ruleset);
+    }
+}
@@ -0,0 +1,153 @@
+//! Fixes rustc proposes itself (`help: consider cloning the value ...`), read
+//! from its JSON diagnostics. The compiler spirit offers them as the last hint.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::process::Command;
+use tempfile::TempDir;
+
+use super::runner::output_with_timeout;
+use crate::i18n::t;
+
+/// One replacement, in rustc's 1-based line and character columns.
+#[derive(Debug, Clone)]
+pub struct Edit {
+    pub line_start: usize,
+    pub col_start: usize,
+    pub line_end: usize,
+    pub col_end: usize,
+    pub replacement: String,
+}
+
+/// A `help:` message with the edits that carry it out.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub message: String,
+    pub edits: Vec<Edit>,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    level: String,
+    #[serde(default)]
+    children: Vec<Diagnostic>,
+    message: String,
+    #[serde(default)]
+    spans: Vec<Span>,
+}
+
+#[derive(Deserialize)]
+struct Span {
+    line_start: usize,
+    column_start: usize,
+    line_end: usize,
+    column_end: usize,
+    suggested_replacement: Option<String>,
+    suggestion_applicability: Option<String>,
+}
+
+/// Compiles `code` once more and returns rustc's suggestions for its errors,
+/// first error first. Suggestions with placeholders (`/* value */`) are left out.
+pub fn suggestions(code: &str) -> Result<Vec<Suggestion>> {
+    let temp_dir = TempDir::new()?;
+    let source_path = temp_dir.path().join("solution.rs");
+    std::fs::write(&source_path, code)?;
+    let output = output_with_timeout(
+        Command::new("rustc")
+            .arg(&source_path)
+            .arg("-o")
+            .arg(temp_dir.path().join("solution"))
+            .args(["--edition=2021", "--error-format=json"]),
+    )
+    .context("Could not start rustc")?;
+    let Some(output) = output else {
+        anyhow::bail!("rustc took too long");
+    };
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let suggestions = stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Diagnostic>(line).ok())
+        .filter(|diagnostic| diagnostic.level == "error")
+        .flat_map(|diagnostic| diagnostic.children)
+        .filter_map(|child| {
+            let edits: Vec<Edit> = child
+                .spans
+                .iter()
+                .filter(|span| {
+                    matches!(
+                        span.suggestion_applicability.as_deref(),
+                        Some("MachineApplicable" | "MaybeIncorrect")
+                    )
+                })
+                .filter_map(|span| {
+                    Some(Edit {
+                        line_start: span.line_start,
+                        col_start: span.column_start,
+                        line_end: span.line_end,
+                        col_end: span.column_end,
+                        replacement: span.suggested_replacement.clone()?,
+                    })
+                })
+                .collect();
+            (!edits.is_empty()).then_some(Suggestion {
+                message: child.message,
+                edits,
+            })
+        })
+        .collect();
+    Ok(suggestions)
+}
+
+impl Suggestion {
+    /// Every line (1-based) the edits touch.
+    pub fn lines(&self) -> Vec<usize> {
+        let mut lines: Vec<usize> = self
+            .edits
+            .iter()
+            .flat_map(|edit| edit.line_start..=edit.line_end)
+            .collect();
+        lines.sort_unstable();
+        lines.dedup();
+        lines
+    }
+
+    /// The buffer with the edits applied, or None if it changed so much that
+    /// the spans no longer fit.
+    pub fn apply(&self, lines: &[String]) -> Option<Vec<String>> {
+        let mut chars: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+        let mut edits = self.edits.clone();
+        // Back to front, so earlier positions stay valid
+        edits.sort_by_key(|edit| std::cmp::Reverse((edit.line_start, edit.col_start)));
+        for edit in edits {
+            let start_line = chars.get(edit.line_start.checked_sub(1)?)?;
+            let end_line = chars.get(edit.line_end.checked_sub(1)?)?;
+            let start = edit.col_start.checked_sub(1)?;
+            let end = edit.col_end.checked_sub(1)?;
+            if start > start_line.len() || end > end_line.len() {
+                return None;
+            }
+            let mut joined: Vec<char> = start_line[..start].to_vec();
+            joined.extend(edit.replacement.chars());
+            joined.extend_from_slice(&end_line[end..]);
+            let replaced: Vec<Vec<char>> =
+                joined.split(|&c| c == '\n').map(<[char]>::to_vec).collect();
+            chars.splice(edit.line_start - 1..edit.line_end, replaced);
+        }
+        Some(chars.into_iter().map(String::from_iter).collect())
+    }
+
+    /// What the spirit says, e.g. "consider cloning the value: line 3 becomes ...".
+    pub fn describe(&self, lines: &[String]) -> String {
+        let mut text = self.message.clone();
+        if let Some(after) = self.apply(lines) {
+            for line in self.lines() {
+                if let Some(new) = after.get(line - 1) {
+                    text.push('\n');
+                    text.push_str(&t!("spirit-line", line = line, code = new.trim()));
+                }
+            }
+        }
+        text
+    }
+}
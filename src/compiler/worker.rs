@@ -0,0 +1,62 @@
+//! Checks that run on a worker thread, so the interface keeps drawing while
+//! rustc works, and that can be called off halfway.
+
+use anyhow::Result;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+
+use super::ValidationResult;
+
+thread_local! {
+    static CANCEL: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// Whether the job running on this thread was cancelled. Process waits check
+/// it and kill what they started.
+pub(crate) fn cancelled() -> bool {
+    CANCEL.with(|cancel| {
+        cancel
+            .borrow()
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    })
+}
+
+/// A check in progress, or other rustc work such as the compiler spirit's
+/// suggestions.
+pub struct Job<T = ValidationResult> {
+    rx: Receiver<Result<T>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> Job<T> {
+    /// Starts `work` on a new thread.
+    pub fn spawn(work: impl FnOnce() -> Result<T> + Send + 'static) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&cancel);
+        std::thread::spawn(move || {
+            CANCEL.with(|cancel| *cancel.borrow_mut() = Some(flag));
+            let _ = tx.send(work());
+        });
+        Job { rx, cancel }
+    }
+
+    /// The verdict, once there is one.
+    pub fn try_result(&self) -> Option<Result<T>> {
+        match self.rx.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some(Err(anyhow::anyhow!("The check stopped without a verdict")))
+            }
+        }
+    }
+
+    /// Kills whatever the job is running; its verdict is thrown away.
+    pub fn cancel(self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
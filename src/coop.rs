@@ -0,0 +1,86 @@
+//! Two-player shared editor over a plain TCP connection.
+//!
+//! The protocol is a simple relay of newline-delimited JSON messages. Puzzle
+//! buffers are a few dozen lines, so each edit sends the whole buffer and the
+//! last writer wins. Both sides run their own compiler on F5, which gives the
+//! same result because they run the same code.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// The sender's editor contents after a change
+    Buffer { lines: Vec<String> },
+    /// The sender pressed F5
+    Run,
+    /// The sender moved to another room (0-based)
+    Goto { level: usize, room: usize },
+}
+
+pub struct Session {
+    writer: TcpStream,
+    incoming: Receiver<Message>,
+    pub peer: String,
+}
+
+impl Session {
+    /// Waits for one partner to connect on `addr` (e.g. `0.0.0.0:7878`).
+    pub fn host(addr: &str) -> Result<Session> {
+        let listener =
+            TcpListener::bind(addr).with_context(|| format!("Could not listen on {}", addr))?;
+        let (stream, peer) = listener.accept()?;
+        Self::start(stream, peer.to_string())
+    }
+
+    pub fn join(addr: &str) -> Result<Session> {
+        let stream =
+            TcpStream::connect(addr).with_context(|| format!("Could not connect to {}", addr))?;
+        Self::start(stream, addr.to_string())
+    }
+
+    fn start(stream: TcpStream, peer: String) -> Result<Session> {
+        stream.set_nodelay(true)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let (tx, incoming) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+                match serde_json::from_str(&line) {
+                    Ok(message) => {
+                        if tx.send(message).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => tracing::warn!(error = %e, "ignoring co-op message"),
+                }
+            }
+        });
+        tracing::info!(%peer, "co-op session started");
+        Ok(Session {
+            writer: stream,
+            incoming,
+            peer,
+        })
+    }
+
+    pub fn send(&mut self, message: &Message) -> Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// The next message from the partner. `Err` means the connection is gone.
+    pub fn poll(&self) -> Result<Option<Message>, ()> {
+        match self.incoming.try_recv() {
+            Ok(message) => Ok(Some(message)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(()),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! The extension points for embedding the game in another frontend or tool.
+//!
+//! [`App`] implements [`GameEngine`]; a frontend feeds it keys and
+//! ticks and draws it whenever it asks. Where puzzles come from and how
+//! solutions are checked are swappable through [`PuzzleSource`] and
+//! [`Validator`].
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use std::path::{Path, PathBuf};
+
+use crate::app::{App, GameState};
+use crate::puzzle::{
+    find_floors, load_floor, load_manifest, Campaign, Deck, FloorManifest, Quiz, Room,
+};
+
+pub use crate::compiler::Validator;
+
+/// What the frontend should do after a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    /// The player quit, finished the dungeon or ran out of HP.
+    Quit,
+}
+
+/// A running game that can be driven by any frontend.
+pub trait GameEngine {
+    /// Applies one key press to the current screen.
+    fn handle_key(&mut self, key: KeyEvent) -> Flow;
+    /// Advances timers and animations; call it every [`crate::timer::TICK`].
+    fn tick(&mut self);
+    /// Returns true (once) when something visible changed since the last call.
+    fn take_redraw(&mut self) -> bool;
+    fn render(&self, frame: &mut Frame);
+    fn state(&self) -> GameState;
+}
+
+impl GameEngine for App<'_> {
+    fn handle_key(&mut self, key: KeyEvent) -> Flow {
+        App::handle_key(self, key)
+    }
+
+    fn tick(&mut self) {
+        App::tick(self)
+    }
+
+    fn take_redraw(&mut self) -> bool {
+        std::mem::take(&mut self.needs_redraw)
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        crate::ui::draw_ui(frame, self)
+    }
+
+    fn state(&self) -> GameState {
+        self.state
+    }
+}
+
+/// Supplies the rooms of each floor, numbered from 1.
+pub trait PuzzleSource {
+    fn floor_count(&self) -> usize;
+    fn floor_name(&self, level: usize) -> String;
+    fn load_floor(&self, level: usize) -> Result<Vec<Room>>;
+    /// Floor-wide settings such as altars; none by default.
+    fn load_manifest(&self, _level: usize) -> Result<FloorManifest> {
+        Ok(FloorManifest::default())
+    }
+    /// Cards that may be drawn between rooms; an empty deck by default.
+    fn load_events(&self) -> Result<Deck> {
+        Ok(Deck::default())
+    }
+    /// Questions that can place a player on a later floor; none by default.
+    fn load_placement(&self) -> Result<Quiz> {
+        Ok(Quiz::default())
+    }
+    /// Pack-wide settings such as the win condition; none by default.
+    fn load_campaign(&self) -> Result<Campaign> {
+        Ok(Campaign::default())
+    }
+}
+
+/// The bundled layout: a directory of room TOML files per floor, found when
+/// the pack is opened (see [`find_floors`]).
+pub struct PuzzleDir {
+    root: PathBuf,
+    floors: Vec<Floor>,
+}
+
+struct Floor {
+    dir: PathBuf,
+    name: String,
+}
+
+impl PuzzleDir {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref().to_path_buf();
+        let floors = find_floors(&root)
+            .into_iter()
+            .map(|dir| Floor {
+                name: load_manifest(&dir)
+                    .ok()
+                    .and_then(|manifest| manifest.name)
+                    .unwrap_or_else(|| name_from_dir(&dir)),
+                dir,
+            })
+            .collect();
+        PuzzleDir { root, floors }
+    }
+
+    fn floor(&self, level: usize) -> Option<&Floor> {
+        self.floors.get(level.checked_sub(1)?)
+    }
+}
+
+impl PuzzleSource for PuzzleDir {
+    fn floor_count(&self) -> usize {
+        self.floors.len()
+    }
+
+    fn floor_name(&self, level: usize) -> String {
+        self.floor(level)
+            .map(|floor| floor.name.clone())
+            .unwrap_or_else(|| "Unknown".to_string())
+    }
+
+    fn load_floor(&self, level: usize) -> Result<Vec<Room>> {
+        let floor = self
+            .floor(level)
+            .ok_or_else(|| anyhow::anyhow!("{} has no floor {}", self.root.display(), level))?;
+        load_floor(&floor.dir)
+    }
+
+    fn load_manifest(&self, level: usize) -> Result<FloorManifest> {
+        match self.floor(level) {
+            Some(floor) => load_manifest(&floor.dir),
+            None => Ok(FloorManifest::default()),
+        }
+    }
+
+    fn load_events(&self) -> Result<Deck> {
+        Deck::load(&self.root.join("events.toml"))
+    }
+
+    fn load_placement(&self) -> Result<Quiz> {
+        Quiz::load(&self.root.join("placement.toml"))
+    }
+
+    fn load_campaign(&self) -> Result<Campaign> {
+        let mut campaign = Campaign::load(&self.root.join("campaign.toml"))?;
+        if campaign.name.is_none() {
+            campaign.name = self
+                .root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned());
+        }
+        Ok(campaign)
+    }
+}
+
+/// A floor's name from its directory, for floors whose manifest has none:
+/// `floor_02_borrowing` becomes "Borrowing".
+fn name_from_dir(dir: &Path) -> String {
+    let name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = name.strip_prefix("floor_").unwrap_or(&name);
+    name.trim_start_matches(|c: char| c.is_ascii_digit() || c == '_')
+        .split(['_', '-'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,49 @@
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Everything an overlay is likely to show, written as one small JSON object.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snapshot {
+    pub screen: String,
+    pub floor: usize,
+    pub floor_name: String,
+    pub room: usize,
+    pub room_count: usize,
+    pub room_id: String,
+    pub room_title: String,
+    pub hp: u32,
+    pub gold: u32,
+    pub attempts: u32,
+    pub hints_used: usize,
+    pub elapsed_secs: u64,
+}
+
+/// Keeps a JSON file (`--live-feed`) in sync with the game for OBS overlays and
+/// dashboards that poll it.
+pub struct LiveFeed {
+    path: PathBuf,
+    last: Option<Snapshot>,
+}
+
+impl LiveFeed {
+    pub fn new(path: PathBuf) -> Self {
+        LiveFeed { path, last: None }
+    }
+
+    /// Rewrites the file if anything changed since the last write. The file is
+    /// replaced atomically so readers never see half a document.
+    pub fn publish(&mut self, snapshot: Snapshot) {
+        if self.last.as_ref() == Some(&snapshot) {
+            return;
+        }
+        let Ok(json) = serde_json::to_string_pretty(&snapshot) else {
+            return;
+        };
+        let temp = self.path.with_extension("json.tmp");
+        let written = std::fs::write(&temp, json).and_then(|()| std::fs::rename(&temp, &self.path));
+        if let Err(e) = written {
+            tracing::warn!(path = %self.path.display(), error = %e, "could not write live feed");
+        }
+        self.last = Some(snapshot);
+    }
+}
@@ -0,0 +1,89 @@
+//! Ratings and comments from `:feedback`, appended to `feedback.jsonl` under
+//! the data dir for the player to pass on to a pack's author.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::i18n::t;
+use crate::paths;
+
+/// Longest comment kept, in characters.
+pub const MAX_COMMENT: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rating {
+    TooEasy,
+    #[default]
+    JustRight,
+    TooHard,
+}
+
+impl Rating {
+    pub const ALL: [Rating; 3] = [Rating::TooEasy, Rating::JustRight, Rating::TooHard];
+
+    /// How the rating is written in the feedback file and in telemetry.
+    pub fn id(self) -> &'static str {
+        match self {
+            Rating::TooEasy => "too-easy",
+            Rating::JustRight => "just-right",
+            Rating::TooHard => "too-hard",
+        }
+    }
+
+    /// The choice as shown on the feedback form.
+    pub fn label(self) -> String {
+        t!(match self {
+            Rating::TooEasy => "feedback-too-easy",
+            Rating::JustRight => "feedback-just-right",
+            Rating::TooHard => "feedback-too-hard",
+        })
+    }
+
+    /// The next choice along, `step` being 1 or -1; stops at either end.
+    pub fn step(self, step: isize) -> Self {
+        let index = Rating::ALL.iter().position(|r| *r == self).unwrap_or(1);
+        Rating::ALL[index.saturating_add_signed(step).min(Rating::ALL.len() - 1)]
+    }
+}
+
+/// The form on screen while it's being filled in.
+#[derive(Debug, Clone, Default)]
+pub struct Draft {
+    pub rating: Rating,
+    pub comment: String,
+}
+
+/// One rating of one room.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Feedback {
+    pub game_version: String,
+    pub campaign: String,
+    pub floor: usize,
+    pub room_id: String,
+    pub rating: Rating,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub comment: String,
+    /// Seconds since the Unix epoch
+    pub at: u64,
+}
+
+fn feedback_path() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("feedback.jsonl"))
+}
+
+/// Appends `feedback` to the file. Returns the file's path.
+pub fn record(feedback: &Feedback) -> Result<PathBuf> {
+    let path = feedback_path().context("No home directory found")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(feedback)?)?;
+    Ok(path)
+}
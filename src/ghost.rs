@@ -0,0 +1,101 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::paths;
+use crate::store::{self, Versioned};
+
+/// How long "your echo just cleared ..." stays in the status bar.
+pub const ANNOUNCE_SECS: u64 = 5;
+
+/// The moment a room was cleared, measured from the start of the run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Split {
+    pub room_id: String,
+    pub title: String,
+    pub secs: u64,
+}
+
+/// The timeline of the fastest full run, raced against in later runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ghost {
+    pub splits: Vec<Split>,
+}
+
+/// What the status bar should say about the ghost right now.
+pub enum Standing<'a> {
+    /// The ghost cleared this room within the last few seconds.
+    JustCleared(&'a Split),
+    /// Seconds ahead (negative) or behind (positive) at the last room both cleared.
+    Delta(i64),
+    /// Neither has cleared anything comparable yet; the ghost is in this room (1-based).
+    InRoom(usize),
+}
+
+impl Versioned for Ghost {
+    const VERSION: u32 = 1;
+}
+
+impl Ghost {
+    pub fn load() -> Option<Self> {
+        ghost_path()
+            .map(|path| store::load::<Ghost>(&path))
+            .filter(|ghost| !ghost.splits.is_empty())
+    }
+
+    /// Writes the ghost, keeping `backups` earlier copies.
+    pub fn save(&self, backups: usize) -> Result<()> {
+        let path = ghost_path().ok_or_else(|| anyhow::anyhow!("No home directory found"))?;
+        store::save(&path, self, backups)
+    }
+
+    /// Total time of the run, i.e. when the last room was cleared.
+    pub fn total_secs(&self) -> Option<u64> {
+        self.splits.last().map(|split| split.secs)
+    }
+
+    /// Whether `splits` (a finished run) should replace this ghost.
+    pub fn beaten_by(&self, splits: &[Split]) -> bool {
+        match (self.total_secs(), splits.last()) {
+            (Some(best), Some(run)) => run.secs < best,
+            (None, Some(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Compares the current run (`ours`, currently in `room_id`, `elapsed_secs` in)
+    /// against the ghost.
+    pub fn standing(&self, ours: &[Split], room_id: &str, elapsed_secs: u64) -> Standing<'_> {
+        let passed = self
+            .splits
+            .iter()
+            .take_while(|split| split.secs <= elapsed_secs)
+            .count();
+        if let Some(split) = passed.checked_sub(1).map(|i| &self.splits[i]) {
+            if elapsed_secs - split.secs < ANNOUNCE_SECS {
+                return Standing::JustCleared(split);
+            }
+        }
+
+        let at = |id: &str| self.splits.iter().find(|split| split.room_id == id);
+        let last_shared = ours
+            .iter()
+            .rev()
+            .find_map(|mine| at(&mine.room_id).map(|theirs| mine.secs as i64 - theirs.secs as i64));
+        // Once the ghost is out of this room we're behind by at least that long
+        let cleared_here = ours.iter().any(|mine| mine.room_id == room_id);
+        let here = at(room_id)
+            .filter(|theirs| !cleared_here && theirs.secs <= elapsed_secs)
+            .map(|theirs| (elapsed_secs - theirs.secs) as i64);
+        match (last_shared, here) {
+            (Some(delta), Some(behind)) => Standing::Delta(delta.max(behind)),
+            (Some(delta), None) | (None, Some(delta)) => Standing::Delta(delta),
+            (None, None) => Standing::InRoom(passed + 1),
+        }
+    }
+}
+
+fn ghost_path() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("ghost.toml"))
+}
@@ -0,0 +1,121 @@
+use ratatui::buffer::Buffer;
+
+/// Decorative symbols that have to be chosen before layout, because emoji
+/// occupy two cells and would shift alignment if swapped afterwards.
+pub struct Glyphs {
+    pub bag: &'static str,
+    pub scroll: &'static str,
+    pub key: &'static str,
+    pub artifact: &'static str,
+    pub ghost: &'static str,
+    /// Frames of the spinner shown while a run compiles
+    pub spinner: &'static [&'static str],
+}
+
+pub const UNICODE: Glyphs = Glyphs {
+    bag: "🎒",
+    scroll: "📜",
+    key: "🗝️",
+    artifact: "✨",
+    ghost: "👻",
+    spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+};
+
+pub const ASCII: Glyphs = Glyphs {
+    bag: "[]",
+    scroll: "~",
+    key: "-o",
+    artifact: "*",
+    ghost: "%",
+    spinner: &["|", "/", "-", "\\"],
+};
+
+impl Glyphs {
+    pub fn item_icon(&self, item: &str) -> &'static str {
+        match item {
+            "Sacred Scroll" => self.scroll,
+            "Twin Keys" => self.key,
+            _ => self.artifact,
+        }
+    }
+}
+
+/// Rewrites single-width decorative characters (box drawing, arrows, bullets)
+/// in an already-rendered buffer to plain ASCII.
+pub fn asciify(buf: &mut Buffer) {
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            let mut chars = cell.symbol().chars();
+            let (Some(c), None) = (chars.next(), chars.next()) else {
+                continue;
+            };
+            if let Some(replacement) = ascii_for(c) {
+                cell.set_char(replacement);
+            }
+        }
+    }
+}
+
+fn ascii_for(c: char) -> Option<char> {
+    let replacement = match c {
+        '─' | '━' | '┄' => '-',
+        '═' => '=',
+        '│' | '┃' | '║' | '▌' => '|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╔' | '╗' | '╚' | '╝' | '╠' | '╣'
+        | '╭' | '╮' | '╯' | '╰' => '+',
+        '█' | '▓' | '▒' => '#',
+        '░' => '.',
+        '▸' | '▶' | '→' => '>',
+        '◂' | '◀' | '←' => '<',
+        '▾' | '▼' | '↓' => 'v',
+        '▴' | '▲' | '↑' => '^',
+        '•' | '◆' | '●' | '✦' | '★' => '*',
+        '·' => '.',
+        '○' | '◇' | '☆' => 'o',
+        '✧' => '+',
+        '∅' => '-',
+        '—' | '–' => '-',
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+/// Best guess at whether the terminal can show Unicode decorations correctly.
+///
+/// Checks the locale first, then (if a terminal is attached in raw mode) prints a
+/// box-drawing character and an emoji off to the side and measures how far the
+/// cursor moved. Anything unexpected means "use ASCII".
+pub fn probe_unicode() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty());
+    if let Some(locale) = locale {
+        let lower = locale.to_lowercase();
+        if !lower.contains("utf-8") && !lower.contains("utf8") {
+            return false;
+        }
+    }
+    if matches!(
+        std::env::var("TERM").as_deref(),
+        Ok("linux" | "dumb" | "vt100")
+    ) {
+        return false;
+    }
+    measure_wide_glyphs().unwrap_or(true)
+}
+
+fn measure_wide_glyphs() -> std::io::Result<bool> {
+    use crossterm::{cursor, execute, style::Print, terminal};
+    let mut stdout = std::io::stdout();
+    execute!(stdout, cursor::MoveTo(0, 0), Print("═🎒"))?;
+    let (col, _) = cursor::position()?;
+    execute!(
+        stdout,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::CurrentLine)
+    )?;
+    Ok(col == 3)
+}
@@ -0,0 +1,406 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::Read;
+use std::path::Path;
+
+use crate::compiler::{Backend, Check, ValidationResult, Validator};
+use crate::puzzle::{
+    find_floors, load_floor, load_manifest, load_puzzle, Campaign, Deck, Door, Quiz, Room,
+};
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Test Anything Protocol, readable by most CI systems
+    Tap,
+    /// One JSON document with a result per room
+    Json,
+}
+
+/// Runs one solution through the same checks as F5 and prints the verdict.
+/// Returns whether the room would have been cleared.
+pub fn validate(room_path: &Path, solution_path: &Path) -> Result<bool> {
+    let room = load_puzzle(room_path)
+        .with_context(|| format!("Could not load room {}", room_path.display()))?;
+    let code = if solution_path == Path::new("-") {
+        let mut code = String::new();
+        std::io::stdin().read_to_string(&mut code)?;
+        code
+    } else {
+        std::fs::read_to_string(solution_path)
+            .with_context(|| format!("Could not read solution {}", solution_path.display()))?
+    };
+
+    let missing = room.challenge.missing_locked_lines(&code);
+    if !missing.is_empty() {
+        let lines: Vec<String> = missing.iter().map(|n| n.to_string()).collect();
+        println!(
+            "FAIL {}: locked line(s) {} were changed",
+            room.meta.id,
+            lines.join(", ")
+        );
+        return Ok(false);
+    }
+
+    let challenge = &room.challenge;
+    match challenge.check.run(
+        &Backend::Local,
+        &code,
+        &challenge.run_spec(),
+        &challenge.sandbox,
+        challenge.require_clippy_clean,
+    )? {
+        ValidationResult::Success => {
+            println!("PASS {}: {}", room.meta.id, room.meta.title);
+            Ok(true)
+        }
+        ValidationResult::CompileError(error) => {
+            println!("FAIL {}: does not compile\n\n{}", room.meta.id, error);
+            Ok(false)
+        }
+        ValidationResult::LintError(lints) => {
+            println!("FAIL {}: Clippy has warnings\n\n{}", room.meta.id, lints);
+            Ok(false)
+        }
+        ValidationResult::WrongOutput { expected, got } => {
+            println!(
+                "FAIL {}: wrong output\n\n--- expected\n{}\n--- got\n{}",
+                room.meta.id, expected, got
+            );
+            Ok(false)
+        }
+        ValidationResult::Rejected(reason)
+        | ValidationResult::HiddenTestFailed { report: reason, .. } => {
+            println!(
+                "FAIL {}: rejected by the room's check\n\n{}",
+                room.meta.id, reason
+            );
+            Ok(false)
+        }
+        ValidationResult::Timeout(limit) => {
+            println!(
+                "FAIL {}: killed after running for {}s",
+                room.meta.id,
+                limit.as_secs()
+            );
+            Ok(false)
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RoomReport {
+    floor: String,
+    room: String,
+    passed: bool,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct PackReport {
+    passed: usize,
+    failed: usize,
+    rooms: Vec<RoomReport>,
+}
+
+/// Checks every room of every floor found under `puzzles_dir`, the way a
+/// puzzle-pack author would in CI. Returns whether all rooms passed.
+pub fn test_pack(puzzles_dir: &Path, format: ReportFormat) -> Result<bool> {
+    if !puzzles_dir.is_dir() {
+        anyhow::bail!("Could not read {}", puzzles_dir.display());
+    }
+    let floors = find_floors(puzzles_dir);
+
+    let mut rooms = Vec::new();
+    if let Err(e) = Deck::load(&puzzles_dir.join("events.toml")) {
+        rooms.push(RoomReport {
+            floor: "*".to_string(),
+            room: "events.toml".to_string(),
+            passed: false,
+            errors: vec![format!("event deck does not load: {}", e)],
+            warnings: Vec::new(),
+        });
+    }
+    if let Err(e) = Campaign::load(&puzzles_dir.join("campaign.toml")) {
+        rooms.push(RoomReport {
+            floor: "*".to_string(),
+            room: "campaign.toml".to_string(),
+            passed: false,
+            errors: vec![format!("campaign manifest does not load: {}", e)],
+            warnings: Vec::new(),
+        });
+    }
+    let placement = puzzles_dir.join("placement.toml");
+    if placement.exists() {
+        rooms.push(check_quiz(&placement)?);
+    }
+    // Items a player could be carrying by now, for checking doors
+    let mut carried = Vec::new();
+    for floor in floors {
+        let floor_name = floor
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let manifest = load_manifest(&floor);
+        if let Err(e) = &manifest {
+            rooms.push(RoomReport {
+                floor: floor_name.clone(),
+                room: "floor.toml".to_string(),
+                passed: false,
+                errors: vec![format!("manifest does not load: {}", e)],
+                warnings: Vec::new(),
+            });
+        }
+        // Nothing carried makes it down into a floor that resets items
+        if manifest.as_ref().is_ok_and(|m| !m.carryover.items) {
+            carried.clear();
+        }
+        match load_floor(&floor) {
+            Ok(loaded) => {
+                let after_room = manifest.as_ref().ok().and_then(|m| m.shop_after_room);
+                if after_room.is_some_and(|after| after == 0 || after > loaded.len()) {
+                    rooms.push(RoomReport {
+                        floor: floor_name.clone(),
+                        room: "shop.toml".to_string(),
+                        passed: false,
+                        errors: vec![format!(
+                            "after_room must name one of the floor's {} rooms",
+                            loaded.len()
+                        )],
+                        warnings: Vec::new(),
+                    });
+                }
+                for mut room in loaded {
+                    let mut report = check_room(&floor_name, &mut room)?;
+                    if let Some(error) = check_door(room.meta.door.as_ref(), &mut carried) {
+                        report.errors.push(error);
+                        report.passed = false;
+                    }
+                    carried.extend(room.rewards.as_ref().and_then(|r| r.grants_item.clone()));
+                    rooms.push(report);
+                }
+                let exit = manifest.ok().and_then(|m| m.exit);
+                if let Some(error) = check_door(exit.as_ref(), &mut carried) {
+                    rooms.push(RoomReport {
+                        floor: floor_name,
+                        room: "floor.toml".to_string(),
+                        passed: false,
+                        errors: vec![error],
+                        warnings: Vec::new(),
+                    });
+                }
+            }
+            Err(e) => rooms.push(RoomReport {
+                floor: floor_name,
+                room: "*".to_string(),
+                passed: false,
+                errors: vec![format!("floor does not load: {}", e)],
+                warnings: Vec::new(),
+            }),
+        }
+    }
+
+    let passed = rooms.iter().filter(|r| r.passed).count();
+    let report = PackReport {
+        passed,
+        failed: rooms.len() - passed,
+        rooms,
+    };
+    match format {
+        ReportFormat::Tap => print_tap(&report),
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(report.failed == 0)
+}
+
+/// A door needing an item no earlier room hands out would end every run
+/// there. Doors that keep their item take it out of `carried`.
+fn check_door(door: Option<&Door>, carried: &mut Vec<String>) -> Option<String> {
+    let door = door?;
+    let Some(index) = carried.iter().position(|i| *i == door.requires_item) else {
+        return Some(format!(
+            "door needs \"{}\", which no earlier room grants",
+            door.requires_item
+        ));
+    };
+    if door.consumes {
+        carried.remove(index);
+    }
+    None
+}
+
+fn check_room(floor: &str, room: &mut Room) -> Result<RoomReport> {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    // Each variant in turn, ending back at the room's own puzzle
+    loop {
+        let label = match room.incarnation {
+            0 => String::new(),
+            n => format!("variant {}: ", n),
+        };
+        let (found, noted) = check_challenge(room)?;
+        errors.extend(found.into_iter().map(|e| format!("{}{}", label, e)));
+        warnings.extend(noted.into_iter().map(|w| format!("{}{}", label, w)));
+        if !room.summon_variant() || room.incarnation == 0 {
+            break;
+        }
+    }
+
+    Ok(RoomReport {
+        floor: floor.to_string(),
+        room: room.meta.id.clone(),
+        passed: errors.is_empty(),
+        errors,
+        warnings,
+    })
+}
+
+/// Errors and warnings for the puzzle the room currently has in.
+fn check_challenge(room: &Room) -> Result<(Vec<String>, Vec<String>)> {
+    let challenge = &room.challenge;
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let code_lines = challenge.code.trim().lines().count();
+    for &line in &challenge.locked_lines {
+        if line == 0 || line > code_lines {
+            errors.push(format!(
+                "locked line {} is outside the {}-line starting code",
+                line, code_lines
+            ));
+        }
+    }
+    if room.narrative.hints.is_empty() {
+        errors.push("no hints".to_string());
+    } else if room.narrative.hints.iter().any(|h| h.trim().is_empty()) {
+        errors.push("empty hint".to_string());
+    }
+    if challenge.expected_output.trim().is_empty() {
+        errors.push("expected_output is empty".to_string());
+    }
+    if !challenge.tests.is_empty() && matches!(challenge.check, Check::Tests(_) | Check::Script(_))
+    {
+        warnings.push("hidden tests are only run when the output is diffed".to_string());
+    }
+    if !challenge.files.is_empty() && matches!(challenge.check, Check::Script(_)) {
+        warnings.push("check scripts don't get the puzzle's files".to_string());
+    }
+
+    match &challenge.solution {
+        None => errors.push("no reference solution".to_string()),
+        Some(solution) => {
+            let missing = challenge.missing_locked_lines(solution);
+            if !missing.is_empty() {
+                errors.push(format!("solution changes locked lines {:?}", missing));
+            }
+            match challenge.check.run(
+                &Backend::Local,
+                solution,
+                &challenge.run_spec(),
+                &challenge.sandbox,
+                challenge.require_clippy_clean,
+            )? {
+                ValidationResult::Success => {}
+                ValidationResult::CompileError(error) => {
+                    errors.push(format!("solution does not compile:\n{}", error));
+                }
+                ValidationResult::LintError(lints) => {
+                    errors.push(format!("solution has Clippy warnings:\n{}", lints));
+                }
+                ValidationResult::WrongOutput { got, .. } => {
+                    errors.push(format!("solution prints the wrong output:\n{}", got));
+                }
+                ValidationResult::Rejected(reason)
+                | ValidationResult::HiddenTestFailed { report: reason, .. } => {
+                    errors.push(format!("solution fails the room's check:\n{}", reason));
+                }
+                ValidationResult::Timeout(limit) => {
+                    errors.push(format!("solution runs longer than {}s", limit.as_secs()));
+                }
+            }
+        }
+    }
+
+    // Some rooms start solved on purpose, so this is only worth a mention
+    if let ValidationResult::Success = challenge.check.run(
+        &Backend::Local,
+        &challenge.code,
+        &challenge.run_spec(),
+        &challenge.sandbox,
+        challenge.require_clippy_clean,
+    )? {
+        warnings.push("starting code already passes".to_string());
+    }
+    Ok((errors, warnings))
+}
+
+/// Every question's answer has to be one of its choices and what its code
+/// really prints.
+fn check_quiz(path: &Path) -> Result<RoomReport> {
+    let mut errors = Vec::new();
+    match Quiz::load(path) {
+        Err(e) => errors.push(format!("placement quiz does not load: {}", e)),
+        Ok(quiz) => {
+            for (i, question) in quiz.questions.iter().enumerate() {
+                let number = i + 1;
+                if question.floor < 2 {
+                    errors.push(format!(
+                        "question {} places on floor {}",
+                        number, question.floor
+                    ));
+                }
+                if !question.choices.contains(&question.answer) {
+                    errors.push(format!(
+                        "question {}: the answer is not one of the choices",
+                        number
+                    ));
+                }
+                match Backend::Local.validate(&question.code, &question.answer)? {
+                    ValidationResult::Success => {}
+                    ValidationResult::CompileError(error) | ValidationResult::LintError(error) => {
+                        errors.push(format!("question {} does not compile:\n{}", number, error));
+                    }
+                    ValidationResult::WrongOutput { got, .. } => {
+                        errors.push(format!("question {} really prints:\n{}", number, got));
+                    }
+                    ValidationResult::Rejected(reason)
+                    | ValidationResult::HiddenTestFailed { report: reason, .. } => {
+                        errors.push(format!("question {} was rejected:\n{}", number, reason));
+                    }
+                    ValidationResult::Timeout(limit) => {
+                        errors.push(format!(
+                            "question {} runs longer than {}s",
+                            number,
+                            limit.as_secs()
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    Ok(RoomReport {
+        floor: "*".to_string(),
+        room: "placement.toml".to_string(),
+        passed: errors.is_empty(),
+        errors,
+        warnings: Vec::new(),
+    })
+}
+
+fn print_tap(report: &PackReport) {
+    println!("TAP version 13");
+    println!("1..{}", report.rooms.len());
+    for (i, room) in report.rooms.iter().enumerate() {
+        let status = if room.passed { "ok" } else { "not ok" };
+        println!("{} {} - {}/{}", status, i + 1, room.floor, room.room);
+        for error in &room.errors {
+            for line in error.lines() {
+                println!("  # {}", line);
+            }
+        }
+        for warning in &room.warnings {
+            println!("  # warning: {}", warning);
+        }
+    }
+    println!("# passed {}, failed {}", report.passed, report.failed);
+}
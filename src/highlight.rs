@@ -0,0 +1,309 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::compiler::Marker;
+
+/// Coarse token classes, enough to make small Rust snippets scannable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Token {
+    Plain,
+    Keyword,
+    Type,
+    Str,
+    Number,
+    Comment,
+    Lifetime,
+    Macro,
+    Sigil, // `&`, `*` and `mut` — the characters this game is all about
+}
+
+const KEYWORDS: [&str; 36] = [
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "async",
+];
+
+pub fn style_for(token: Token) -> Style {
+    match token {
+        Token::Plain => Style::default(),
+        Token::Keyword => Style::default().fg(Color::Magenta),
+        Token::Type => Style::default().fg(Color::Yellow),
+        Token::Str => Style::default().fg(Color::Green),
+        Token::Number => Style::default().fg(Color::LightRed),
+        Token::Comment => Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC),
+        Token::Lifetime => Style::default().fg(Color::LightBlue),
+        Token::Macro => Style::default().fg(Color::Cyan),
+        Token::Sigil => Style::default()
+            .fg(Color::LightRed)
+            .add_modifier(Modifier::BOLD),
+    }
+}
+
+/// Splits one line into `(byte_range, token)` pieces covering the whole line.
+///
+/// `in_block_comment` carries `/* ... */` state from one line to the next.
+pub fn tokenize(line: &str, in_block_comment: &mut bool) -> Vec<(std::ops::Range<usize>, Token)> {
+    let bytes = line.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+
+        if *in_block_comment {
+            match line[i..].find("*/") {
+                Some(end) => {
+                    i += end + 2;
+                    *in_block_comment = false;
+                }
+                None => i = bytes.len(),
+            }
+            tokens.push((start..i, Token::Comment));
+            continue;
+        }
+
+        let c = bytes[i];
+        let token = if line[i..].starts_with("//") {
+            i = bytes.len();
+            Token::Comment
+        } else if line[i..].starts_with("/*") {
+            *in_block_comment = true;
+            i += 2;
+            continue_block(line, &mut i, in_block_comment);
+            Token::Comment
+        } else if c == b'"' {
+            i += 1;
+            while i < bytes.len() && bytes[i] != b'"' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(bytes.len());
+            Token::Str
+        } else if c == b'\'' {
+            lex_quote(bytes, &mut i)
+        } else if c.is_ascii_digit() {
+            while i < bytes.len()
+                && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'.')
+            {
+                // Stop before `..` so ranges like `0..end` stay readable
+                if bytes[i] == b'.' && bytes.get(i + 1) == Some(&b'.') {
+                    break;
+                }
+                i += 1;
+            }
+            Token::Number
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let word = &line[start..i];
+            if bytes.get(i) == Some(&b'!') {
+                i += 1;
+                Token::Macro
+            } else if word == "mut" {
+                Token::Sigil
+            } else if KEYWORDS.contains(&word) {
+                Token::Keyword
+            } else if word.starts_with(|ch: char| ch.is_ascii_uppercase()) {
+                Token::Type
+            } else {
+                Token::Plain
+            }
+        } else if c == b'&' || c == b'*' {
+            i += 1;
+            Token::Sigil
+        } else {
+            // Advance a whole UTF-8 character so ranges stay on char boundaries
+            i += line[i..].chars().next().map_or(1, char::len_utf8);
+            Token::Plain
+        };
+
+        // Merge neighbouring plain text to keep span counts low
+        match tokens.last_mut() {
+            Some((range, Token::Plain)) if token == Token::Plain && range.end == start => {
+                range.end = i;
+            }
+            _ => tokens.push((start..i, token)),
+        }
+    }
+
+    tokens
+}
+
+fn continue_block(line: &str, i: &mut usize, in_block_comment: &mut bool) {
+    match line[*i..].find("*/") {
+        Some(end) => {
+            *i += end + 2;
+            *in_block_comment = false;
+        }
+        None => *i = line.len(),
+    }
+}
+
+/// Distinguishes char literals (`'a'`, `'\n'`) from lifetimes (`'a`).
+fn lex_quote(bytes: &[u8], i: &mut usize) -> Token {
+    let start = *i;
+    *i += 1;
+    if bytes.get(*i) == Some(&b'\\') {
+        while *i < bytes.len() && bytes[*i] != b'\'' {
+            *i += 1;
+        }
+        *i = (*i + 1).min(bytes.len());
+        return Token::Str;
+    }
+    // Skip one (possibly multi-byte) character and look for the closing quote
+    let mut j = *i + 1;
+    while j < bytes.len() && (bytes[j] & 0xC0) == 0x80 {
+        j += 1;
+    }
+    if bytes.get(j) == Some(&b'\'') {
+        *i = j + 1;
+        return Token::Str;
+    }
+    while *i < bytes.len() && (bytes[*i].is_ascii_alphanumeric() || bytes[*i] == b'_') {
+        *i += 1;
+    }
+    if *i == start + 1 {
+        return Token::Plain;
+    }
+    Token::Lifetime
+}
+
+/// Highlights a whole snippet, one styled line per source line.
+pub fn highlight(code: &str) -> Vec<Line<'static>> {
+    let mut in_block_comment = false;
+    code.lines()
+        .map(|line| {
+            let spans: Vec<Span<'static>> = tokenize(line, &mut in_block_comment)
+                .into_iter()
+                .map(|(range, token)| Span::styled(line[range].to_string(), style_for(token)))
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Colors the code a `TextArea` has already drawn into `area` of `buf`, its
+/// inside without the border. `lines` is the editor's text, `locked` the
+/// numbers, counting from 1, of its sealed lines, and `errors` where the
+/// last run's compile errors point.
+///
+/// The widget only draws plain text, so each row is matched back to its line
+/// through the line number in its gutter. Only foregrounds are set, so the
+/// cursor and the selection keep their look. Rows scrolled sideways, with
+/// the gutter out of view, or holding tabs stay plain. Sealed rows get a
+/// mark in the gutter and a dark band behind the code, left off the cursor
+/// and the selection. Rows with an error get a red mark and number, and
+/// what the error points at is underlined in red.
+pub fn paint_editor(
+    buf: &mut Buffer,
+    area: Rect,
+    lines: &[String],
+    locked: &[usize],
+    errors: &[Marker],
+) {
+    let gutter = lines.len().max(1).to_string().len() as u16 + 2;
+    let mut in_block_comment = false;
+    let mut tokenized = 0;
+    let mut styles: Vec<Vec<Style>> = Vec::new();
+    for y in area.top()..area.bottom() {
+        let number: String = (area.left()..(area.left() + gutter).min(area.right()))
+            .map(|x| buf[(x, y)].symbol())
+            .collect();
+        let Some(row) = number
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .filter(|&row| row < lines.len())
+        else {
+            continue;
+        };
+        if locked.contains(&(row + 1)) {
+            paint_sealed(buf, area, y, gutter);
+        }
+        if errors.iter().any(|error| error.line == row + 1) {
+            paint_error_mark(buf, area, y, gutter);
+        }
+        if lines[row].contains('\t') {
+            continue;
+        }
+        // Block comments carry over, so every line above has to be seen first
+        while tokenized <= row {
+            let line = &lines[tokenized];
+            let mut line_styles = vec![Style::default(); line.len()];
+            for (range, token) in tokenize(line, &mut in_block_comment) {
+                line_styles[range].fill(style_for(token));
+            }
+            styles.push(line_styles);
+            tokenized += 1;
+        }
+        let line = &lines[row];
+        let underlined: Vec<(usize, usize)> = errors
+            .iter()
+            .flat_map(|error| &error.underlines)
+            .filter(|(line, _, _)| *line == row + 1)
+            .map(|&(_, from, to)| (from, to.max(from + 1)))
+            .collect();
+        // Past the end too, for errors about something missing there
+        let last = underlined
+            .iter()
+            .map(|&(_, to)| to - 1)
+            .fold(line.chars().count(), usize::max);
+        let mut chars = line.char_indices();
+        let mut column = 0;
+        for x in (area.left() + gutter)..area.right() {
+            let cell = &mut buf[(x, y)];
+            // The second half of a wide character
+            if cell.symbol().is_empty() {
+                continue;
+            }
+            column += 1;
+            if column > last {
+                break;
+            }
+            if let Some((i, _)) = chars.next() {
+                cell.set_style(styles[row][i]);
+            }
+            if underlined
+                .iter()
+                .any(|&(from, to)| (from..to).contains(&column))
+            {
+                cell.modifier.insert(Modifier::UNDERLINED);
+                cell.underline_color = Color::Red;
+            }
+        }
+    }
+}
+
+/// Marks row `y` as one an error points at: a dot where the gutter's padding
+/// was and its number in red.
+fn paint_error_mark(buf: &mut Buffer, area: Rect, y: u16, gutter: u16) {
+    let error = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    buf[(area.left(), y)].set_symbol("●").set_style(error);
+    for x in (area.left() + 1)..(area.left() + gutter).min(area.right()) {
+        buf[(x, y)].set_style(error);
+    }
+}
+
+/// Marks row `y` as sealed: a bar where the gutter's padding was, its number
+/// in yellow, and a band across the code.
+fn paint_sealed(buf: &mut Buffer, area: Rect, y: u16, gutter: u16) {
+    let sealed = Style::default().fg(Color::Yellow);
+    buf[(area.left(), y)].set_symbol("▌").set_style(sealed);
+    for x in (area.left() + 1)..(area.left() + gutter).min(area.right()) {
+        buf[(x, y)].set_style(sealed);
+    }
+    for x in (area.left() + gutter)..area.right() {
+        let cell = &mut buf[(x, y)];
+        if cell.bg == Color::Reset && !cell.modifier.contains(Modifier::REVERSED) {
+            cell.bg = Color::Black;
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// A minimal HTTP client built on the system `curl`, which keeps TLS and proxy
+/// handling out of the game's own dependencies.
+///
+/// Headers are passed through a temporary file and the body through stdin, so
+/// tokens never show up in the process list. Non-2xx responses are errors.
+pub fn send(
+    method: &str,
+    url: &str,
+    headers: &[String],
+    body: Option<&str>,
+    timeout_secs: u32,
+) -> Result<String> {
+    let mut header_file = tempfile::NamedTempFile::new()?;
+    for header in headers {
+        writeln!(header_file, "{}", header)?;
+    }
+    header_file.flush()?;
+
+    let mut command = Command::new("curl");
+    command
+        .args(["-sSL", "-X", method])
+        .args(["--max-time", &timeout_secs.to_string()])
+        .args(["-A", concat!("rust-raid/", env!("CARGO_PKG_VERSION"))])
+        .arg("-H")
+        .arg(format!("@{}", header_file.path().display()))
+        // Append the status code on its own line after the body
+        .args(["-w", "\n%{http_code}"]);
+    if body.is_some() {
+        command.args(["--data-binary", "@-"]);
+    }
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("curl is needed for network features but was not found on PATH")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(body.unwrap_or_default().as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = text.rsplit_once('\n').unwrap_or(("", &text));
+    let status: u16 = status.trim().parse().unwrap_or(0);
+    tracing::debug!(method, url, status, "http request");
+    if !(200..300).contains(&status) {
+        bail!("{} {} returned HTTP {}", method, url, status);
+    }
+    Ok(body.to_string())
+}
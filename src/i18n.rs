@@ -0,0 +1,142 @@
+//! Interface text, looked up by id in Fluent files so the UI can be
+//! translated independently of puzzle packs.
+//!
+//! Bundled locales live in `locales/<locale>/ui.ftl` and are compiled in. A
+//! file at `~/.config/rust-raid/locales/<locale>.ftl` is read at startup and
+//! takes precedence, so translators can work without rebuilding. Messages a
+//! translation lacks fall back to `en-US`.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+use crate::paths;
+
+pub const DEFAULT_LOCALE: &str = "en-US";
+
+/// Locales compiled into the binary, as (locale, Fluent source).
+const BUNDLED: &[(&str, &str)] = &[(DEFAULT_LOCALE, include_str!("../locales/en-US/ui.ftl"))];
+
+/// Looks up UI text: `t!("title-new-game")` or `t!("hud-gold", gold = app.gold)`.
+macro_rules! t {
+    ($id:expr) => {
+        $crate::i18n::text($id, None)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::text($id, Some(&args))
+    }};
+}
+pub(crate) use t;
+
+/// Bundles to try in order: the chosen locale first, `en-US` last.
+struct Localizer {
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Picks the UI language: `locale` from the settings, or the environment
+/// (`LC_ALL`, `LC_MESSAGES`, `LANG`) when unset. Only the first call counts.
+pub fn init(locale: Option<&str>) {
+    let _ = LOCALIZER.set(Localizer::new(locale));
+}
+
+fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(|| Localizer::new(None))
+}
+
+/// The text for `id`, or the id itself if no locale has it (so a missing
+/// message is visible rather than an empty line).
+pub fn text(id: &str, args: Option<&FluentArgs>) -> String {
+    for bundle in &localizer().bundles {
+        let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) else {
+            continue;
+        };
+        let mut errors = Vec::new();
+        let value = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            tracing::debug!(id, ?errors, "message formatted with errors");
+        }
+        return value.into_owned();
+    }
+    tracing::warn!(id, "missing UI message");
+    id.to_string()
+}
+
+/// Whether any locale has a message for `id`, for text that only some ids
+/// come with.
+pub fn has(id: &str) -> bool {
+    localizer()
+        .bundles
+        .iter()
+        .any(|bundle| bundle.has_message(id))
+}
+
+impl Localizer {
+    fn new(locale: Option<&str>) -> Self {
+        let wanted = locale
+            .map(str::to_string)
+            .or_else(locale_from_env)
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+        let mut bundles = Vec::new();
+        for candidate in candidates(&wanted) {
+            let user_file = paths::config_dir()
+                .map(|dir| dir.join("locales").join(format!("{}.ftl", candidate)))
+                .and_then(|path| std::fs::read_to_string(path).ok());
+            if let Some(source) = user_file {
+                bundles.extend(bundle(&candidate, source));
+            }
+            if let Some((_, source)) = BUNDLED.iter().find(|(name, _)| *name == candidate) {
+                bundles.extend(bundle(&candidate, source.to_string()));
+            }
+        }
+        tracing::debug!(locale = %wanted, bundles = bundles.len(), "loaded UI text");
+        Localizer { bundles }
+    }
+}
+
+/// `de-AT` is tried as `de-AT`, then `de`; `en-US` always comes last.
+fn candidates(locale: &str) -> Vec<String> {
+    let mut list = vec![locale.to_string()];
+    if let Some((language, _)) = locale.split_once('-') {
+        list.push(language.to_string());
+    }
+    list.push(DEFAULT_LOCALE.to_string());
+    list.dedup();
+    list
+}
+
+fn bundle(locale: &str, source: String) -> Option<FluentBundle<FluentResource>> {
+    let langid: LanguageIdentifier = locale.parse().ok()?;
+    let resource = FluentResource::try_new(source).unwrap_or_else(|(resource, errors)| {
+        tracing::warn!(
+            locale,
+            ?errors,
+            "UI text has syntax errors; using what parsed"
+        );
+        resource
+    });
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // Unicode isolation marks show up as stray characters in many terminals
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        tracing::warn!(locale, ?errors, "duplicate UI messages");
+    }
+    Some(bundle)
+}
+
+/// `de_AT.UTF-8` becomes `de-AT`; `C` and `POSIX` mean no preference.
+fn locale_from_env() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            let value = value.split(['.', '@']).next().unwrap_or_default();
+            value.replace('_', "-")
+        })
+        .filter(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
@@ -0,0 +1,111 @@
+//! Keys that mean the same thing on every screen; the rest go to the screen.
+
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, KeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
+};
+use std::io::Write;
+
+use crate::app::App;
+use crate::engine::Flow;
+use crate::screens;
+
+/// How the terminal encodes keys, as found at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum KeyProtocol {
+    /// Not asked, e.g. when the game isn't running in a terminal
+    #[default]
+    Unknown,
+    /// Classic escape sequences, where Ctrl+Shift chords and some F-keys get lost
+    Legacy,
+    /// The kitty keyboard protocol, which reports every key unambiguously
+    Enhanced,
+}
+
+/// Stand-ins for keys that legacy encodings or multiplexers tend to swallow:
+/// Alt plus the character acts as the key. Keep `keys-help` in step.
+const FALLBACK_CHORDS: [(char, KeyCode, KeyModifiers); 10] = [
+    ('1', KeyCode::F(1), KeyModifiers::NONE),
+    ('2', KeyCode::F(2), KeyModifiers::NONE),
+    ('3', KeyCode::F(3), KeyModifiers::NONE),
+    ('4', KeyCode::F(4), KeyModifiers::NONE),
+    ('5', KeyCode::F(5), KeyModifiers::NONE),
+    ('6', KeyCode::F(6), KeyModifiers::NONE),
+    ('7', KeyCode::F(7), KeyModifiers::NONE),
+    ('8', KeyCode::F(8), KeyModifiers::NONE),
+    ('9', KeyCode::F(9), KeyModifiers::NONE),
+    (
+        'z',
+        KeyCode::Char('Z'),
+        KeyModifiers::CONTROL.union(KeyModifiers::SHIFT),
+    ),
+];
+
+/// Asks the terminal for the kitty keyboard protocol and turns it on if it's
+/// there. Must run in raw mode; undo it with `PopKeyboardEnhancementFlags`.
+pub(crate) fn enable_enhanced_keys(out: &mut impl Write) -> KeyProtocol {
+    match crossterm::terminal::supports_keyboard_enhancement() {
+        Ok(true) => {
+            let flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS;
+            match crossterm::execute!(out, PushKeyboardEnhancementFlags(flags)) {
+                Ok(()) => KeyProtocol::Enhanced,
+                Err(e) => {
+                    tracing::debug!(error = %e, "could not enable the kitty keyboard protocol");
+                    KeyProtocol::Legacy
+                }
+            }
+        }
+        Ok(false) => KeyProtocol::Legacy,
+        Err(e) => {
+            tracing::debug!(error = %e, "keyboard protocol query failed");
+            KeyProtocol::Legacy
+        }
+    }
+}
+
+/// Makes the same key look the same under every encoding: drops releases
+/// (the Windows console reports them), turns `Shift+a` into `A`, and maps the
+/// fallback chords onto the keys they stand in for.
+fn normalize(mut key: KeyEvent) -> Option<KeyEvent> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    if let KeyCode::Char(c) = key.code {
+        if key.modifiers.contains(KeyModifiers::SHIFT) && c.is_ascii_lowercase() {
+            key.code = KeyCode::Char(c.to_ascii_uppercase());
+        }
+        if key.modifiers == KeyModifiers::ALT {
+            if let Some(&(_, code, modifiers)) = FALLBACK_CHORDS
+                .iter()
+                .find(|(chord, _, _)| *chord == c.to_ascii_lowercase())
+            {
+                key.code = code;
+                key.modifiers = modifiers;
+            }
+        }
+    }
+    Some(key)
+}
+
+impl App<'_> {
+    /// Applies one key press to whatever screen is showing.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Flow {
+        let Some(key) = normalize(key) else {
+            return Flow::Continue;
+        };
+        self.last_key = std::time::Instant::now();
+
+        // Global Ctrl+C handler - always quit
+        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+            return Flow::Quit;
+        }
+
+        // Any key finishes a running animation instead of acting on the screen beneath it
+        if self.skip_animations() {
+            return Flow::Continue;
+        }
+
+        screens::for_state(self.state).handle_key(self, key)
+    }
+}
@@ -0,0 +1,149 @@
+//! What the player carries. Rooms and events hand out items, a full pack
+//! means leaving something behind, and some doors and strangers take items.
+
+use serde::{Deserialize, Serialize};
+
+use crate::modifiers::Adjustment;
+use crate::puzzle::types::{Rewards, Ware};
+
+/// Slots before any pack is found.
+pub const BASE_CAPACITY: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    /// Carried for its effect, or just for the story
+    #[default]
+    Relic,
+    /// Opens a door somewhere
+    Key,
+    /// Adds slots; worn rather than carried, so it takes none itself
+    Pack,
+}
+
+/// What a shop sells to be used up rather than carried. None of it takes a slot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Supplies {
+    /// Hints that cost no HP
+    pub hints: u32,
+    /// Reads of a room's locked-line intent
+    pub intent_scrolls: u32,
+    /// Compile errors that cost no HP
+    pub shields: u32,
+}
+
+impl Supplies {
+    pub fn is_empty(&self) -> bool {
+        *self == Supplies::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub name: String,
+    pub description: String,
+    pub kind: ItemKind,
+    /// Extra capacity, for packs
+    pub slots: usize,
+    /// What carrying the item changes
+    pub modifiers: Vec<Adjustment>,
+}
+
+impl Item {
+    /// The item a room's rewards grant, if any.
+    pub fn from_rewards(rewards: &Rewards) -> Option<Item> {
+        let name = rewards.grants_item.clone()?;
+        Some(Item {
+            name,
+            description: rewards
+                .item_description
+                .clone()
+                .unwrap_or_else(|| crate::i18n::t!("item-default-description")),
+            kind: rewards.item_kind,
+            slots: rewards.item_slots,
+            modifiers: rewards.modifiers.clone(),
+        })
+    }
+
+    /// The item a shop sells as `ware`.
+    pub fn from_ware(ware: &Ware) -> Item {
+        Item {
+            name: ware.name.clone(),
+            description: ware
+                .description
+                .clone()
+                .unwrap_or_else(|| crate::i18n::t!("item-default-description")),
+            kind: ware.kind,
+            slots: ware.slots,
+            modifiers: ware.modifiers.clone(),
+        }
+    }
+
+    fn takes_slot(&self) -> bool {
+        self.kind != ItemKind::Pack
+    }
+}
+
+#[derive(Default)]
+pub struct Inventory {
+    items: Vec<Item>,
+}
+
+impl Inventory {
+    pub fn capacity(&self) -> usize {
+        BASE_CAPACITY + self.items.iter().map(|i| i.slots).sum::<usize>()
+    }
+
+    /// Slots in use; packs don't count.
+    pub fn used(&self) -> usize {
+        self.items.iter().filter(|i| i.takes_slot()).count()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.used() >= self.capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.items.iter().any(|i| i.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Item> {
+        self.items.get(index)
+    }
+
+    /// Stows `item`, or hands it back if there's no room for it.
+    pub fn add(&mut self, item: Item) -> Result<(), Item> {
+        if item.takes_slot() && self.is_full() {
+            return Err(item);
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    /// Takes out the item called `name` (ignoring case). Packs stay on, since
+    /// taking one off could leave more items than slots.
+    pub fn remove(&mut self, name: &str) -> Option<Item> {
+        let index = self
+            .items
+            .iter()
+            .position(|i| i.takes_slot() && i.name.eq_ignore_ascii_case(name))?;
+        Some(self.items.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
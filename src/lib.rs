@@ -0,0 +1,62 @@
+//! Rust Raid: a dungeon crawler where every lock is a Rust compiler error.
+//!
+//! The `rust-raid` binary is a thin command-line wrapper around this crate.
+//! [`tui::run`] plays a session in the terminal; other frontends can drive an
+//! [`App`] through the [`GameEngine`] trait and plug in their own
+//! [`PuzzleSource`] or [`Validator`].
+
+mod achievements;
+mod anim;
+pub mod app;
+mod archive;
+mod attract;
+pub mod bugreport;
+mod campaigns;
+mod card;
+mod codex;
+mod commands;
+pub mod compiler;
+mod coop;
+pub mod engine;
+mod feed;
+mod feedback;
+mod ghost;
+mod glyphs;
+pub mod headless;
+mod highlight;
+mod http;
+mod i18n;
+mod input;
+mod inventory;
+pub mod logging;
+mod markdown;
+mod menu;
+mod mismatch;
+mod modifiers;
+mod notify;
+#[cfg(feature = "ai-hints")]
+mod oracle;
+mod paths;
+mod placement;
+mod presence;
+pub mod profile;
+pub mod puzzle;
+mod replay;
+mod save;
+mod screens;
+pub mod settings;
+mod sound;
+mod stddoc;
+mod store;
+pub mod sync;
+pub mod telemetry;
+pub mod theme;
+pub mod timer;
+mod toast;
+pub mod tui;
+mod ui;
+mod update;
+
+pub use app::{App, GameState};
+pub use engine::{Flow, GameEngine, PuzzleDir, PuzzleSource, Validator};
+pub use ui::draw_ui;
@@ -0,0 +1,36 @@
+use anyhow::Result;
+use std::path::PathBuf;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+
+use crate::paths;
+
+/// Where `--debug` writes its logs (`~/.local/share/rust-raid/logs`).
+pub fn log_dir() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("logs"))
+}
+
+/// Starts writing debug logs to a daily file in [`log_dir`]. The returned guard
+/// flushes pending lines when dropped, so keep it alive until the game exits.
+///
+/// `RUST_LOG` can narrow or widen what gets recorded; the default is `debug`
+/// for this crate only.
+pub fn init() -> Result<(WorkerGuard, PathBuf)> {
+    let dir = log_dir().ok_or_else(|| anyhow::anyhow!("No home directory found"))?;
+    std::fs::create_dir_all(&dir)?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("rust-raid")
+        .filename_suffix("log")
+        .max_log_files(7)
+        .build(&dir)?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("rust_raid=debug"));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false)
+        .init();
+    Ok((guard, dir))
+}
@@ -0,0 +1,128 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Renders the small Markdown subset puzzle authors use into styled lines.
+///
+/// Supported: `**bold**`, `*italic*` / `_italic_`, `` `inline code` ``,
+/// fenced code blocks, `# headings` and `> quotes`. Anything that doesn't
+/// parse as markup is shown verbatim, so plain text is always safe.
+pub fn render(text: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for raw in text.lines() {
+        if raw.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            // Code blocks keep the surrounding style so compiler output stays readable
+            lines.push(Line::from(Span::raw(raw.to_string())));
+            continue;
+        }
+
+        let trimmed = raw.trim_start();
+        if let Some(heading) = trimmed.strip_prefix("# ") {
+            let style = Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            lines.push(Line::from(inline(heading, style)));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let style = Style::default().add_modifier(Modifier::ITALIC);
+            let mut spans = vec![Span::styled("┃ ", Style::default().fg(Color::DarkGray))];
+            spans.extend(inline(quote.strip_prefix(' ').unwrap_or(quote), style));
+            lines.push(Line::from(spans));
+        } else {
+            lines.push(Line::from(inline(raw, Style::default())));
+        }
+    }
+
+    lines
+}
+
+fn inline(text: &str, style: Style) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '`' {
+            if let Some(end) = find_char(&chars, i + 1, '`') {
+                flush(&mut plain, style, &mut spans);
+                let code: String = chars[i + 1..end].iter().collect();
+                spans.push(Span::styled(code, style.fg(Color::LightYellow)));
+                i = end + 1;
+                continue;
+            }
+        }
+
+        if c == '*' || c == '_' {
+            let double = chars.get(i + 1) == Some(&c);
+            let width = if double { 2 } else { 1 };
+            if let Some(end) = find_closing(&chars, i, c, width) {
+                flush(&mut plain, style, &mut spans);
+                let inner: String = chars[i + width..end].iter().collect();
+                let modifier = if double {
+                    Modifier::BOLD
+                } else {
+                    Modifier::ITALIC
+                };
+                spans.extend(inline(&inner, style.add_modifier(modifier)));
+                i = end + width;
+                continue;
+            }
+        }
+
+        plain.push(c);
+        i += 1;
+    }
+
+    flush(&mut plain, style, &mut spans);
+    spans
+}
+
+fn flush(plain: &mut String, style: Style, spans: &mut Vec<Span<'static>>) {
+    if !plain.is_empty() {
+        spans.push(Span::styled(std::mem::take(plain), style));
+    }
+}
+
+fn find_char(chars: &[char], from: usize, target: char) -> Option<usize> {
+    (from..chars.len()).find(|&j| chars[j] == target)
+}
+
+/// Finds the matching closing delimiter for an emphasis run starting at `start`.
+///
+/// Openers must be followed by a non-space, non-delimiter character and closers
+/// preceded by one, which keeps things like `*** ROOM CLEARED! ***` or `*x += 1`
+/// literal. Underscores additionally must not sit inside a word (`light_statue`).
+fn find_closing(chars: &[char], start: usize, delim: char, width: usize) -> Option<usize> {
+    let after = *chars.get(start + width)?;
+    if after.is_whitespace() || after == delim {
+        return None;
+    }
+    if delim == '_' && start > 0 && chars[start - 1].is_alphanumeric() {
+        return None;
+    }
+
+    let mut j = start + width + 1;
+    while j + width <= chars.len() {
+        let run = chars[j..j + width].iter().all(|&ch| ch == delim);
+        let before = chars[j - 1];
+        let next = chars.get(j + width).copied();
+        let closes = run
+            && !before.is_whitespace()
+            && before != delim
+            && next != Some(delim)
+            && !(delim == '_' && next.is_some_and(char::is_alphanumeric));
+        if closes {
+            return Some(j);
+        }
+        j += 1;
+    }
+    None
+}
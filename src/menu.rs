@@ -0,0 +1,101 @@
+/// A vertical list of actions where unavailable entries are shown but can't be selected.
+pub struct Menu<T> {
+    items: Vec<MenuItem<T>>,
+    selected: usize,
+}
+
+pub struct MenuItem<T> {
+    /// Fluent id of the text shown for the item.
+    pub label: &'static str,
+    pub action: T,
+    pub enabled: bool,
+}
+
+impl<T: Copy + PartialEq> Menu<T> {
+    pub fn new(items: Vec<MenuItem<T>>) -> Self {
+        let mut menu = Menu { items, selected: 0 };
+        menu.reset();
+        menu
+    }
+
+    /// Moves the cursor back to the first enabled entry.
+    pub fn reset(&mut self) {
+        self.selected = self.items.iter().position(|i| i.enabled).unwrap_or(0);
+    }
+
+    pub fn next(&mut self) {
+        self.step(1);
+    }
+
+    pub fn previous(&mut self) {
+        self.step(self.items.len().saturating_sub(1));
+    }
+
+    fn step(&mut self, offset: usize) {
+        let len = self.items.len();
+        for _ in 0..len {
+            self.selected = (self.selected + offset) % len;
+            if self.items[self.selected].enabled {
+                return;
+            }
+        }
+    }
+
+    /// Makes the entry for `action` selectable or not. The cursor moves to the
+    /// first enabled entry, as it does on a new menu.
+    pub fn set_enabled(&mut self, action: T, enabled: bool) {
+        for item in self.items.iter_mut().filter(|i| i.action == action) {
+            item.enabled = enabled;
+        }
+        self.reset();
+    }
+
+    /// Highlights the entry for `action`, if it is enabled.
+    pub fn select(&mut self, action: T) {
+        if let Some(index) = self
+            .items
+            .iter()
+            .position(|i| i.enabled && i.action == action)
+        {
+            self.selected = index;
+        }
+    }
+
+    /// The highlighted action, if it is currently available.
+    pub fn selected(&self) -> Option<T> {
+        self.items
+            .get(self.selected)
+            .filter(|i| i.enabled)
+            .map(|i| i.action)
+    }
+
+    /// Entries paired with whether each one is highlighted.
+    pub fn entries(&self) -> impl Iterator<Item = (&MenuItem<T>, bool)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(move |(i, item)| (item, i == self.selected))
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl<T> MenuItem<T> {
+    pub fn new(label: &'static str, action: T) -> Self {
+        MenuItem {
+            label,
+            action,
+            enabled: true,
+        }
+    }
+
+    pub fn disabled(label: &'static str, action: T) -> Self {
+        MenuItem {
+            label,
+            action,
+            enabled: false,
+        }
+    }
+}
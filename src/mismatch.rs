@@ -0,0 +1,61 @@
+//! Where a wrong answer first goes wrong: the line and column at which the
+//! output stops matching, shown as a short excerpt with a caret.
+
+use crate::i18n::t;
+
+/// The first point where `got` differs from `expected` (both 0-based).
+pub struct Mismatch<'a> {
+    pub line: usize,
+    pub column: usize,
+    /// None when one output ran out of lines before the other
+    pub expected: Option<&'a str>,
+    pub got: Option<&'a str>,
+}
+
+/// Compares line by line, so Windows line endings or a missing trailing
+/// newline don't count as differences. None if the outputs match.
+pub fn find<'a>(expected: &'a str, got: &'a str) -> Option<Mismatch<'a>> {
+    let mut expected_lines = expected.lines();
+    let mut got_lines = got.lines();
+    for line in 0.. {
+        let (expected, got) = (expected_lines.next(), got_lines.next());
+        let column = match (expected, got) {
+            (None, None) => return None,
+            (Some(e), Some(g)) if e == g => continue,
+            (Some(e), Some(g)) => e.chars().zip(g.chars()).take_while(|(a, b)| a == b).count(),
+            _ => 0,
+        };
+        return Some(Mismatch {
+            line,
+            column,
+            expected,
+            got,
+        });
+    }
+    None
+}
+
+impl Mismatch<'_> {
+    /// Three lines for a code block: the expected line, the line printed
+    /// instead, and a caret under the first character that differs.
+    pub fn excerpt(&self) -> String {
+        let expected_label = t!("mismatch-expected");
+        let got_label = t!("mismatch-got");
+        let width = expected_label
+            .chars()
+            .count()
+            .max(got_label.chars().count());
+        let missing = t!("mismatch-no-line");
+        let mut excerpt = format!(
+            "{:>width$} {}\n{:>width$} {}",
+            expected_label,
+            self.expected.unwrap_or(&missing),
+            got_label,
+            self.got.unwrap_or(&missing),
+        );
+        if self.expected.is_some() && self.got.is_some() {
+            excerpt.push_str(&format!("\n{}^", " ".repeat(width + 1 + self.column)));
+        }
+        excerpt
+    }
+}
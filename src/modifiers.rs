@@ -0,0 +1,179 @@
+//! Buffs and debuffs. Whatever a hint or a wrong answer costs and whatever a
+//! cleared room pays is worked out from the room's base amount through the
+//! run's [`Modifiers`], which come from the difficulty, items and events.
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+
+/// A scored amount that modifiers can change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stat {
+    /// HP a hint costs (the spirit and the oracle charge multiples of it)
+    HintHp,
+    /// HP lost to a failed run
+    WrongAnswerHp,
+    /// Gold paid for clearing a room
+    Gold,
+}
+
+/// A change to one stat, as written in room and event files:
+/// `{ stat = "hint_hp", percent = -50 }` halves hint costs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Adjustment {
+    pub stat: Stat,
+    #[serde(default)]
+    pub percent: i32,
+    /// Added after the percentages
+    #[serde(default)]
+    pub flat: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Difficulty,
+    Item,
+    Event,
+}
+
+pub struct Modifier {
+    pub name: String,
+    pub source: Source,
+    pub adjustments: Vec<Adjustment>,
+}
+
+/// How forgiving a run is; set in the settings, applied from the next new game.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Compile errors in a row in one room before mercy halves its penalties
+    /// and explains the errors plainly; Hard never shows it.
+    pub fn mercy_after(self) -> Option<u32> {
+        match self {
+            Difficulty::Easy => Some(2),
+            Difficulty::Normal => Some(3),
+            Difficulty::Hard => None,
+        }
+    }
+
+    fn adjustments(self) -> Vec<Adjustment> {
+        let adjust = |stat, percent| Adjustment {
+            stat,
+            percent,
+            flat: 0,
+        };
+        match self {
+            Difficulty::Easy => vec![adjust(Stat::HintHp, -40), adjust(Stat::WrongAnswerHp, -50)],
+            Difficulty::Normal => Vec::new(),
+            // Harder rooms pay better, so there's a reason to pick it
+            Difficulty::Hard => vec![
+                adjust(Stat::HintHp, 60),
+                adjust(Stat::WrongAnswerHp, 100),
+                adjust(Stat::Gold, 25),
+            ],
+        }
+    }
+}
+
+/// The modifiers active in this run.
+#[derive(Default)]
+pub struct Modifiers {
+    difficulty: Difficulty,
+    active: Vec<Modifier>,
+}
+
+impl Modifiers {
+    /// A fresh run's modifiers: just the difficulty's.
+    pub fn new(difficulty: Difficulty) -> Self {
+        let mut modifiers = Modifiers {
+            difficulty,
+            ..Modifiers::default()
+        };
+        let name = t!(match difficulty {
+            Difficulty::Easy => "difficulty-easy",
+            Difficulty::Normal => "difficulty-normal",
+            Difficulty::Hard => "difficulty-hard",
+        });
+        modifiers.add(name, Source::Difficulty, difficulty.adjustments());
+        modifiers
+    }
+
+    /// The difficulty the run started on.
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Adds a modifier; ones that change nothing are left out.
+    pub fn add(&mut self, name: String, source: Source, adjustments: Vec<Adjustment>) {
+        if !adjustments.is_empty() {
+            self.active.push(Modifier {
+                name,
+                source,
+                adjustments,
+            });
+        }
+    }
+
+    /// Drops the modifier `name` brought in, e.g. when an item is dropped.
+    pub fn remove(&mut self, name: &str, source: Source) {
+        self.active
+            .retain(|m| !(m.source == source && m.name == name));
+    }
+
+    /// `base` after every active modifier: percentages add up first, then the
+    /// flat amounts. Never below zero.
+    pub fn apply(&self, stat: Stat, base: u32) -> u32 {
+        let (percent, flat) = self
+            .active
+            .iter()
+            .flat_map(|m| &m.adjustments)
+            .filter(|a| a.stat == stat)
+            .fold((100i64, 0i64), |(percent, flat), a| {
+                (percent + i64::from(a.percent), flat + i64::from(a.flat))
+            });
+        (i64::from(base) * percent.max(0) / 100 + flat).max(0) as u32
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Modifier> {
+        self.active.iter()
+    }
+}
+
+impl Adjustment {
+    /// Whether this makes the run easier.
+    pub fn helps(&self) -> bool {
+        let change = self.percent + self.flat;
+        match self.stat {
+            Stat::HintHp | Stat::WrongAnswerHp => change < 0,
+            Stat::Gold => change > 0,
+        }
+    }
+
+    /// Short text for the status bar, such as "hints -40%".
+    pub fn describe(&self) -> String {
+        let mut change = Vec::new();
+        if self.percent != 0 {
+            change.push(format!("{:+}%", self.percent));
+        }
+        if self.flat != 0 {
+            change.push(format!("{:+}", self.flat));
+        }
+        let change = change.join(" ");
+        t!(
+            match self.stat {
+                Stat::HintHp => "modifier-hint-hp",
+                Stat::WrongAnswerHp => "modifier-wrong-answer-hp",
+                Stat::Gold => "modifier-gold",
+            },
+            change = change.as_str()
+        )
+    }
+}
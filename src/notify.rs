@@ -0,0 +1,34 @@
+//! Tells a player who switched away that a slow run has a verdict.
+
+use std::io::Write;
+use std::time::Duration;
+
+use crate::settings::VerdictAlert;
+
+/// Runs shorter than this finish before anyone has time to look away.
+pub const SLOW_RUN: Duration = Duration::from_secs(3);
+
+pub fn send(alert: VerdictAlert, verdict: &str) {
+    match alert {
+        VerdictAlert::Off => {}
+        VerdictAlert::Bell => bell(),
+        VerdictAlert::Desktop => {
+            #[cfg(feature = "desktop-notify")]
+            match notify_rust::Notification::new()
+                .summary("Rust Raid")
+                .body(verdict)
+                .show()
+            {
+                Ok(_) => return,
+                Err(e) => tracing::debug!(error = %e, "desktop notification failed"),
+            }
+            let _ = verdict;
+            bell();
+        }
+    }
+}
+
+fn bell() {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+}
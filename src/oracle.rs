@@ -0,0 +1,96 @@
+//! AI hints for `:oracle`. Compiled in with `--features ai-hints` and only
+//! active when `RUST_RAID_AI_KEY` is set.
+//!
+//! The request carries the room's title and concept plus the compiler error,
+//! trimmed and with local paths removed. The error quotes the lines rustc
+//! complained about, but the rest of the buffer and the room's reference
+//! solution are never sent. Any OpenAI-compatible chat completions endpoint
+//! works; see `ai_hint_endpoint` and `ai_hint_model` in the settings.
+
+use anyhow::{Context, Result};
+use std::sync::mpsc::{self, Receiver};
+
+use crate::http;
+
+/// How many lines of the compiler error are sent; the first error is what matters.
+const MAX_ERROR_LINES: usize = 40;
+
+const SYSTEM_PROMPT: &str = "You are the oracle in a dungeon crawler that teaches Rust. \
+     The player is stuck on a compiler error. Reply with one or two short Socratic \
+     questions or nudges that lead them toward the fix. Never write the corrected code \
+     and never quote a full solution.";
+
+/// What the oracle gets to see.
+pub struct Question {
+    pub room_title: String,
+    pub concept: String,
+    pub compiler_error: String,
+}
+
+/// Asks on a background thread so the UI keeps drawing while the request is out.
+pub fn spawn_ask(
+    question: Question,
+    endpoint: String,
+    model: String,
+) -> Receiver<Result<String, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = ask(&question, &endpoint, &model).map_err(|e| format!("{:#}", e));
+        let _ = tx.send(result);
+    });
+    rx
+}
+
+fn api_key() -> Result<String> {
+    std::env::var("RUST_RAID_AI_KEY")
+        .ok()
+        .filter(|key| !key.is_empty())
+        .context("Set RUST_RAID_AI_KEY to an API key for your hint endpoint")
+}
+
+#[tracing::instrument(skip_all, fields(endpoint, model))]
+fn ask(question: &Question, endpoint: &str, model: &str) -> Result<String> {
+    let key = api_key()?;
+    let prompt = format!(
+        "Room: {}\nRust concept: {}\n\nCompiler error:\n{}",
+        question.room_title,
+        question.concept,
+        sanitize(&question.compiler_error)
+    );
+    let request = serde_json::json!({
+        "model": model,
+        "max_tokens": 200,
+        "messages": [
+            { "role": "system", "content": SYSTEM_PROMPT },
+            { "role": "user", "content": prompt },
+        ],
+    });
+    let headers = [
+        "Content-Type: application/json".to_string(),
+        format!("Authorization: Bearer {}", key),
+    ];
+    let body = http::send("POST", endpoint, &headers, Some(&request.to_string()), 30)
+        .context("Could not reach the hint endpoint")?;
+    let response: serde_json::Value =
+        serde_json::from_str(&body).context("Unexpected reply from the hint endpoint")?;
+    let hint = response["choices"][0]["message"]["content"]
+        .as_str()
+        .map(str::trim)
+        .filter(|hint| !hint.is_empty())
+        .context("The hint endpoint sent an empty answer")?;
+    Ok(hint.to_string())
+}
+
+/// Cuts the error down and replaces anything that looks like a local path.
+fn sanitize(error: &str) -> String {
+    let home = crate::paths::home_dir().map(|home| home.display().to_string());
+    error
+        .lines()
+        .take(MAX_ERROR_LINES)
+        .map(|line| match &home {
+            Some(home) if !home.is_empty() => line.replace(home.as_str(), "~"),
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
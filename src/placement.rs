@@ -0,0 +1,75 @@
+//! A placement quiz in progress. Questions come a floor at a time, easiest
+//! first; the first wrong answer ends the quiz, and the player starts on the
+//! deepest floor whose questions they all got right.
+
+use crate::puzzle::{Question, Quiz};
+
+pub struct Placement {
+    quiz: Quiz,
+    current: usize,
+    /// The highlighted choice
+    pub cursor: usize,
+    /// Deepest floor proven so far; 1 until a floor's questions are all right
+    passed: usize,
+    /// The question answered wrongly, which ends the quiz
+    missed: Option<usize>,
+}
+
+impl Placement {
+    pub fn new(quiz: Quiz) -> Self {
+        Placement {
+            quiz,
+            current: 0,
+            cursor: 0,
+            passed: 1,
+            missed: None,
+        }
+    }
+
+    /// The question being asked; None once the quiz is over.
+    pub fn question(&self) -> Option<&Question> {
+        if self.missed.is_some() {
+            return None;
+        }
+        self.quiz.questions.get(self.current)
+    }
+
+    /// (number of the current question, how many there are)
+    pub fn progress(&self) -> (usize, usize) {
+        (self.current + 1, self.quiz.questions.len())
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        if let Some(question) = self.question() {
+            let last = question.choices.len().saturating_sub(1);
+            self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Locks in the highlighted choice.
+    pub fn answer(&mut self) {
+        let Some(question) = self.question() else {
+            return;
+        };
+        if question.choices.get(self.cursor) != Some(&question.answer) {
+            self.missed = Some(self.current);
+            return;
+        }
+        let floor = question.floor;
+        self.current += 1;
+        self.cursor = 0;
+        if self.question().is_none_or(|next| next.floor != floor) {
+            self.passed = floor;
+        }
+    }
+
+    /// The question that ended the quiz, if one did.
+    pub fn missed(&self) -> Option<&Question> {
+        self.missed.and_then(|i| self.quiz.questions.get(i))
+    }
+
+    /// The floor the player has earned a start on.
+    pub fn floor(&self) -> usize {
+        self.passed
+    }
+}
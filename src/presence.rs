@@ -0,0 +1,81 @@
+//! Discord Rich Presence: shows the current floor, room and time in the room on
+//! the player's Discord profile. Compiled in with `--features discord` and only
+//! active when `discord_presence = true` is set in the settings.
+
+#[cfg(feature = "discord")]
+use discord_rich_presence::{activity, DiscordIpc, DiscordIpcClient};
+
+/// The Discord application the presence is published under. Packagers set it at
+/// build time; without it the feature stays dormant.
+#[cfg(feature = "discord")]
+const APPLICATION_ID: Option<&str> = option_env!("RUST_RAID_DISCORD_APP_ID");
+
+/// What the presence currently shows, so unchanged updates aren't resent.
+#[derive(Clone, PartialEq)]
+pub struct Status {
+    pub details: String,
+    pub state: String,
+    /// Unix time the room was entered; Discord counts up from it.
+    pub started_at: i64,
+}
+
+#[derive(Default)]
+pub struct Presence {
+    #[cfg(feature = "discord")]
+    client: Option<DiscordIpcClient>,
+    current: Option<Status>,
+}
+
+impl Presence {
+    /// Connects to a running Discord client if enabled. Failing to find one is
+    /// normal (Discord closed, feature not built) and leaves a no-op presence.
+    pub fn connect(enabled: bool) -> Self {
+        #[cfg(feature = "discord")]
+        if enabled {
+            let client = APPLICATION_ID.and_then(|id| {
+                let mut client = DiscordIpcClient::new(id);
+                match client.connect() {
+                    Ok(()) => Some(client),
+                    Err(e) => {
+                        tracing::debug!(error = %e, "discord not available");
+                        None
+                    }
+                }
+            });
+            return Presence {
+                client,
+                current: None,
+            };
+        }
+        let _ = enabled;
+        Presence::default()
+    }
+
+    pub fn update(&mut self, status: Status) {
+        if self.current.as_ref() == Some(&status) {
+            return;
+        }
+        #[cfg(feature = "discord")]
+        if let Some(client) = self.client.as_mut() {
+            let activity = activity::Activity::new()
+                .details(status.details.as_str())
+                .state(status.state.as_str())
+                .timestamps(activity::Timestamps::new().start(status.started_at));
+            if let Err(e) = client.set_activity(activity) {
+                tracing::debug!(error = %e, "discord update failed; disconnecting");
+                self.client = None;
+            }
+        }
+        self.current = Some(status);
+    }
+}
+
+impl Drop for Presence {
+    fn drop(&mut self) {
+        #[cfg(feature = "discord")]
+        if let Some(client) = self.client.as_mut() {
+            let _ = client.clear_activity();
+            let _ = client.close();
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use crate::paths;
+use crate::store::{self, Versioned};
+
+/// Player facts that outlive a single run (stored under the data dir).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// Whether the how-to-play pages have been shown once.
+    pub seen_onboarding: bool,
+    /// Fastest clear of each room in seconds, by room id.
+    pub best_times: BTreeMap<String, u64>,
+    /// What each campaign has seen across runs, by campaign name.
+    pub campaigns: BTreeMap<String, CampaignProgress>,
+    /// Ids of the achievements earned.
+    pub achievements: BTreeSet<String>,
+}
+
+/// One campaign's progress over every run of it, for the dashboard.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CampaignProgress {
+    /// Ids of the rooms cleared at least once
+    pub cleared: BTreeSet<String>,
+    /// Titles of the codex entries found at least once
+    pub codex: BTreeSet<String>,
+    /// Fastest won run in seconds
+    pub best_run: Option<u64>,
+}
+
+impl Versioned for Profile {
+    const VERSION: u32 = 1;
+}
+
+impl Profile {
+    pub fn load() -> Self {
+        profile_path()
+            .map(|path| store::load(&path))
+            .unwrap_or_default()
+    }
+
+    /// Writes the profile, keeping `backups` earlier copies.
+    pub fn save(&self, backups: usize) -> Result<()> {
+        let path = profile_path().ok_or_else(|| anyhow::anyhow!("No home directory found"))?;
+        store::save(&path, self, backups)
+    }
+}
+
+fn profile_path() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("profile.toml"))
+}
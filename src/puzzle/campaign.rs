@@ -0,0 +1,291 @@
+//! The campaign manifest: `campaign.toml` at the root of a puzzle pack, for
+//! what holds across all of its floors: its name, its economy, and the win
+//! condition for packs where clearing the last floor isn't enough on its own.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct Campaign {
+    /// What the dashboard calls the pack, and what its progress is kept
+    /// under; the pack's directory name if unset
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Who wrote the pack, for the credits
+    #[serde(default)]
+    pub author: Option<String>,
+    /// The terms it's shared under, for the credits
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub win: Option<WinCondition>,
+    #[serde(default)]
+    pub economy: Economy,
+}
+
+/// HP and gold before modifiers, for packs that tune their own difficulty.
+/// A room's `[scoring]` still overrides the penalties for that room.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct Economy {
+    /// HP a run starts with; the altar, potions and events never heal past it
+    pub starting_hp: u32,
+    /// Gold for clearing a room without hints
+    pub room_gold: u32,
+    /// Gold taken off `room_gold` for each hint used in the room
+    pub gold_per_hint: u32,
+    /// The least a clear pays, however many hints it took
+    pub min_room_gold: u32,
+    /// HP a hint costs
+    pub hint_hp: u32,
+    /// HP a failed run costs
+    pub wrong_answer_hp: u32,
+}
+
+impl Default for Economy {
+    fn default() -> Self {
+        Economy {
+            starting_hp: 100,
+            room_gold: 50,
+            gold_per_hint: 10,
+            min_room_gold: 10,
+            hint_hp: 5,
+            wrong_answer_hp: 2,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WinCondition {
+    /// Checked once the last floor is cleared; the run only counts as won if
+    /// it holds
+    pub when: Condition,
+    /// The goal in words, for the player
+    pub description: String,
+}
+
+impl Campaign {
+    /// Reads `path`; a pack without one is won by clearing its last floor.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Campaign::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        let campaign: Campaign = toml::from_str(&content).inspect_err(
+            |e| tracing::warn!(path = %path.display(), error = %e, "invalid campaign manifest"),
+        )?;
+        if campaign.economy.starting_hp == 0 {
+            anyhow::bail!("economy.starting_hp must be at least 1");
+        }
+        Ok(campaign)
+    }
+}
+
+/// Where a run stands, for a [`Condition`] to test.
+#[derive(Debug, Clone, Default)]
+pub struct Standing {
+    pub hp: u32,
+    pub max_hp: u32,
+    pub gold: u32,
+    pub codex: usize,
+    /// Codex entries the pack has to give
+    pub codex_total: usize,
+    pub items: usize,
+    pub secrets: usize,
+    /// Rooms in the pack with a secret
+    pub secrets_total: usize,
+    pub hints: usize,
+    pub errors: u32,
+    /// The current floor's hints and compile errors
+    pub floor_hints: usize,
+    pub floor_errors: u32,
+    /// Runs in the current room
+    pub attempts: u32,
+    /// Seconds taken by whatever is being judged: a room, a floor or the run
+    pub time: u64,
+}
+
+/// Comparisons such as `hp >= 50` or `codex == all`, joined with `and` and
+/// `or`; `and` binds tighter. Stats are `hp`, `gold`, `codex`, `items`,
+/// `secrets`, `hints`, `errors`, `floor_hints`, `floor_errors`, `attempts`
+/// and `time`, and `all` stands for the most `hp`, `codex` or `secrets` can be.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Condition {
+    source: String,
+    /// Alternatives, each a list of comparisons that must all hold
+    any: Vec<Vec<Comparison>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Comparison {
+    stat: Stat,
+    op: Op,
+    target: Target,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Stat {
+    Hp,
+    Gold,
+    Codex,
+    Items,
+    Secrets,
+    Hints,
+    Errors,
+    FloorHints,
+    FloorErrors,
+    Attempts,
+    Time,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Number(u64),
+    All,
+}
+
+impl Condition {
+    pub fn holds(&self, standing: &Standing) -> bool {
+        self.any
+            .iter()
+            .any(|all| all.iter().all(|comparison| comparison.holds(standing)))
+    }
+}
+
+impl Comparison {
+    fn holds(&self, standing: &Standing) -> bool {
+        let value = match self.stat {
+            Stat::Hp => u64::from(standing.hp),
+            Stat::Gold => u64::from(standing.gold),
+            Stat::Codex => standing.codex as u64,
+            Stat::Items => standing.items as u64,
+            Stat::Secrets => standing.secrets as u64,
+            Stat::Hints => standing.hints as u64,
+            Stat::Errors => u64::from(standing.errors),
+            Stat::FloorHints => standing.floor_hints as u64,
+            Stat::FloorErrors => u64::from(standing.floor_errors),
+            Stat::Attempts => u64::from(standing.attempts),
+            Stat::Time => standing.time,
+        };
+        let target = match (self.target, self.stat) {
+            (Target::Number(n), _) => n,
+            (Target::All, Stat::Hp) => u64::from(standing.max_hp),
+            (Target::All, Stat::Codex) => standing.codex_total as u64,
+            (Target::All, Stat::Secrets) => standing.secrets_total as u64,
+            // Turned away when the condition is read
+            (Target::All, _) => return false,
+        };
+        match self.op {
+            Op::Less => value < target,
+            Op::LessOrEqual => value <= target,
+            Op::Equal => value == target,
+            Op::NotEqual => value != target,
+            Op::GreaterOrEqual => value >= target,
+            Op::Greater => value > target,
+        }
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = String;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        let tokens = tokenize(&source)?;
+        let mut any = Vec::new();
+        for alternative in tokens.split(|token| token == "or") {
+            let mut all = Vec::new();
+            for comparison in alternative.split(|token| token == "and") {
+                all.push(parse_comparison(comparison)?);
+            }
+            any.push(all);
+        }
+        Ok(Condition { source, any })
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Words and numbers, and operators whether or not they have spaces around them.
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        let is_op = |c: char| "<>=!".contains(c);
+        let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        if c.is_whitespace() {
+            chars.next();
+        } else if is_op(c) {
+            tokens.push(std::iter::from_fn(|| chars.next_if(|&c| is_op(c))).collect());
+        } else if is_word(c) {
+            tokens.push(std::iter::from_fn(|| chars.next_if(|&c| is_word(c))).collect());
+        } else {
+            return Err(format!("unexpected `{}` in `{}`", c, source));
+        }
+    }
+    if tokens.is_empty() {
+        return Err("the condition is empty".to_string());
+    }
+    Ok(tokens)
+}
+
+fn parse_comparison(tokens: &[String]) -> Result<Comparison, String> {
+    if tokens.is_empty() {
+        return Err("`and` or `or` is missing a comparison on one side".to_string());
+    }
+    let [stat, op, target] = tokens else {
+        return Err(format!(
+            "expected a comparison such as `hp >= 50`, found `{}`",
+            tokens.join(" ")
+        ));
+    };
+    let stat = match stat.as_str() {
+        "hp" => Stat::Hp,
+        "gold" => Stat::Gold,
+        "codex" => Stat::Codex,
+        "items" => Stat::Items,
+        "secrets" => Stat::Secrets,
+        "hints" => Stat::Hints,
+        "errors" => Stat::Errors,
+        "floor_hints" => Stat::FloorHints,
+        "floor_errors" => Stat::FloorErrors,
+        "attempts" => Stat::Attempts,
+        "time" => Stat::Time,
+        other => return Err(format!("unknown stat `{}`", other)),
+    };
+    let op = match op.as_str() {
+        "<" => Op::Less,
+        "<=" => Op::LessOrEqual,
+        "==" => Op::Equal,
+        "!=" => Op::NotEqual,
+        ">=" => Op::GreaterOrEqual,
+        ">" => Op::Greater,
+        other => return Err(format!("unknown operator `{}`", other)),
+    };
+    let target = match target.as_str() {
+        "all" if matches!(stat, Stat::Hp | Stat::Codex | Stat::Secrets) => Target::All,
+        "all" => return Err("`all` only goes with hp, codex or secrets".to_string()),
+        number => Target::Number(
+            number
+                .parse()
+                .map_err(|_| format!("`{}` is not a number", number))?,
+        ),
+    };
+    Ok(Comparison { stat, op, target })
+}
@@ -0,0 +1,84 @@
+//! The event deck: blessings, curses and small trades drawn between rooms.
+//! The cards live in `events.toml` at the root of a puzzle pack, so a pack
+//! can add variety without new rooms.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::modifiers::Adjustment;
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct Deck {
+    /// Percent chance of drawing a card when moving to the next room
+    #[serde(default)]
+    pub chance: u32,
+    #[serde(default, rename = "event")]
+    pub cards: Vec<EventCard>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EventCard {
+    pub title: String,
+    pub text: String,
+    pub kind: EventKind,
+    /// How often the card comes up relative to the others
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    #[serde(default)]
+    pub hp: i32,
+    #[serde(default)]
+    pub gold: i32,
+    /// Lasting effects for the rest of the run
+    #[serde(default)]
+    pub modifiers: Vec<Adjustment>,
+    /// An item the card takes; a trade can't be accepted without it
+    #[serde(default)]
+    pub takes_item: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Blessing,
+    Curse,
+    /// Only happens if the player accepts
+    Trade,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+impl Deck {
+    /// Reads `path`; a pack without an event deck gets an empty one.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Deck::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        let deck: Deck = toml::from_str(&content).inspect_err(
+            |e| tracing::warn!(path = %path.display(), error = %e, "invalid event deck"),
+        )?;
+        tracing::debug!(path = %path.display(), cards = deck.cards.len(), "loaded event deck");
+        Ok(deck)
+    }
+
+    /// The card `seed` turns up, if any; the same seed always draws the same card.
+    pub fn draw(&self, seed: u64) -> Option<&EventCard> {
+        let total: u64 = self.cards.iter().map(|c| u64::from(c.weight)).sum();
+        if total == 0 || seed % 100 >= u64::from(self.chance) {
+            return None;
+        }
+        // The chance roll used the low digits; pick the card from the high bits
+        let mut pick = (seed >> 32) % total;
+        self.cards.iter().find(|card| {
+            let weight = u64::from(card.weight);
+            if pick < weight {
+                return true;
+            }
+            pick -= weight;
+            false
+        })
+    }
+}
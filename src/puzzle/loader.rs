@@ -1,11 +1,69 @@
-use anyhow::Result;
-use std::path::Path;
+use anyhow::{Context, Result};
+use std::path::{Component, Path, PathBuf};
 
-use super::types::Room;
+use super::types::{FloorManifest, Room, ShopManifest};
 
 pub fn load_puzzle(path: &Path) -> Result<Room> {
     let content = std::fs::read_to_string(path)?;
-    let room: Room = toml::from_str(&content)?;
+    let mut room: Room = toml::from_str(&content)
+        .inspect_err(|e| tracing::warn!(path = %path.display(), error = %e, "invalid room file"))?;
+    if let Some(dir) = path.parent() {
+        room.challenge.check.resolve_paths(dir);
+    }
+    for file in &mut room.challenge.files {
+        let plain = file
+            .path
+            .components()
+            .all(|part| matches!(part, Component::Normal(_)));
+        let name = file.path.to_string_lossy();
+        if !plain || name == "solution" || name == "solution.rs" {
+            anyhow::bail!(
+                "{}: puzzle file `{}` has to be a plain relative path, not `solution` or `solution.rs`",
+                path.display(),
+                name
+            );
+        }
+        // Bundled files are read now, so the room doesn't depend on where
+        // it's checked from
+        if let Some(source) = &file.source {
+            let source = path.parent().unwrap_or(Path::new(".")).join(source);
+            file.contents = std::fs::read_to_string(&source)
+                .with_context(|| format!("Could not read puzzle file {}", source.display()))?;
+        }
+    }
+    // Regex rooms fail here, not on the first run
+    let challenge = &room.challenge;
+    let puzzles = std::iter::once((
+        challenge.output_match,
+        &challenge.expected_output,
+        &challenge.tests,
+    ))
+    .chain(
+        challenge
+            .variants
+            .iter()
+            .map(|v| (v.output_match, &v.expected_output, &v.tests)),
+    );
+    for (output_match, expected_output, tests) in puzzles {
+        let expected =
+            std::iter::once(expected_output).chain(tests.iter().map(|t| &t.expected_output));
+        for expected in expected {
+            if let Err(e) = output_match.check(expected) {
+                anyhow::bail!(
+                    "{}: expected output is not a valid pattern: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+    }
+    // Variants without hints of their own share the room's
+    for variant in &mut room.challenge.variants {
+        if variant.hints.is_empty() {
+            variant.hints = room.narrative.hints.clone();
+        }
+    }
+    tracing::debug!(path = %path.display(), id = %room.meta.id, "loaded room");
     Ok(room)
 }
 
@@ -28,5 +86,53 @@ pub fn load_floor(floor_dir: &Path) -> Result<Vec<Room>> {
         rooms.push(room);
     }
 
+    tracing::info!(floor = %floor_dir.display(), rooms = rooms.len(), "loaded floor");
     Ok(rooms)
 }
+
+/// The floors of the pack at `root`, in the order they're played: every
+/// directory named `floor_*` or holding a `floor.toml`, by the manifests'
+/// `order` and then by directory name. Floors without an `order` come last.
+pub fn find_floors(root: &Path) -> Vec<PathBuf> {
+    let mut floors: Vec<(Option<usize>, PathBuf)> = std::fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let named = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("floor_"));
+            path.is_dir() && (named || path.join("floor.toml").exists())
+        })
+        .map(|path| (load_manifest(&path).ok().and_then(|m| m.order), path))
+        .collect();
+    floors.sort_by(|(a, a_path), (b, b_path)| {
+        (a.is_none(), a, a_path).cmp(&(b.is_none(), b, b_path))
+    });
+    floors.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Reads the floor's `floor.toml`, and its `shop.toml` if it has one; a
+/// floor without either gets the defaults.
+pub fn load_manifest(floor_dir: &Path) -> Result<FloorManifest> {
+    let path = floor_dir.join("floor.toml");
+    let mut manifest: FloorManifest = if path.exists() {
+        let content = std::fs::read_to_string(&path)?;
+        toml::from_str(&content).inspect_err(
+            |e| tracing::warn!(path = %path.display(), error = %e, "invalid floor manifest"),
+        )?
+    } else {
+        FloorManifest::default()
+    };
+    let path = floor_dir.join("shop.toml");
+    if path.exists() {
+        let content = std::fs::read_to_string(&path)?;
+        let shop: ShopManifest = toml::from_str(&content).inspect_err(
+            |e| tracing::warn!(path = %path.display(), error = %e, "invalid shop manifest"),
+        )?;
+        manifest.shop.extend(shop.wares);
+        manifest.shop_after_room = shop.after_room;
+    }
+    Ok(manifest)
+}
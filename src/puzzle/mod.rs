@@ -1,5 +1,13 @@
+pub mod campaign;
+pub mod deck;
 pub mod loader;
+pub mod quiz;
 pub mod types;
 
-pub use loader::load_floor;
-pub use types::{CodexEntry, Room};
+pub use campaign::{Campaign, Condition, Economy, Standing, WinCondition};
+pub use deck::{Deck, EventCard, EventKind};
+pub use loader::{find_floors, load_floor, load_manifest, load_puzzle};
+pub use quiz::{Question, Quiz};
+pub use types::{
+    Altar, Carryover, CodexEntry, Consumable, Door, FloorManifest, Medal, OutOfAttempts, Room, Ware,
+};
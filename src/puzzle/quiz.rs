@@ -0,0 +1,45 @@
+//! The placement quiz: predict-the-output questions that let a player who
+//! already knows a floor's material start below it. The questions live in
+//! `placement.toml` at the root of a puzzle pack.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct Quiz {
+    #[serde(default, rename = "question")]
+    pub questions: Vec<Question>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Question {
+    /// Answering every question for a floor (and the floors above it)
+    /// correctly lets the player start there
+    pub floor: usize,
+    pub code: String,
+    /// Possible outputs, shown in this order
+    pub choices: Vec<String>,
+    /// The choice the code really prints
+    pub answer: String,
+}
+
+impl Quiz {
+    /// Reads `path`; a pack without a quiz gets an empty one.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Quiz::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        let mut quiz: Quiz = toml::from_str(&content).inspect_err(
+            |e| tracing::warn!(path = %path.display(), error = %e, "invalid placement quiz"),
+        )?;
+        // Easier floors come first, so a wrong answer can end the quiz early
+        quiz.questions.sort_by_key(|q| q.floor);
+        Ok(quiz)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.questions.is_empty()
+    }
+}
@@ -1,4 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::compiler::{Check, Fixture, OutputMatch, RunSpec, SandboxPolicy, TestCase};
+use crate::inventory::ItemKind;
+use crate::modifiers::Adjustment;
+use crate::theme::Ambience;
 
 #[derive(Debug, Deserialize)]
 pub struct Room {
@@ -11,20 +16,36 @@ pub struct Room {
     pub rewards: Option<Rewards>,
     #[serde(default)]
     pub codex: Option<CodexEntry>,
+    /// How many times the puzzle has been swapped for the next variant, wrapping
+    /// back to 0 once the room's own code is in again
+    #[serde(skip)]
+    pub incarnation: usize,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct CodexEntry {
     pub title: String,
     pub description: String,
+    #[serde(default)]
+    pub example: Option<String>, // Minimal working program, shown highlighted
 }
 
 #[derive(Debug, Deserialize)]
 pub struct RoomMeta {
     pub id: String,
+    #[allow(dead_code)]
     pub room_number: u32,
     pub title: String,
     pub concept: String,
+    /// Locks the way into this room
+    #[serde(default)]
+    pub door: Option<Door>,
+    /// Whether `:xyzzy` finds something here
+    #[serde(default)]
+    pub secret: bool,
+    /// Tints the room screen's borders; plain if unset
+    #[serde(default)]
+    pub ambience: Option<Ambience>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,6 +56,9 @@ pub struct Narrative {
     pub success: String,
     pub failure_compile: String,
     pub failure_output: String,
+    /// Shown when the code has to be stopped for running too long
+    #[serde(default)]
+    pub failure_timeout: Option<String>,
     pub hints: Vec<String>,
     #[serde(default)]
     pub alternative_solution: Option<String>,
@@ -46,19 +70,352 @@ pub struct Rewards {
     pub grants_item: Option<String>,
     #[serde(default)]
     pub item_description: Option<String>,
+    #[serde(default)]
+    pub item_kind: ItemKind,
+    /// Slots a pack adds
+    #[serde(default)]
+    pub item_slots: usize,
+    /// What carrying the item changes for the rest of the run
+    #[serde(default)]
+    pub modifiers: Vec<Adjustment>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Challenge {
     pub code: String,
     pub expected_output: String,
+    /// How the output is compared with `expected_output`
+    #[serde(default)]
+    pub output_match: OutputMatch,
+    /// Fed to the program when it's run for `expected_output`
+    #[serde(default)]
+    pub stdin: Option<String>,
+    #[serde(default)]
+    pub locked_lines: Vec<usize>,
+    /// Why the locked lines are as they are, for an intent scroll to reveal
+    #[serde(default)]
+    pub locked_intent: Option<String>,
+    #[serde(default)]
+    pub solution: Option<String>, // Reference solution, checked by `rust-raid test`
+    /// Further runs with their own input and output, never shown to the player
+    #[serde(default)]
+    pub tests: Vec<TestCase>,
+    /// Written into the program's directory before it runs
+    #[serde(default)]
+    pub files: Vec<Fixture>,
+    #[serde(default)]
+    pub check: Check,
+    /// Also fail a working solution that Clippy warns about
+    #[serde(default)]
+    pub require_clippy_clean: bool,
+    /// What the solution may do while it runs
+    #[serde(default)]
+    pub sandbox: SandboxPolicy,
+    /// Nudges shown when the output first goes wrong on a given line
+    #[serde(default)]
+    pub output_hints: Vec<OutputHint>,
+    /// Other incarnations of the same puzzle, offered to a player stuck on this one
+    #[serde(default)]
+    pub variants: Vec<Variant>,
+}
+
+/// Different code teaching the same concept, swapped in with `:summon`.
+#[derive(Debug, Deserialize)]
+pub struct Variant {
+    pub code: String,
+    pub expected_output: String,
+    #[serde(default)]
+    pub output_match: OutputMatch,
+    #[serde(default)]
+    pub stdin: Option<String>,
     #[serde(default)]
     pub locked_lines: Vec<usize>,
+    #[serde(default)]
+    pub locked_intent: Option<String>,
+    #[serde(default)]
+    pub solution: Option<String>,
+    #[serde(default)]
+    pub tests: Vec<TestCase>,
+    /// The room's hints are used when this is empty
+    #[serde(default)]
+    pub hints: Vec<String>,
+    #[serde(default)]
+    pub output_hints: Vec<OutputHint>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutputHint {
+    /// Line of `expected_output`, counting from 1
+    pub line: usize,
+    pub hint: String,
+}
+
+impl Room {
+    /// Swaps the puzzle for the next of its variants; the one it replaces
+    /// goes to the back of the pool. Returns false if there are none.
+    pub fn summon_variant(&mut self) -> bool {
+        if self.challenge.variants.is_empty() {
+            return false;
+        }
+        let next = self.challenge.variants.remove(0);
+        let challenge = &mut self.challenge;
+        let previous = Variant {
+            code: std::mem::replace(&mut challenge.code, next.code),
+            expected_output: std::mem::replace(
+                &mut challenge.expected_output,
+                next.expected_output,
+            ),
+            output_match: std::mem::replace(&mut challenge.output_match, next.output_match),
+            stdin: std::mem::replace(&mut challenge.stdin, next.stdin),
+            locked_lines: std::mem::replace(&mut challenge.locked_lines, next.locked_lines),
+            locked_intent: std::mem::replace(&mut challenge.locked_intent, next.locked_intent),
+            solution: std::mem::replace(&mut challenge.solution, next.solution),
+            tests: std::mem::replace(&mut challenge.tests, next.tests),
+            hints: std::mem::replace(&mut self.narrative.hints, next.hints),
+            output_hints: std::mem::replace(&mut challenge.output_hints, next.output_hints),
+        };
+        challenge.variants.push(previous);
+        self.incarnation = (self.incarnation + 1) % (challenge.variants.len() + 1);
+        true
+    }
+}
+
+impl Challenge {
+    /// What the solution is given and expected to print when it's checked.
+    pub fn run_spec(&self) -> RunSpec {
+        RunSpec {
+            expected_output: self.expected_output.clone(),
+            output_match: self.output_match,
+            stdin: self.stdin.clone(),
+            tests: self.tests.clone(),
+            files: self.files.clone(),
+            only_test: None,
+        }
+    }
+
+    /// Locked lines (1-based, as in the puzzle file) whose original text no
+    /// longer appears in `solution`. The editor enforces these interactively;
+    /// headless checks have to compare text instead.
+    pub fn missing_locked_lines(&self, solution: &str) -> Vec<usize> {
+        let original: Vec<&str> = self.code.trim().lines().collect();
+        let submitted: Vec<&str> = solution.lines().map(str::trim_end).collect();
+        self.locked_lines
+            .iter()
+            .copied()
+            .filter(|&n| {
+                original
+                    .get(n.wrapping_sub(1))
+                    .is_some_and(|line| !submitted.contains(&line.trim_end()))
+            })
+            .collect()
+    }
+}
+
+/// Optional `floor.toml` next to a floor's room files, for what belongs to
+/// the floor rather than any one room.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct FloorManifest {
+    /// What the floor is called on the completion screens; made from the
+    /// directory name if unset, `floor_02_borrowing` becoming "Borrowing"
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Where the floor comes in the pack; floors without one follow, by
+    /// directory name
+    #[serde(default)]
+    pub order: Option<usize>,
+    /// What the floor is about in a few words, shown when descending to it
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Shown when the floor is entered, before its first room
+    #[serde(default)]
+    pub intro: Option<String>,
+    /// What the floor teaches, previewed on the recap of the floor before
+    #[serde(default)]
+    pub concepts: Vec<String>,
+    #[serde(default)]
+    pub altar: Option<Altar>,
+    /// The way down to the next floor, if it needs an item
+    #[serde(default)]
+    pub exit: Option<Door>,
+    /// What survives the way down into this floor
+    #[serde(default)]
+    pub carryover: Carryover,
+    /// Wares on sale after the floor's last room, or after `shop_after_room`
+    #[serde(default)]
+    pub shop: Vec<Ware>,
+    /// The room (1-based) after which the shop opens, from the floor's
+    /// `shop.toml`; after the last room if unset
+    #[serde(skip)]
+    pub shop_after_room: Option<usize>,
+}
+
+/// Optional `shop.toml` next to `floor.toml`, for a floor whose shop opens
+/// between two of its rooms.
+#[derive(Debug, Deserialize, Default)]
+pub struct ShopManifest {
+    /// The room (1-based) the shop opens after
+    #[serde(default)]
+    pub after_room: Option<usize>,
+    #[serde(default, rename = "ware")]
+    pub wares: Vec<Ware>,
+}
+
+/// Whether gold, items and HP come along into a floor. Whatever doesn't goes
+/// back to how a run starts, for campaigns played as separate descents.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Carryover {
+    pub gold: bool,
+    pub items: bool,
+    pub hp: bool,
+}
+
+impl Default for Carryover {
+    fn default() -> Self {
+        Carryover {
+            gold: true,
+            items: true,
+            hp: true,
+        }
+    }
+}
+
+/// An item a floor's shop sells.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Ware {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub price: u32,
+    #[serde(default)]
+    pub kind: ItemKind,
+    /// Slots a pack adds
+    #[serde(default)]
+    pub slots: usize,
+    /// What carrying the item changes for the rest of the run
+    #[serde(default)]
+    pub modifiers: Vec<Adjustment>,
+    /// Used up rather than carried: it takes no slot and can be bought again
+    #[serde(default)]
+    pub effect: Option<Consumable>,
+}
+
+/// What a ware that isn't carried does once it's bought.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Consumable {
+    /// HP back at once, up to the most a run can have
+    Potion { hp: u32 },
+    /// The next hint costs no HP
+    Hint,
+    /// One read of a room's `locked_intent`, with `:intent`
+    IntentScroll,
+    /// The next compile error costs no HP
+    Shield,
+}
+
+/// A door that only opens for someone carrying `requires_item`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Door {
+    pub requires_item: String,
+    /// Whether the door keeps the item
+    #[serde(default)]
+    pub consumes: bool,
+    /// Shown while the door stays shut
+    #[serde(default)]
+    pub locked_text: Option<String>,
+}
+
+/// An altar between two rooms where gold and HP can be traded for each other.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Altar {
+    /// The room (1-based, as in `room_number`) after which the altar appears
+    pub after_room: usize,
+    /// Gold per HP, whichever way the trade goes
+    pub gold_per_hp: u32,
+    /// HP changing hands in one offering
+    #[serde(default = "default_altar_step")]
+    pub hp_per_offering: u32,
+}
+
+fn default_altar_step() -> u32 {
+    10
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Scoring {
+    /// The gold medal time, unless `gold_time_seconds` says otherwise; silver
+    /// defaults to 1.5x par and bronze to 2x
     pub par_time_seconds: Option<u32>,
     pub hint_penalty_hp: Option<u32>,
     pub wrong_answer_penalty_hp: Option<u32>,
+    pub gold_time_seconds: Option<u32>,
+    pub silver_time_seconds: Option<u32>,
+    pub bronze_time_seconds: Option<u32>,
+    /// Gold for a clear within par, before modifiers; 20 if unset
+    pub par_bonus_gold: Option<u32>,
+    /// Runs allowed before every further failed one triggers `out_of_attempts`
+    pub max_attempts: Option<u32>,
+    #[serde(default)]
+    pub out_of_attempts: OutOfAttempts,
+}
+
+/// What a failed run past a room's `max_attempts` costs on top of the usual
+/// wrong-answer penalty.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutOfAttempts {
+    /// More HP
+    ExtraHp { hp: u32 },
+    /// The next hint, shown and paid for whether it was asked for or not
+    ForcedHint,
+    /// The room strikes back: HP, and the code goes back to how the room started
+    Counterattack {
+        hp: u32,
+        /// Shown instead of the generic counterattack line
+        #[serde(default)]
+        text: Option<String>,
+    },
+}
+
+impl Default for OutOfAttempts {
+    fn default() -> Self {
+        OutOfAttempts::ExtraHp { hp: 5 }
+    }
+}
+
+/// Time attack medals, worst first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Medal {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl Scoring {
+    /// The gold a clear in `secs` seconds earns for beating par; none when
+    /// the room has no par or it was missed.
+    pub fn par_bonus(&self, secs: u64) -> Option<u32> {
+        self.par_time_seconds
+            .filter(|&par| secs <= u64::from(par))
+            .map(|_| self.par_bonus_gold.unwrap_or(20))
+    }
+
+    /// The best medal a clear in `secs` seconds earns, if any. Rooms without
+    /// any times set award none.
+    pub fn medal(&self, secs: u64) -> Option<Medal> {
+        let par = self.par_time_seconds;
+        let gold = self.gold_time_seconds.or(par);
+        let silver = self.silver_time_seconds.or(par.map(|p| p * 3 / 2));
+        let bronze = self.bronze_time_seconds.or(par.map(|p| p * 2));
+        [
+            (gold, Medal::Gold),
+            (silver, Medal::Silver),
+            (bronze, Medal::Bronze),
+        ]
+        .into_iter()
+        .find(|(limit, _)| limit.is_some_and(|limit| secs <= u64::from(limit)))
+        .map(|(_, medal)| medal)
+    }
 }
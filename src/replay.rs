@@ -0,0 +1,275 @@
+//! Session recording (`--record`) and playback (`--replay`).
+//!
+//! A replay is newline-delimited JSON: a header with everything needed to
+//! start the same run, then every key press and state change with its time
+//! since launch. Playback feeds the keys back through the normal input
+//! handling, so it also exercises the compiler again rather than showing
+//! recorded results.
+
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Entry {
+    Header {
+        game_version: String,
+        seed: u64,
+        /// Floor and 0-based room when the run skipped the title screen
+        start: Option<(usize, usize)>,
+        seen_onboarding: bool,
+    },
+    /// A key press, e.g. `"k"`, `"C-s"`, `"Enter"`, `"F5"`
+    Key {
+        ms: u64,
+        key: String,
+    },
+    State {
+        ms: u64,
+        state: String,
+    },
+}
+
+pub struct Recorder {
+    file: BufWriter<std::fs::File>,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, header: &Entry) -> Result<Self> {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("Could not create {}", path.display()))?;
+        let mut recorder = Recorder {
+            file: BufWriter::new(file),
+            started: Instant::now(),
+        };
+        recorder.write(header);
+        Ok(recorder)
+    }
+
+    pub fn key(&mut self, key: &KeyEvent) {
+        if let Some(key) = encode_key(key) {
+            let ms = self.ms();
+            self.write(&Entry::Key { ms, key });
+        }
+    }
+
+    pub fn state(&mut self, state: impl std::fmt::Debug) {
+        let ms = self.ms();
+        self.write(&Entry::State {
+            ms,
+            state: format!("{:?}", state),
+        });
+    }
+
+    fn ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    // A recording that stops half-way is still useful, so errors only get logged
+    fn write(&mut self, entry: &Entry) {
+        let result = serde_json::to_string(entry)
+            .map_err(anyhow::Error::from)
+            .and_then(|line| Ok(writeln!(self.file, "{}", line)?));
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "could not write replay entry");
+        }
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.file.flush();
+    }
+}
+
+/// Steps through a recording on a virtual clock that can be sped up or paused.
+pub struct Player {
+    pub header: Entry,
+    entries: Vec<Entry>,
+    next: usize,
+    position_ms: f64,
+    last_update: Instant,
+    pub speed: f64,
+    pub paused: bool,
+    expected_state: Option<String>,
+}
+
+impl Player {
+    pub fn load(path: &Path, speed: f64) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Could not open {}", path.display()))?;
+        let mut entries = Vec::new();
+        for (number, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: Entry = serde_json::from_str(&line).with_context(|| {
+                format!("{}:{}: not a replay entry", path.display(), number + 1)
+            })?;
+            entries.push(entry);
+        }
+        if !matches!(entries.first(), Some(Entry::Header { .. })) {
+            bail!("{} does not start with a replay header", path.display());
+        }
+        let header = entries.remove(0);
+        Ok(Player {
+            header,
+            entries,
+            next: 0,
+            position_ms: 0.0,
+            last_update: Instant::now(),
+            speed,
+            paused: false,
+            expected_state: None,
+        })
+    }
+
+    /// Handles a key the viewer pressed: space pauses, `+`/`-` change speed.
+    /// Returns true for `q`/Esc, which end the playback.
+    pub fn control(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('+') | KeyCode::Char('=') => self.speed = (self.speed * 2.0).min(16.0),
+            KeyCode::Char('-') => self.speed = (self.speed / 2.0).max(0.25),
+            _ => {}
+        }
+        false
+    }
+
+    /// The next recorded key whose time has come, if any.
+    pub fn next_due(&mut self) -> Option<KeyEvent> {
+        let now = Instant::now();
+        if !self.paused {
+            self.position_ms +=
+                now.duration_since(self.last_update).as_secs_f64() * 1000.0 * self.speed;
+        }
+        self.last_update = now;
+
+        while let Some(entry) = self.entries.get(self.next) {
+            match entry {
+                Entry::State { ms, state } if (*ms as f64) <= self.position_ms => {
+                    self.expected_state = Some(state.clone());
+                }
+                Entry::Key { ms, key } if (*ms as f64) <= self.position_ms => {
+                    self.next += 1;
+                    match decode_key(key) {
+                        Some(event) => return Some(event),
+                        None => continue,
+                    }
+                }
+                Entry::Header { .. } => {}
+                _ => return None,
+            }
+            self.next += 1;
+        }
+        None
+    }
+
+    /// Whether the game reached the same screen as the recording did at this point.
+    pub fn in_sync(&self, state: impl std::fmt::Debug) -> bool {
+        self.expected_state
+            .as_ref()
+            .is_none_or(|expected| *expected == format!("{:?}", state))
+    }
+
+    pub fn finished(&self) -> bool {
+        self.next >= self.entries.len()
+    }
+
+    /// Short status shown in the corner while playing back.
+    pub fn label(&self, in_sync: bool) -> String {
+        let status = if self.finished() {
+            "done, q to quit".to_string()
+        } else if self.paused {
+            "paused".to_string()
+        } else {
+            format!("{}x", self.speed)
+        };
+        let sync = if in_sync { "" } else { " · out of sync" };
+        format!(" REPLAY {}{} ", status, sync)
+    }
+}
+
+/// Compact, readable key names: `a`, `C-s`, `A-x`, `S-A`, `Enter`, `F5`.
+pub fn encode_key(key: &KeyEvent) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Enter => "Enter".into(),
+        KeyCode::Esc => "Esc".into(),
+        KeyCode::Backspace => "Backspace".into(),
+        KeyCode::Tab => "Tab".into(),
+        KeyCode::BackTab => "BackTab".into(),
+        KeyCode::Delete => "Delete".into(),
+        KeyCode::Insert => "Insert".into(),
+        KeyCode::Home => "Home".into(),
+        KeyCode::End => "End".into(),
+        KeyCode::PageUp => "PageUp".into(),
+        KeyCode::PageDown => "PageDown".into(),
+        KeyCode::Up => "Up".into(),
+        KeyCode::Down => "Down".into(),
+        KeyCode::Left => "Left".into(),
+        KeyCode::Right => "Right".into(),
+        _ => return None,
+    };
+    let mut prefix = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        prefix.push_str("C-");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        prefix.push_str("A-");
+    }
+    if key.modifiers.contains(KeyModifiers::SHIFT) {
+        prefix.push_str("S-");
+    }
+    Some(prefix + &name)
+}
+
+pub fn decode_key(text: &str) -> Option<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = text;
+    // Checking the length keeps a literal `C` or `-` key from being read as a prefix
+    while rest.len() > 2 {
+        let modifier = match rest.get(..2) {
+            Some("C-") => KeyModifiers::CONTROL,
+            Some("A-") => KeyModifiers::ALT,
+            Some("S-") => KeyModifiers::SHIFT,
+            _ => break,
+        };
+        modifiers |= modifier;
+        rest = &rest[2..];
+    }
+    let code = match rest {
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Backspace" => KeyCode::Backspace,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "Delete" => KeyCode::Delete,
+        "Insert" => KeyCode::Insert,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        _ => {
+            let mut chars = rest.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                (Some('F'), Some(_)) => KeyCode::F(rest[1..].parse().ok()?),
+                _ => return None,
+            }
+        }
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
@@ -0,0 +1,90 @@
+//! The run in progress, kept in `save.toml` so that Continue on the title
+//! screen can pick it up after a quit or a crash. Items, codex entries and
+//! event curses are stored by name and looked up in the puzzle pack again on
+//! load, so the file holds the player's progress and not the pack's text.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::card::RoomMark;
+use crate::inventory::Supplies;
+use crate::modifiers::Difficulty;
+use crate::paths;
+use crate::puzzle::Medal;
+use crate::store::{self, Versioned};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveGame {
+    pub level: usize,
+    /// 0-based
+    pub room: usize,
+    /// Whether that room was already cleared; the run carries on from its
+    /// victory message instead of asking for the solution again
+    pub cleared: bool,
+    pub hp: u32,
+    pub gold: u32,
+    pub difficulty: Difficulty,
+    pub items: Vec<String>,
+    /// Shop supplies not yet used
+    pub supplies: Supplies,
+    /// A room's item waiting on the full-pack dialog
+    pub pending_item: Option<String>,
+    /// Event cards whose modifiers are still in force
+    pub events: Vec<String>,
+    /// Titles of the unlocked codex entries
+    pub codex: Vec<String>,
+    /// Ids of the rooms whose secret was found
+    pub secrets: Vec<String>,
+    /// The editor as it was, for a room not yet cleared
+    pub editor: Vec<String>,
+    pub room_secs: u64,
+    pub run_secs: u64,
+    pub attempts_room: u32,
+    /// Which of the room's variants was in, counting `:summon`s
+    pub incarnation: usize,
+    pub hints_used_room: usize,
+    /// Whether the room's locked-line intent was already paid for
+    pub intent_read: bool,
+    pub hints_used_total: usize,
+    pub compile_errors_total: u32,
+    pub run_hints: usize,
+    pub run_compile_errors: u32,
+    pub medals: Vec<(usize, Medal)>,
+    pub marks: Vec<RoomMark>,
+}
+
+impl Versioned for SaveGame {
+    const VERSION: u32 = 1;
+}
+
+impl SaveGame {
+    /// The saved run, if there is one.
+    pub fn load() -> Option<Self> {
+        let path = save_path()?;
+        if !path.exists() {
+            return None;
+        }
+        Some(store::load::<SaveGame>(&path)).filter(|save| save.level > 0)
+    }
+
+    /// Writes the run, keeping `backups` earlier copies, and returns where.
+    pub fn save(&self, backups: usize) -> Result<PathBuf> {
+        let path = save_path().ok_or_else(|| anyhow::anyhow!("No home directory found"))?;
+        store::save(&path, self, backups)?;
+        Ok(path)
+    }
+
+    /// Forgets the saved run, once it has ended.
+    pub fn delete() -> Result<()> {
+        match save_path() {
+            Some(path) if path.exists() => Ok(std::fs::remove_file(path)?),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn save_path() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("save.toml"))
+}
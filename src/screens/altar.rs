@@ -0,0 +1,74 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{playing, Screen};
+use crate::app::{AltarOption, App};
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::{centered_rect, menu_line};
+
+/// The trades offered by a floor's altar, over the room just cleared.
+pub(crate) struct AltarScreen;
+
+impl Screen for AltarScreen {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.altar_menu.previous(),
+            KeyCode::Down | KeyCode::Char('j') => app.altar_menu.next(),
+            KeyCode::Enter => {
+                if let Some(option) = app.altar_menu.selected() {
+                    app.use_altar(option);
+                }
+            }
+            KeyCode::Esc => app.use_altar(AltarOption::Leave),
+            KeyCode::PageDown => playing::scroll_message(app, 3),
+            KeyCode::PageUp => playing::scroll_message(app, -3),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        let Some(altar) = app.altar_here() else {
+            return;
+        };
+        let mut lines = vec![Line::from("")];
+        lines.extend(
+            t!("altar-inscription", rate = altar.gold_per_hp)
+                .lines()
+                .map(|l| Line::from(l.to_string())),
+        );
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            t!("altar-purse", hp = app.hp, gold = app.gold),
+            Style::default().fg(Color::Yellow),
+        ));
+        lines.push(Line::from(""));
+        for (item, selected) in app.altar_menu.entries() {
+            let hp = match item.action {
+                AltarOption::OfferGold => altar
+                    .hp_per_offering
+                    .min(app.max_hp().saturating_sub(app.hp)),
+                _ => altar.hp_per_offering,
+            };
+            let label = t!(item.label, hp = hp, gold = hp * altar.gold_per_hp);
+            lines.push(menu_line(&label, selected, item.enabled));
+        }
+
+        let area = centered_rect(60, lines.len() as u16 + 4, f.area());
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(t!("altar-title")),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(dialog, area);
+    }
+}
@@ -0,0 +1,153 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::Screen;
+use crate::app::{App, GameState};
+use crate::archive::{self, Archive};
+use crate::engine::Flow;
+use crate::highlight;
+use crate::i18n::t;
+use crate::ui::format_duration;
+
+/// Past solutions: a list, and one of them opened read-only.
+pub(crate) struct ArchiveScreen;
+
+impl Screen for ArchiveScreen {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        if key.code == KeyCode::Char('s') {
+            app.archive_to_scratch();
+            return Flow::Continue;
+        }
+        let Some(archive) = app.archive.as_mut() else {
+            app.state = GameState::Playing;
+            return Flow::Continue;
+        };
+        match (archive.reading, key.code) {
+            (Some(_), KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) => archive.reading = None,
+            (Some(scroll), KeyCode::Down | KeyCode::Char('j')) => {
+                archive.reading = Some(scroll.saturating_add(1))
+            }
+            (Some(scroll), KeyCode::Up | KeyCode::Char('k')) => {
+                archive.reading = Some(scroll.saturating_sub(1))
+            }
+            (Some(scroll), KeyCode::PageDown) => archive.reading = Some(scroll.saturating_add(10)),
+            (Some(scroll), KeyCode::PageUp) => archive.reading = Some(scroll.saturating_sub(10)),
+            (None, KeyCode::Esc | KeyCode::Char('q')) => app.state = GameState::Playing,
+            (None, KeyCode::Enter) => archive.reading = Some(0),
+            (None, KeyCode::Down | KeyCode::Char('j')) => archive.move_cursor(1),
+            (None, KeyCode::Up | KeyCode::Char('k')) => archive.move_cursor(-1),
+            (None, KeyCode::PageDown) => archive.move_cursor(10),
+            (None, KeyCode::PageUp) => archive.move_cursor(-10),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        let Some(archive) = &app.archive else {
+            return;
+        };
+        match (archive.reading, archive.selected()) {
+            (Some(scroll), Some(solution)) => draw_solution(f, solution, scroll),
+            _ => draw_list(f, archive),
+        }
+    }
+}
+
+fn draw_list(f: &mut Frame, archive: &Archive) {
+    let area = f.area();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("  {}", t!("archive-help")),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+    ];
+    let first = lines.len();
+    for (i, solution) in archive.newest_first().enumerate() {
+        let selected = i == archive.cursor;
+        let title_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {}  ", archive::date(solution.solved_at)),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                solution.title.clone(),
+                if selected {
+                    title_style.add_modifier(Modifier::REVERSED)
+                } else {
+                    title_style
+                },
+            ),
+            Span::styled(
+                format!("  {}", stats(solution)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = (first + archive.cursor).saturating_sub(visible / 2) as u16;
+    let list = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(t!("archive-title", count = archive.solutions.len())),
+        )
+        .scroll((scroll, 0));
+    f.render_widget(list, area);
+}
+
+fn draw_solution(f: &mut Frame, solution: &archive::Solution, scroll: u16) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "  {}  {}",
+                archive::date(solution.solved_at),
+                stats(solution)
+            ),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(Span::styled(
+            format!("  {}", t!("archive-reading-help")),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+    ];
+    for (n, code_line) in highlight::highlight(&solution.code).into_iter().enumerate() {
+        let mut spans = vec![Span::styled(
+            format!("{:>4} │ ", n + 1),
+            Style::default().fg(Color::DarkGray),
+        )];
+        spans.extend(code_line.spans);
+        lines.push(Line::from(spans));
+    }
+    let code = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(format!(" {} ", solution.title)),
+        )
+        .scroll((scroll, 0));
+    f.render_widget(code, f.area());
+}
+
+fn stats(solution: &archive::Solution) -> String {
+    t!(
+        "archive-stats",
+        floor = solution.level,
+        time = format_duration(std::time::Duration::from_secs(solution.secs)),
+        attempts = solution.attempts,
+        hints = solution.hints
+    )
+}
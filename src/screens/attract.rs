@@ -0,0 +1,167 @@
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{title, Screen};
+use crate::app::{App, GameState};
+use crate::attract::Phase;
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::centered_rect;
+use crate::{anim, highlight, markdown};
+
+/// The idle demo: any key goes back to the menu without acting on it.
+pub(crate) struct Attract;
+
+impl Screen for Attract {
+    fn handle_key(&self, app: &mut App, _key: KeyEvent) -> Flow {
+        app.attract = None;
+        app.state = GameState::TitleScreen;
+        Flow::Continue
+    }
+
+    fn update(&self, app: &mut App) {
+        app.needs_redraw = true;
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        let Some(demo) = &app.attract else {
+            return;
+        };
+        let frame = (demo.elapsed().as_millis() / anim::FRAME.as_millis()) as u64;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(f.area());
+
+        match demo.phase() {
+            Phase::Logo => draw_logo(f, app, frame, chunks[0]),
+            Phase::Fact(entry) => {
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        entry.title.clone(),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(""),
+                ];
+                lines.extend(markdown::render(&entry.description));
+                if let Some(example) = &entry.example {
+                    lines.push(Line::from(""));
+                    lines.extend(highlight::highlight(example));
+                }
+                let area = centered_rect(70, lines.len() as u16 + 4, chunks[0]);
+                let fact = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow))
+                        .title(t!("attract-fact-title")),
+                );
+                f.render_widget(fact, area);
+            }
+            Phase::Replay {
+                title,
+                lines,
+                cursor,
+                cleared,
+            } => draw_replay(f, title, &lines, cursor, cleared, frame, chunks[0]),
+        }
+
+        // Blinks slowly so it reads as a prompt rather than part of the demo
+        let prompt_style = if (frame / 20).is_multiple_of(2) {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        f.render_widget(
+            Paragraph::new(t!("attract-prompt"))
+                .style(prompt_style)
+                .alignment(Alignment::Center),
+            chunks[1],
+        );
+    }
+}
+
+/// The title logo with a band of color sweeping down through it.
+fn draw_logo(f: &mut Frame, app: &App, frame: u64, area: Rect) {
+    const WAVE: [Color; 6] = [
+        Color::Red,
+        Color::LightRed,
+        Color::Yellow,
+        Color::LightYellow,
+        Color::Yellow,
+        Color::LightRed,
+    ];
+    let lines: Vec<Line> = title::title_art(app)
+        .lines()
+        .enumerate()
+        .map(|(row, line)| {
+            let color = WAVE[(row + (frame / 2) as usize) % WAVE.len()];
+            Line::from(Span::styled(line.to_string(), Style::default().fg(color)))
+        })
+        .collect();
+    let logo_area = centered_rect(area.width, lines.len() as u16, area);
+    f.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        logo_area,
+    );
+}
+
+fn draw_replay(
+    f: &mut Frame,
+    title: &str,
+    lines: &[String],
+    cursor: Option<usize>,
+    cleared: bool,
+    frame: u64,
+    area: Rect,
+) {
+    let mut code: Vec<Line> = highlight::highlight(&lines.join("\n"))
+        .into_iter()
+        .enumerate()
+        .map(|(row, mut line)| {
+            line.spans.insert(
+                0,
+                Span::styled(
+                    format!("{:>3} ", row + 1),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            );
+            if cursor == Some(row) && (frame / 8).is_multiple_of(2) {
+                line.spans
+                    .push(Span::styled("█", Style::default().fg(Color::White)));
+            }
+            line
+        })
+        .collect();
+    code.push(Line::from(""));
+    code.push(if cleared {
+        Line::from(Span::styled(
+            format!(" F5 ▸ {} ", t!("celebration-room")),
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ))
+    } else {
+        Line::from(Span::styled(
+            t!("attract-replay-hint"),
+            Style::default().fg(Color::DarkGray),
+        ))
+    });
+
+    let area = centered_rect(80, code.len() as u16 + 2, area);
+    let editor = Paragraph::new(code).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if cleared { Color::Green } else { Color::Cyan }))
+            .title(t!("attract-replay-title", room = title)),
+    );
+    f.render_widget(editor, area);
+}
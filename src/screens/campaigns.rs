@@ -0,0 +1,116 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::Screen;
+use crate::app::App;
+use crate::campaigns::Summary;
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::format_duration;
+
+/// Every installed pack's progress side by side, from `:campaigns`.
+pub(crate) struct Campaigns;
+
+impl Screen for Campaigns {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+            app.state = app.campaigns_return;
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        let dim = Style::default().fg(Color::DarkGray);
+        let rows: Vec<[String; 5]> = app.campaigns.iter().map(cells).collect();
+        let name_width = rows
+            .iter()
+            .map(|cells| cells[0].chars().count())
+            .chain([t!("campaigns-column-name").chars().count()])
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let mut lines = vec![
+            Line::from(Span::styled(format!("  {}", t!("campaigns-help")), dim)),
+            Line::from(""),
+            Line::from(Span::styled(
+                row(
+                    name_width,
+                    &[
+                        t!("campaigns-column-name"),
+                        t!("campaigns-column-cleared"),
+                        t!("campaigns-column-codex"),
+                        t!("campaigns-column-best-run"),
+                        t!("campaigns-column-sum-of-best"),
+                    ],
+                ),
+                dim.add_modifier(Modifier::BOLD),
+            )),
+        ];
+        for (summary, cells) in app.campaigns.iter().zip(&rows) {
+            let style = if summary.current {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Cyan)
+            };
+            lines.push(Line::from(Span::styled(row(name_width, cells), style)));
+        }
+        if app.campaigns.len() < 2 {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  {}", t!("campaigns-only-one")),
+                dim,
+            )));
+        }
+
+        let dashboard = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(t!("campaigns-title", count = app.campaigns.len())),
+        );
+        f.render_widget(dashboard, f.area());
+    }
+}
+
+fn cells(summary: &Summary) -> [String; 5] {
+    let time = |secs: Option<u64>| {
+        secs.map(|secs| format_duration(std::time::Duration::from_secs(secs)))
+            .unwrap_or_else(|| "—".to_string())
+    };
+    let name = if summary.current {
+        t!("campaigns-current", name = summary.name.as_str())
+    } else {
+        summary.name.clone()
+    };
+    [
+        name,
+        format!(
+            "{}/{} ({}%)",
+            summary.cleared,
+            summary.rooms,
+            summary.percent()
+        ),
+        format!("{}/{}", summary.codex_found, summary.codex_total),
+        time(summary.best_run),
+        time(summary.sum_of_best),
+    ]
+}
+
+/// The name padded to `name_width`, then the rest in fixed columns.
+fn row(name_width: usize, cells: &[String]) -> String {
+    let [name, rest @ ..] = cells else {
+        return String::new();
+    };
+    let mut line = format!("  {:<width$}", name, width = name_width);
+    for cell in rest {
+        line.push_str(&format!("{:<14}", cell));
+    }
+    line.trim_end().to_string()
+}
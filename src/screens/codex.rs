@@ -0,0 +1,227 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::Screen;
+use crate::app::{App, GameState};
+use crate::codex::CodexRow;
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::{highlight, markdown};
+
+pub(crate) struct Codex;
+
+impl Screen for Codex {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        if app.codex.filtering {
+            match key.code {
+                KeyCode::Esc => {
+                    app.codex.filter.clear();
+                    app.codex.filtering = false;
+                }
+                KeyCode::Enter => app.codex.filtering = false,
+                KeyCode::Backspace => app.codex.pop_filter(),
+                KeyCode::Up => app.codex.move_cursor(-1),
+                KeyCode::Down => app.codex.move_cursor(1),
+                KeyCode::Char(c) => app.codex.push_filter(c),
+                _ => {}
+            }
+            return Flow::Continue;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                app.state = GameState::Playing;
+            }
+            KeyCode::Char('/') => app.codex.filtering = true,
+            KeyCode::Up | KeyCode::Char('k') => app.codex.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.codex.move_cursor(1),
+            KeyCode::PageUp => app.codex.move_cursor(-5),
+            KeyCode::PageDown => app.codex.move_cursor(5),
+            KeyCode::Enter | KeyCode::Char(' ') => app.codex.toggle_group(),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        draw_codex(f, app);
+    }
+}
+
+fn draw_codex(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    // Build codex content
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![Span::styled(
+            "══════════════════════════════════════════════════",
+            Style::default().fg(Color::Yellow),
+        )]),
+        Line::from(vec![Span::styled(
+            format!("              {}", t!("codex-heading")),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![Span::styled(
+            "══════════════════════════════════════════════════",
+            Style::default().fg(Color::Yellow),
+        )]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("  {}", t!("codex-tagline")),
+            Style::default().fg(Color::DarkGray),
+        )]),
+        Line::from(vec![Span::styled(
+            format!("  {}", t!("codex-help")),
+            Style::default().fg(Color::DarkGray),
+        )]),
+    ];
+
+    if app.codex.filtering || !app.codex.filter.is_empty() {
+        let cursor = if app.codex.filtering { "█" } else { "" };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", t!("codex-search")),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!("{}{}", app.codex.filter, cursor),
+                Style::default().fg(Color::White),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Track where the highlighted row lands so it can be scrolled into view
+    let mut cursor_line = 0;
+    let rows = app.codex.rows();
+    if rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", t!("codex-no-match")),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for (i, row) in rows.iter().enumerate() {
+        let selected = i == app.codex.cursor;
+        if selected {
+            cursor_line = lines.len();
+        }
+        let highlight = |style: Style| {
+            if selected {
+                style.add_modifier(Modifier::REVERSED)
+            } else {
+                style
+            }
+        };
+        match row {
+            CodexRow::Header {
+                level,
+                count,
+                collapsed,
+            } => {
+                lines.push(Line::from(""));
+                if selected {
+                    cursor_line = lines.len();
+                }
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        if *collapsed { "▸ " } else { "▾ " },
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled(
+                        t!(
+                            "codex-floor",
+                            floor = *level,
+                            name = app.puzzles.floor_name(*level),
+                            count = *count
+                        ),
+                        highlight(
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    ),
+                ]));
+            }
+            CodexRow::Entry(page) => {
+                lines.push(Line::from(vec![
+                    Span::styled("  ◆ ", Style::default().fg(Color::Green)),
+                    Span::styled(
+                        page.entry.title.clone(),
+                        highlight(
+                            Style::default()
+                                .fg(Color::Cyan)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    ),
+                    Span::styled(
+                        format!("  [{}]", page.concept),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]));
+
+                for desc_line in markdown::render(&page.entry.description) {
+                    let mut spans = vec![Span::raw("      ")];
+                    spans.extend(desc_line.spans);
+                    lines.push(Line::from(spans).style(Style::default().fg(Color::White)));
+                }
+
+                if let Some(example) = &page.entry.example {
+                    lines.push(Line::from(Span::styled(
+                        format!("      {}", t!("codex-example")),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                    for code_line in highlight::highlight(example.trim_matches('\n')) {
+                        let mut spans = vec![Span::styled(
+                            "      │ ",
+                            Style::default().fg(Color::DarkGray),
+                        )];
+                        spans.extend(code_line.spans);
+                        lines.push(Line::from(spans).style(Style::default().fg(Color::White)));
+                    }
+                }
+                lines.push(Line::from(""));
+            }
+        }
+    }
+
+    // Show locked entries hint
+    let total_possible = 9; // 3 rooms × 3 levels
+    let unlocked = app.codex.len();
+    if unlocked < total_possible {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  ○ {}",
+                t!("codex-undiscovered", count = total_possible - unlocked)
+            ),
+            Style::default().fg(Color::DarkGray),
+        )]));
+    }
+
+    // Long entries wrap, so the cursor sits as far down as the rows the
+    // lines above it take up
+    let width = area.width.saturating_sub(2).max(1) as usize;
+    let cursor_row: usize = lines[..cursor_line]
+        .iter()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum();
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = cursor_row.saturating_sub(visible / 2) as u16;
+    let codex = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(t!("codex-title")),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+
+    f.render_widget(codex, area);
+}
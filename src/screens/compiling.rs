@@ -0,0 +1,62 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::{playing, Screen};
+use crate::app::App;
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::{centered_rect, format_duration};
+
+/// A spinner over the room while rustc and the solution run on a worker
+/// thread. Esc calls the run off.
+pub(crate) struct Compiling;
+
+impl Screen for Compiling {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        if key.code == KeyCode::Esc {
+            app.cancel_run();
+        }
+        Flow::Continue
+    }
+
+    fn update(&self, app: &mut App) {
+        if app.poll_run() {
+            playing::check_fallen(app);
+        }
+        // The spinner turns
+        app.needs_redraw = true;
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        let spinner = app.glyphs().spinner;
+        // Ambient frames are 33ms; a spinner step every three reads as steady
+        let frame = spinner[(app.ambient_frame() / 3) as usize % spinner.len()];
+        let elapsed = app
+            .pending_run
+            .as_ref()
+            .map(|run| format_duration(run.started.elapsed()))
+            .unwrap_or_default();
+        let lines = vec![
+            Line::styled(
+                t!("compiling", spinner = frame, time = elapsed.as_str()),
+                Style::default().fg(Color::Cyan),
+            ),
+            Line::styled(t!("compiling-help"), Style::default().fg(Color::DarkGray)),
+        ];
+
+        let area = centered_rect(40, lines.len() as u16 + 2, f.area());
+        let dialog = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(dialog, area);
+    }
+}
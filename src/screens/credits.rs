@@ -0,0 +1,104 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::Screen;
+use crate::app::{App, GameState};
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::centered_rect;
+
+pub(crate) struct Credits;
+
+impl Screen for Credits {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+            app.state = GameState::TitleScreen;
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        draw_credits(f, app);
+    }
+}
+
+fn draw_credits(f: &mut Frame, app: &App) {
+    let heading = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let text = Style::default().fg(Color::White);
+
+    let mut lines = vec![
+        Line::from(Span::styled(t!("credits-heading"), heading)),
+        Line::from(Span::styled(
+            t!("credits-version", version = env!("CARGO_PKG_VERSION")),
+            text,
+        )),
+        Line::from(Span::styled(
+            t!(
+                "credits-build",
+                hash = env!("RUST_RAID_GIT_HASH"),
+                profile = env!("RUST_RAID_BUILD_PROFILE"),
+                target = env!("RUST_RAID_BUILD_TARGET")
+            ),
+            dim,
+        )),
+        Line::from(""),
+        Line::from(Span::styled(t!("credits-created-by"), heading)),
+        Line::from(Span::styled("bradleyd", text)),
+        Line::from(""),
+        Line::from(Span::styled(t!("credits-puzzle-packs"), heading)),
+    ];
+    for pack in &app.pack_credits {
+        lines.push(Line::from(Span::styled(
+            match &pack.author {
+                Some(author) => t!(
+                    "credits-pack-by",
+                    name = pack.name.as_str(),
+                    author = author.as_str()
+                ),
+                None => pack.name.clone(),
+            },
+            text,
+        )));
+        lines.push(Line::from(Span::styled(
+            match &pack.license {
+                Some(license) => t!("credits-pack-license", license = license.as_str()),
+                None => t!("credits-pack-no-license"),
+            },
+            dim,
+        )));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from(Span::styled(t!("credits-built-with"), heading)),
+        Line::from(Span::styled(t!("credits-built-with-ui"), text)),
+        Line::from(Span::styled(t!("credits-built-with-data"), text)),
+        Line::from(""),
+        Line::from(Span::styled(
+            t!("credits-compiler"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::ITALIC),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(t!("credits-back"), dim)),
+    ]);
+
+    let area = centered_rect(70, lines.len() as u16 + 4, f.area());
+    let credits = Paragraph::new(lines)
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(t!("credits-title")),
+        );
+    f.render_widget(credits, area);
+}
@@ -0,0 +1,96 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{playing, Screen};
+use crate::app::App;
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::puzzle::EventKind;
+use crate::ui::centered_rect;
+
+/// A card from the event deck, over the room just cleared.
+pub(crate) struct Event;
+
+impl Screen for Event {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Enter if app.event.as_ref().is_some_and(|card| app.can_accept(card)) => {
+                app.resolve_event(true);
+            }
+            KeyCode::Esc => app.resolve_event(false),
+            KeyCode::PageDown => playing::scroll_message(app, 3),
+            KeyCode::PageUp => playing::scroll_message(app, -3),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        let Some(card) = &app.event else {
+            return;
+        };
+        let (title, color) = match card.kind {
+            EventKind::Blessing => ("event-blessing", Color::Green),
+            EventKind::Curse => ("event-curse", Color::Magenta),
+            EventKind::Trade => ("event-trade", Color::Yellow),
+        };
+        let mut lines = vec![Line::from("")];
+        lines.extend(card.text.trim().lines().map(|l| Line::from(l.to_string())));
+        lines.push(Line::from(""));
+        for (amount, id) in [(card.hp, "event-hp"), (card.gold, "event-gold")] {
+            if amount != 0 {
+                let style = if amount > 0 {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Red)
+                };
+                let amount = format!("{:+}", amount);
+                lines.push(Line::styled(
+                    t!(id, amount = amount.as_str()),
+                    style.add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+        if let Some(item) = &card.takes_item {
+            lines.push(Line::styled(
+                t!("event-takes-item", item = item.as_str()),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        for adjustment in &card.modifiers {
+            let color = if adjustment.helps() {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            lines.push(Line::styled(
+                t!("event-lasting", effect = adjustment.describe()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+        }
+        lines.push(Line::from(""));
+        let prompt = match card.kind {
+            EventKind::Trade if app.can_accept(card) => t!("event-trade-prompt"),
+            EventKind::Trade if card.takes_item.is_some() => t!("event-missing-item"),
+            EventKind::Trade => t!("event-cannot-afford"),
+            EventKind::Blessing | EventKind::Curse => t!("event-continue"),
+        };
+        lines.push(Line::styled(prompt, Style::default().fg(Color::DarkGray)));
+
+        let area = centered_rect(60, lines.len() as u16 + 3, f.area());
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color))
+                .title(t!(title, title = card.title.as_str())),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(dialog, area);
+    }
+}
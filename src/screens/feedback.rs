@@ -0,0 +1,81 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{playing, Screen};
+use crate::app::{App, GameState};
+use crate::engine::Flow;
+use crate::feedback::{Rating, MAX_COMMENT};
+use crate::i18n::t;
+use crate::ui::centered_rect;
+
+/// `:feedback` over the room: a rating picked with the arrows and a comment
+/// typed straight in.
+pub(crate) struct Feedback;
+
+impl Screen for Feedback {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        let draft = &mut app.feedback;
+        match key.code {
+            KeyCode::Esc => app.state = GameState::Playing,
+            KeyCode::Enter => app.send_feedback(),
+            KeyCode::Left | KeyCode::Up => draft.rating = draft.rating.step(-1),
+            KeyCode::Right | KeyCode::Down | KeyCode::Tab => draft.rating = draft.rating.step(1),
+            KeyCode::Backspace => {
+                draft.comment.pop();
+            }
+            KeyCode::Char(c) if draft.comment.chars().count() < MAX_COMMENT => {
+                draft.comment.push(c)
+            }
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut choices = vec![Span::raw(" ")];
+        for rating in Rating::ALL {
+            let style = if rating == app.feedback.rating {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            choices.push(Span::styled(format!(" {} ", rating.label()), style));
+            choices.push(Span::raw("  "));
+        }
+        let lines = vec![
+            Line::from(""),
+            Line::from(format!(" {}", t!("feedback-question"))),
+            Line::from(""),
+            Line::from(choices),
+            Line::from(""),
+            Line::from(Span::styled(format!(" {}", t!("feedback-comment")), dim)),
+            Line::from(vec![
+                Span::raw(format!(" {}", app.feedback.comment)),
+                Span::styled("_", Style::default().fg(Color::Yellow)),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(format!(" {}", t!("feedback-help")), dim)),
+        ];
+        let area = centered_rect(60, lines.len() as u16 + 4, f.area());
+        let form = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title(t!("feedback-title", room = app.room().meta.title.as_str())),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(Clear, area);
+        f.render_widget(form, area);
+    }
+}
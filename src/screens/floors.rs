@@ -0,0 +1,100 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::Screen;
+use crate::app::{App, GameState};
+use crate::engine::Flow;
+use crate::i18n::t;
+
+/// The pack's floors from the title screen, to start a run on any floor
+/// already reached instead of from the top.
+pub(crate) struct SelectFloor;
+
+impl Screen for SelectFloor {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        let last = app.floor_select.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.state = GameState::TitleScreen,
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.floor_select_cursor = (app.floor_select_cursor + 1).min(last)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.floor_select_cursor = app.floor_select_cursor.saturating_sub(1)
+            }
+            KeyCode::Enter => {
+                if let Some(floor) = app
+                    .floor_select
+                    .get(app.floor_select_cursor)
+                    .filter(|floor| floor.unlocked)
+                {
+                    app.start_on_floor(floor.level);
+                }
+            }
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![
+            Line::from(Span::styled(format!("  {}", t!("floor-select-help")), dim)),
+            Line::from(""),
+        ];
+        for (i, floor) in app.floor_select.iter().enumerate() {
+            let selected = i == app.floor_select_cursor;
+            let marker = if selected { "▸" } else { " " };
+            let style = if floor.unlocked {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                dim
+            };
+            let style = if selected {
+                style.add_modifier(Modifier::REVERSED)
+            } else {
+                style
+            };
+            let status = if floor.unlocked {
+                t!(
+                    "floor-select-cleared",
+                    cleared = floor.cleared,
+                    rooms = floor.rooms
+                )
+            } else {
+                t!("floor-select-locked", above = floor.level - 1)
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("{} ", marker)),
+                Span::styled(
+                    t!(
+                        "floor-select-row",
+                        level = floor.level,
+                        name = floor.name.as_str()
+                    ),
+                    style,
+                ),
+                Span::styled(format!("  {}", status), dim),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", t!("floor-select-carry")),
+            dim,
+        )));
+
+        let list = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(t!("floor-select-title")),
+        );
+        f.render_widget(list, f.area());
+    }
+}
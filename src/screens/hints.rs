@@ -0,0 +1,104 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::Screen;
+use crate::app::{App, GameState};
+use crate::engine::Flow;
+use crate::i18n::t;
+
+/// The room's hints from `:hints`: the ones already paid for, to read again,
+/// and what the rest would cost.
+pub(crate) struct Hints;
+
+impl Screen for Hints {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        let last = app.room().narrative.hints.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.state = GameState::Playing,
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.hints_cursor = (app.hints_cursor + 1).min(last)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.hints_cursor = app.hints_cursor.saturating_sub(1)
+            }
+            KeyCode::Home => app.hints_cursor = 0,
+            KeyCode::End => app.hints_cursor = last,
+            // Hints come in order, so only the next one can be bought
+            KeyCode::Enter if app.hints_cursor == app.hints_used_room => {
+                app.state = GameState::Playing;
+                app.show_hint();
+            }
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        let hints = &app.room().narrative.hints;
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![
+            Line::from(Span::styled(format!("  {}", t!("hint-index-help")), dim)),
+            Line::from(""),
+        ];
+        let mut cursor_line = 0;
+        for (i, hint) in hints.iter().enumerate() {
+            let selected = i == app.hints_cursor;
+            if selected {
+                cursor_line = lines.len();
+            }
+            let marker = if selected { "▸" } else { " " };
+            let heading = Style::default().add_modifier(Modifier::BOLD);
+            let heading = if selected {
+                heading.add_modifier(Modifier::REVERSED)
+            } else {
+                heading
+            };
+            if i < app.hints_used_room {
+                lines.push(Line::from(Span::styled(
+                    format!("{} {}", marker, t!("hint-index-revealed", number = i + 1)),
+                    heading.fg(Color::Cyan),
+                )));
+                lines.extend(hint.lines().map(|text| Line::from(format!("    {}", text))));
+            } else {
+                let (key, hp) = if i == app.hints_used_room {
+                    ("hint-index-next", app.next_hint_cost())
+                } else {
+                    ("hint-index-locked", app.hint_cost())
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("{} {}", marker, t!(key, number = i + 1, hp = hp)),
+                    heading.fg(Color::DarkGray),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            format!("  {}", t!("hint-index-spirit", hp = 3 * app.hint_cost())),
+            dim,
+        )));
+
+        let area = f.area();
+        let visible = area.height.saturating_sub(2) as usize;
+        let scroll = cursor_line.saturating_sub(visible / 2) as u16;
+        let index = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title(t!(
+                        "hint-index-title",
+                        room = app.room().meta.title.as_str(),
+                        revealed = app.hints_used_room,
+                        total = hints.len()
+                    )),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0));
+        f.render_widget(index, area);
+    }
+}
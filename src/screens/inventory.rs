@@ -0,0 +1,80 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{playing, Screen};
+use crate::app::{App, DropOption};
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::{centered_rect, menu_line};
+
+/// A new item with no slot left for it: something has to stay behind.
+pub(crate) struct InventoryFull;
+
+impl Screen for InventoryFull {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.drop_menu.previous(),
+            KeyCode::Down | KeyCode::Char('j') => app.drop_menu.next(),
+            KeyCode::Enter => {
+                if let Some(option) = app.drop_menu.selected() {
+                    app.make_room(option);
+                }
+            }
+            KeyCode::Esc => {
+                let can_leave = app
+                    .drop_menu
+                    .entries()
+                    .any(|(entry, _)| entry.action == DropOption::LeaveNew && entry.enabled);
+                if can_leave {
+                    app.make_room(DropOption::LeaveNew);
+                }
+            }
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        let Some(new_item) = &app.pending_item else {
+            return;
+        };
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(t!(
+                "inventory-full-body",
+                item = new_item.name.as_str(),
+                capacity = app.inventory.capacity()
+            )),
+            Line::from(""),
+        ];
+        for (entry, selected) in app.drop_menu.entries() {
+            let name = match entry.action {
+                DropOption::Drop(index) => app.inventory.get(index).map(|i| i.name.as_str()),
+                DropOption::LeaveNew => Some(new_item.name.as_str()),
+            };
+            let label = t!(entry.label, item = name.unwrap_or_default());
+            lines.push(menu_line(&label, selected, entry.enabled));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            t!("inventory-full-help"),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        let area = centered_rect(60, lines.len() as u16 + 4, f.area());
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(t!("inventory-full-title")),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(dialog, area);
+    }
+}
@@ -0,0 +1,212 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::{playing, title, Screen};
+use crate::app::{App, GameState, PauseOption, PendingAction};
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::{centered_rect, format_duration, menu_line};
+
+/// The Esc menu over the room.
+pub(crate) struct Pause;
+
+impl Screen for Pause {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.pause_menu.previous(),
+            KeyCode::Down | KeyCode::Char('j') => app.pause_menu.next(),
+            KeyCode::Esc => app.state = GameState::Playing,
+            KeyCode::Enter => match app.pause_menu.selected() {
+                Some(PauseOption::Resume) => app.state = GameState::Playing,
+                Some(PauseOption::RestartRoom) => app.ask_confirmation(PendingAction::RestartRoom),
+                Some(PauseOption::Settings) => app.open_settings(GameState::Paused),
+                Some(PauseOption::ViewKeys) => {
+                    app.state = GameState::Playing;
+                    app.show_keys();
+                }
+                Some(PauseOption::SaveQuit) => return save_and_quit(app),
+                None => {}
+            },
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        draw_pause_menu(f, app);
+    }
+}
+
+/// Settings, opened from the title screen or the pause menu.
+pub(crate) struct SettingsMenu;
+
+impl Screen for SettingsMenu {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        let count = app.settings_entries().len();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.settings_selection = (app.settings_selection + count - 1) % count;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.settings_selection = (app.settings_selection + 1) % count;
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                app.toggle_setting(app.settings_selection);
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.state = app.settings_return,
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn update(&self, app: &mut App) {
+        if app.settings_return == GameState::TitleScreen {
+            title::Title.update(app);
+        }
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        if app.settings_return == GameState::TitleScreen {
+            // The title screen draws the settings box over itself
+            title::Title.render(f, app);
+        } else {
+            playing::draw_room(f, app);
+            draw_settings(f, app);
+        }
+    }
+}
+
+/// "Are you sure?" before quitting or restarting a room.
+pub(crate) struct Confirm;
+
+impl Screen for Confirm {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Char('s') | KeyCode::Char('S')
+                if app.pending_action == PendingAction::Quit =>
+            {
+                return save_and_quit(app);
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => match app.pending_action {
+                PendingAction::Quit => return save_and_quit(app),
+                PendingAction::RestartRoom => app.restart_room(),
+                PendingAction::RevealHint => {
+                    app.state = app.confirm_return;
+                    app.reveal_hint();
+                    return playing::check_fallen(app);
+                }
+                PendingAction::SummonSpirit => {
+                    app.state = app.confirm_return;
+                    app.buy_spirit();
+                    return playing::check_fallen(app);
+                }
+            },
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.state = app.confirm_return;
+            }
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        draw_confirmation(f, app);
+    }
+}
+
+/// Writes the run and leaves, or goes back to the room to show why it
+/// couldn't be written.
+fn save_and_quit(app: &mut App) -> Flow {
+    if app.save_before_quit() {
+        return Flow::Quit;
+    }
+    app.state = GameState::Playing;
+    Flow::Continue
+}
+
+fn draw_pause_menu(f: &mut Frame, app: &App) {
+    let area = centered_rect(30, app.pause_menu.len() as u16 + 6, f.area());
+    let mut lines = vec![Line::from("")];
+    for (item, selected) in app.pause_menu.entries() {
+        lines.push(menu_line(&t!(item.label), selected, item.enabled));
+    }
+    let session = format_duration(app.launched.elapsed());
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {}", t!("pause-session", time = session.as_str())),
+        Style::default().fg(Color::DarkGray),
+    )));
+    let menu = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(t!("pause-title")),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(menu, area);
+}
+
+pub(super) fn draw_settings(f: &mut Frame, app: &App) {
+    let entries = app.settings_entries();
+    let area = centered_rect(44, entries.len() as u16 + 6, f.area());
+    let mut lines = vec![Line::from("")];
+    for (i, (label, value)) in entries.iter().enumerate() {
+        lines.push(menu_line(
+            &format!("{:<24}{:>13}", label, value),
+            i == app.settings_selection,
+            true,
+        ));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {}", t!("settings-help")),
+        Style::default().fg(Color::DarkGray),
+    )));
+    let menu = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(t!("settings-title")),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(menu, area);
+}
+
+fn draw_confirmation(f: &mut Frame, app: &App) {
+    let (title, body) = app.confirmation_prompt();
+    let mut lines = vec![Line::from("")];
+    lines.extend(body.into_iter().map(|l| {
+        Line::from(Span::styled(
+            format!(" {}", l),
+            Style::default().fg(Color::White),
+        ))
+    }));
+    lines.push(Line::from(""));
+    let (key, yes) = match app.pending_action {
+        PendingAction::Quit => (" [s] ", t!("confirm-save-quit")),
+        _ => (" [y] ", t!("confirm-yes")),
+    };
+    lines.push(Line::from(vec![
+        Span::styled(key, Style::default().fg(Color::Black).bg(Color::Red)),
+        Span::raw(format!(" {}    ", yes)),
+        Span::styled(" [n] ", Style::default().fg(Color::Black).bg(Color::Green)),
+        Span::raw(format!(" {}", t!("confirm-no"))),
+    ]));
+
+    let area = centered_rect(50, lines.len() as u16 + 2, f.area());
+    let dialog = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title(format!(" {} ", title)),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(dialog, area);
+}
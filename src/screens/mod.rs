@@ -0,0 +1,73 @@
+//! One module per screen. The event loop asks [`for_state`] which screen is
+//! showing and hands it key presses, ticks and the frame to draw on, so a new
+//! screen is a new module plus one line here.
+
+mod altar;
+mod archive;
+mod attract;
+mod campaigns;
+mod codex;
+mod compiling;
+mod credits;
+mod event;
+mod feedback;
+mod floors;
+mod hints;
+mod inventory;
+mod menus;
+mod onboarding;
+mod placement;
+mod playing;
+mod recap;
+mod reminder;
+mod shop;
+mod story;
+mod title;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::app::{App, GameState};
+use crate::engine::Flow;
+
+pub(crate) trait Screen {
+    /// Reacts to a key press that no global shortcut claimed.
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow;
+
+    /// Runs once per tick while the screen is showing, e.g. to keep an
+    /// animation redrawing.
+    fn update(&self, _app: &mut App) {}
+
+    fn render(&self, f: &mut Frame, app: &App);
+}
+
+pub(crate) fn for_state(state: GameState) -> &'static dyn Screen {
+    match state {
+        GameState::TitleScreen => &title::Title,
+        GameState::Attract => &attract::Attract,
+        GameState::Credits => &credits::Credits,
+        GameState::Onboarding => &onboarding::Onboarding,
+        GameState::Playing => &playing::Playing,
+        GameState::RoomComplete => &story::RoomComplete,
+        GameState::RoomTransition => &story::RoomTransition,
+        GameState::Altar => &altar::AltarScreen,
+        GameState::Shop => &shop::Shop,
+        GameState::Event => &event::Event,
+        GameState::InventoryFull => &inventory::InventoryFull,
+        GameState::Placement => &placement::Placement,
+        GameState::BreakReminder => &reminder::BreakReminder,
+        GameState::Compiling => &compiling::Compiling,
+        GameState::LevelComplete => &story::LevelComplete,
+        GameState::FloorRecap => &recap::FloorRecap,
+        GameState::GameOver => &story::GameOver,
+        GameState::ViewingCodex => &codex::Codex,
+        GameState::ViewingArchive => &archive::ArchiveScreen,
+        GameState::ViewingCampaigns => &campaigns::Campaigns,
+        GameState::SelectingFloor => &floors::SelectFloor,
+        GameState::ViewingHints => &hints::Hints,
+        GameState::Feedback => &feedback::Feedback,
+        GameState::Paused => &menus::Pause,
+        GameState::Settings => &menus::SettingsMenu,
+        GameState::Confirm => &menus::Confirm,
+    }
+}
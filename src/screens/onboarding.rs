@@ -0,0 +1,90 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::Screen;
+use crate::app::{App, ONBOARDING_PAGES};
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::markdown;
+use crate::ui::centered_rect;
+
+pub(crate) struct Onboarding;
+
+impl Screen for Onboarding {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => {
+                app.onboarding_page = app.onboarding_page.saturating_sub(1);
+            }
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter | KeyCode::Char(' ') => {
+                if app.onboarding_page + 1 < ONBOARDING_PAGES.len() {
+                    app.onboarding_page += 1;
+                } else {
+                    app.finish_onboarding();
+                }
+            }
+            KeyCode::Esc => app.finish_onboarding(),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        draw_onboarding(f, app);
+    }
+}
+
+fn draw_onboarding(f: &mut Frame, app: &App) {
+    let (title, body) = ONBOARDING_PAGES[app.onboarding_page];
+    let body = t!(body);
+    let mut lines = vec![
+        Line::from(Span::styled(
+            t!(title),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(markdown::render(&body));
+    lines.push(Line::from(""));
+
+    let last = app.onboarding_page + 1 == ONBOARDING_PAGES.len();
+    let footer = t!(
+        if last {
+            "onboarding-footer-last"
+        } else {
+            "onboarding-footer"
+        },
+        page = app.onboarding_page + 1,
+        pages = ONBOARDING_PAGES.len()
+    );
+
+    let area = centered_rect(64, 18, f.area());
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+    let page = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .style(Style::default().fg(Color::White))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(t!("onboarding-title")),
+        );
+    f.render_widget(page, chunks[0]);
+    f.render_widget(
+        Paragraph::new(footer)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(ratatui::layout::Alignment::Center),
+        chunks[1],
+    );
+}
@@ -0,0 +1,117 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{title, Screen};
+use crate::app::{App, GameState};
+use crate::engine::Flow;
+use crate::highlight;
+use crate::i18n::t;
+use crate::ui::{centered_rect, menu_line};
+
+/// The placement quiz, over the title screen; Esc goes back to the menu.
+pub(crate) struct Placement;
+
+impl Screen for Placement {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        let Some(quiz) = &mut app.placement else {
+            app.state = GameState::TitleScreen;
+            return Flow::Continue;
+        };
+        let finished = quiz.question().is_none();
+        match key.code {
+            KeyCode::Esc => {
+                app.placement = None;
+                app.state = GameState::TitleScreen;
+            }
+            KeyCode::Enter if finished => app.finish_placement(),
+            KeyCode::Enter => quiz.answer(),
+            KeyCode::Up | KeyCode::Char('k') => quiz.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => quiz.move_cursor(1),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        title::draw_title_screen(f, app);
+        let Some(quiz) = &app.placement else {
+            return;
+        };
+        let mut lines = vec![Line::from("")];
+        let title = match quiz.question() {
+            Some(question) => {
+                let (number, total) = quiz.progress();
+                lines.extend(highlight::highlight(question.code.trim_matches('\n')));
+                lines.push(Line::from(""));
+                lines.push(Line::styled(
+                    t!("placement-ask"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ));
+                lines.push(Line::from(""));
+                for (i, choice) in question.choices.iter().enumerate() {
+                    // Multi-line outputs are shown on one line
+                    let choice = choice.trim_end().replace('\n', " ⏎ ");
+                    lines.push(menu_line(&choice, i == quiz.cursor, true));
+                }
+                lines.push(Line::from(""));
+                lines.push(Line::styled(
+                    t!("placement-help"),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                t!(
+                    "placement-title",
+                    number = number,
+                    total = total,
+                    level = question.floor
+                )
+            }
+            None => {
+                if let Some(missed) = quiz.missed() {
+                    lines.push(Line::from(t!("placement-missed")));
+                    lines.push(Line::from(""));
+                    lines.extend(highlight::highlight(missed.code.trim_matches('\n')));
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(vec![
+                        Span::raw(t!("placement-missed-answer")),
+                        Span::styled(
+                            missed.answer.trim_end().replace('\n', " ⏎ "),
+                            Style::default().fg(Color::LightYellow),
+                        ),
+                    ]));
+                    lines.push(Line::from(""));
+                }
+                lines.push(Line::styled(
+                    t!(
+                        "placement-result",
+                        level = quiz.floor(),
+                        name = app.puzzles.floor_name(quiz.floor())
+                    ),
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                lines.push(Line::from(""));
+                lines.push(Line::styled(
+                    t!("placement-result-help"),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                t!("placement-result-title")
+            }
+        };
+
+        let area = centered_rect(70, lines.len() as u16 + 3, f.area());
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(title),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(dialog, area);
+    }
+}
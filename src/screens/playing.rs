@@ -0,0 +1,1037 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::Screen;
+use crate::anim::{self, Celebration};
+use crate::app::{App, GameState, PendingAction};
+use crate::codex::Codex;
+use crate::compiler::TestCase;
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::modifiers::Source;
+use crate::toast::ToastKind;
+use crate::ui::format_duration;
+use crate::{commands, ghost, highlight, markdown, sync};
+
+/// The room itself: editing, running and command mode.
+pub(crate) struct Playing;
+
+impl Screen for Playing {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        // Paging through the keys is still reading them
+        app.reading_keys &= matches!(key.code, KeyCode::PageUp | KeyCode::PageDown);
+        if app.command_mode {
+            return handle_command_key(app, key);
+        }
+
+        match (key.code, key.modifiers) {
+            (KeyCode::Char(':'), KeyModifiers::NONE) => {
+                app.command_mode = true;
+                app.command_buffer.clear();
+                app.message_scroll = 0; // Reset scroll so command is visible
+            }
+            (KeyCode::Esc, _) => {
+                app.pause_menu.reset();
+                app.state = GameState::Paused;
+            }
+            (KeyCode::PageDown, KeyModifiers::SHIFT) | (KeyCode::Down, KeyModifiers::ALT) => {
+                app.scroll_narrative(3);
+            }
+            (KeyCode::PageUp, KeyModifiers::SHIFT) | (KeyCode::Up, KeyModifiers::ALT) => {
+                app.scroll_narrative(-3);
+            }
+            (KeyCode::PageDown, _) => scroll_message(app, 3),
+            (KeyCode::PageUp, _) => scroll_message(app, -3),
+            (KeyCode::F(5), _) | (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                app.run_shared();
+            }
+            (KeyCode::F(1), _) => {
+                app.show_hint();
+            }
+            (KeyCode::F(2), _) => {
+                app.show_keys();
+            }
+            (KeyCode::F(3), _) => {
+                app.toggle_side_by_side();
+            }
+            (KeyCode::F(4), _) => {
+                app.toggle_expected();
+            }
+            (KeyCode::F(6), _) => {
+                app.toggle_cheat_sheet();
+            }
+            (KeyCode::F(7), _) => {
+                app.look_up_mention();
+            }
+            (KeyCode::F(8), _) => {
+                app.toggle_compile_errors();
+            }
+            (KeyCode::F(9), _) => {
+                app.explain_error(None);
+            }
+            (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+                app.toggle_test_focus();
+            }
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
+                app.delete_line();
+            }
+            (KeyCode::Home, KeyModifiers::CONTROL) => {
+                app.goto_top();
+            }
+            (KeyCode::End, KeyModifiers::CONTROL) => {
+                app.goto_bottom();
+            }
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) => {
+                // Show current position
+                let (row, col) = app.editor.cursor();
+                let max = app.editor.lines().len();
+                app.message = t!(
+                    "cursor-position",
+                    line = row + 1,
+                    lines = max,
+                    col = col + 1
+                );
+                app.message_style = Style::default().fg(Color::DarkGray);
+            }
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => {
+                app.yank_line();
+            }
+            (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                app.paste_line();
+            }
+            (KeyCode::Char('z'), KeyModifiers::CONTROL) => {
+                app.editor.undo();
+            }
+            // Ctrl+Shift+Z; some terminals leave the Shift out of the modifiers
+            (KeyCode::Char('Z'), modifiers) if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.editor.redo();
+            }
+            _ => {
+                let (cursor_row, _) = app.editor.cursor();
+                let is_destructive = matches!(
+                    key.code,
+                    KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete | KeyCode::Enter
+                );
+
+                if is_destructive && app.is_line_locked(cursor_row) {
+                    app.message = t!("line-sealed");
+                    app.message_style = Style::default().fg(Color::Magenta);
+                } else {
+                    app.editor.input(key);
+                }
+            }
+        }
+
+        check_fallen(app)
+    }
+
+    fn update(&self, app: &mut App) {
+        // The room clock in the status bar only changes once a second
+        if app.clock_timer.fire(std::time::Instant::now()) {
+            app.needs_redraw = true;
+        }
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        draw_room(f, app);
+    }
+}
+
+/// Ends the run once HP is gone.
+pub(super) fn check_fallen(app: &mut App) -> Flow {
+    if app.hp == 0 {
+        app.state = GameState::GameOver;
+        app.record_telemetry("fell");
+        app.message = t!("game-over");
+        app.message_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+        return Flow::Quit;
+    }
+    Flow::Continue
+}
+
+/// The active buffs and debuffs, under the status bar: green where they
+/// help, red where they hurt.
+fn modifier_line(app: &App) -> Line<'static> {
+    let mut spans = Vec::new();
+    for modifier in app.modifiers.iter() {
+        let icon = match modifier.source {
+            Source::Item => format!("{} ", app.glyphs().item_icon(&modifier.name)),
+            Source::Difficulty | Source::Event => String::new(),
+        };
+        spans.push(Span::styled(
+            format!("  {}{}:", icon, modifier.name),
+            Style::default().fg(Color::Gray),
+        ));
+        for adjustment in &modifier.adjustments {
+            spans.push(Span::styled(
+                format!(" {}", adjustment.describe()),
+                Style::default().fg(if adjustment.helps() {
+                    Color::Green
+                } else {
+                    Color::Red
+                }),
+            ));
+        }
+    }
+    Line::from(spans)
+}
+
+/// Keys typed after `:`, run as a command on Enter (vim-style `:q`).
+fn handle_command_key(app: &mut App, key: KeyEvent) -> Flow {
+    match key.code {
+        KeyCode::Esc => {
+            app.command_mode = false;
+            app.command_buffer.clear();
+            app.command_history.reset();
+        }
+        KeyCode::Enter => {
+            app.command_history.record(&app.command_buffer);
+            if app.command_buffer == "q!" || app.command_buffer == "quit!" {
+                return Flow::Quit;
+            } else if app.command_buffer == "q" || app.command_buffer == "quit" {
+                app.ask_confirmation(PendingAction::Quit);
+            } else if app.command_buffer == "w" {
+                app.write_run();
+            } else if app.command_buffer == "help" {
+                app.message = t!("command-help");
+                app.message_style = Style::default().fg(Color::Cyan);
+            } else if app.command_buffer == "howto" || app.command_buffer == "tutorial" {
+                app.onboarding_page = 0;
+                app.state = GameState::Onboarding;
+            } else if app.command_buffer == "hint" {
+                app.show_hint();
+            } else if app.command_buffer == "accept" {
+                app.accept_spirit();
+            } else if app.command_buffer == "scratch" {
+                app.toggle_scratch();
+            } else if app.command_buffer == "diff" {
+                app.toggle_side_by_side();
+            } else if app.command_buffer == "cheat" || app.command_buffer == "cheatsheet" {
+                app.toggle_cheat_sheet();
+            } else if app.command_buffer == "expected" || app.command_buffer == "exp" {
+                app.toggle_expected();
+            } else if app.command_buffer == "inv" || app.command_buffer == "inventory" {
+                app.show_inventory();
+            } else if app.command_buffer == "intent" {
+                app.read_intent();
+            } else if app.command_buffer == "map" {
+                app.show_map();
+            } else if app.command_buffer == "errors" {
+                app.next_error();
+            } else if app.command_buffer == "last-error" {
+                app.goto_last_error();
+            } else if app.command_buffer == "keys"
+                || app.command_buffer == "shortcuts"
+                || app.command_buffer == "?"
+            {
+                app.show_keys();
+            } else if app.command_buffer == "archive" {
+                app.show_archive();
+                if app.state == GameState::ViewingArchive {
+                    app.command_mode = false;
+                    app.command_buffer.clear();
+                    return Flow::Continue;
+                }
+            } else if app.command_buffer == "campaigns" {
+                app.command_mode = false;
+                app.command_buffer.clear();
+                app.show_campaigns();
+                return Flow::Continue;
+            } else if app.command_buffer == "codex export" {
+                app.export_codex();
+            } else if let Some(term) = app.command_buffer.strip_prefix("codex ") {
+                let term = term.to_string();
+                app.open_codex_at(&term);
+            } else if app.command_buffer == "sync push" || app.command_buffer == "sync" {
+                app.start_sync(sync::Direction::Push);
+            } else if app.command_buffer == "sync pull" {
+                app.start_sync(sync::Direction::Pull);
+            } else if app.command_buffer == "bugreport" {
+                app.write_bug_report();
+            } else if app.command_buffer == "share" {
+                app.start_share();
+            } else if cfg!(feature = "ai-hints") && app.command_buffer == "oracle" {
+                #[cfg(feature = "ai-hints")]
+                app.start_oracle();
+            } else if app.command_buffer == "codex" || app.command_buffer == "j" {
+                if app.codex.is_empty() {
+                    app.message = t!("codex-empty");
+                    app.message_style = Style::default().fg(Color::DarkGray);
+                } else {
+                    app.command_mode = false;
+                    app.command_buffer.clear();
+                    app.state = GameState::ViewingCodex;
+                    app.codex.open();
+                    return Flow::Continue;
+                }
+            } else if app.command_buffer == "xyzzy" {
+                app.find_secret();
+            } else if app.command_buffer == "feedback" {
+                app.command_mode = false;
+                app.command_buffer.clear();
+                app.open_feedback();
+                return Flow::Continue;
+            } else if app.command_buffer == "hints" {
+                app.command_mode = false;
+                app.command_buffer.clear();
+                app.show_hints();
+                return Flow::Continue;
+            } else if app.command_buffer == "achievements" {
+                app.show_achievements();
+            } else if app.command_buffer == "goal" {
+                app.show_goal();
+            } else if app.command_buffer == "restart-room" {
+                app.ask_confirmation(PendingAction::RestartRoom);
+            } else if app.command_buffer == "summon" {
+                app.summon_variant();
+            } else if app.command_buffer == "restart" {
+                app.start_game();
+            } else if app.command_buffer == "top" || app.command_buffer == "0" {
+                app.goto_top();
+            } else if app.command_buffer == "bot" || app.command_buffer == "$" {
+                app.goto_bottom();
+            } else if let Some(item) = app.command_buffer.strip_prefix("drop ") {
+                let item = item.to_string();
+                app.drop_command(&item);
+            } else if let Some(expr) = app.command_buffer.strip_prefix("eval ") {
+                let expr = expr.to_string();
+                app.eval(&expr);
+            } else if app.command_buffer == "explain" {
+                app.explain_error(None);
+            } else if let Some(code) = app.command_buffer.strip_prefix("explain ") {
+                let code = code.to_string();
+                app.explain_error(Some(&code));
+            } else if let Some(query) = app.command_buffer.strip_prefix("doc ") {
+                let query = query.to_string();
+                app.show_doc(&query);
+            } else if let Some(line_str) = app.command_buffer.strip_prefix("goto ") {
+                if let Some(line) = line_target(app, line_str) {
+                    app.goto_line(line);
+                } else {
+                    app.message = t!("invalid-line", line = line_str);
+                    app.message_style = Style::default().fg(Color::Red);
+                }
+            } else if let Some(line) = line_target(app, &app.command_buffer) {
+                // Bare number = goto line
+                app.goto_line(line);
+            } else if !app.command_buffer.is_empty() {
+                app.message = t!("unknown-command", command = app.command_buffer.as_str());
+                app.message_style = Style::default().fg(Color::Red);
+            }
+            app.command_mode = false;
+            app.command_buffer.clear();
+        }
+        KeyCode::Backspace => {
+            app.command_buffer.pop();
+            app.command_history.reset();
+            if app.command_buffer.is_empty() {
+                app.command_mode = false;
+            }
+        }
+        KeyCode::Tab => {
+            if let Some(completed) = commands::complete(&app.command_buffer) {
+                app.command_buffer = completed;
+                app.command_history.reset();
+            }
+        }
+        KeyCode::Up => {
+            if let Some(previous) = app.command_history.previous(&app.command_buffer) {
+                app.command_buffer = previous.to_string();
+            }
+        }
+        KeyCode::Down => {
+            if let Some(next) = app.command_history.next() {
+                app.command_buffer = next.to_string();
+            }
+        }
+        KeyCode::Char(c) => {
+            app.command_buffer.push(c);
+            app.command_history.reset();
+        }
+        _ => {}
+    }
+    Flow::Continue
+}
+
+/// The line `spec` asks for: `5` for line 5, `+5` and `-3` for that many
+/// lines below or above the cursor's.
+fn line_target(app: &App, spec: &str) -> Option<usize> {
+    let spec = spec.trim();
+    let current = app.editor.cursor().0 + 1;
+    if let Some(down) = spec.strip_prefix('+') {
+        Some(current.saturating_add(down.parse().ok()?))
+    } else if let Some(up) = spec.strip_prefix('-') {
+        Some(current.saturating_sub(up.parse().ok()?).max(1))
+    } else {
+        spec.parse().ok()
+    }
+}
+
+/// Under the command being typed: the commands it could still become, or
+/// the usage of the one typed.
+fn command_guide(buffer: &str) -> Option<Line<'static>> {
+    let guide = match commands::usage(buffer) {
+        Some(usage) => t!(usage),
+        None => {
+            let names: Vec<String> = commands::candidates(buffer)
+                .iter()
+                .map(|command| format!(":{}", command.name))
+                .collect();
+            // Nothing to add once the only one left is what's typed
+            if names.is_empty() || names == [format!(":{}", buffer)] {
+                return None;
+            }
+            names.join("  ")
+        }
+    };
+    Some(Line::from(Span::styled(
+        guide,
+        Style::default().fg(Color::Gray),
+    )))
+}
+
+/// Scrolls the message pane, keeping its last few lines in view.
+pub(super) fn scroll_message(app: &mut App, delta: i16) {
+    let lines = app.message.lines().count() as u16;
+    if delta > 0 && app.message_scroll >= lines.saturating_sub(5) {
+        return;
+    }
+    app.message_scroll = app.message_scroll.saturating_add_signed(delta);
+}
+
+/// The room layout: status bar, narrative, editor and message pane.
+pub(super) fn draw_room(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(10),
+        ])
+        .split(f.area());
+
+    // Status bar
+    let room_progress = format!(
+        " {} ",
+        t!(
+            "hud-room",
+            level = app.current_level,
+            room = app.current_room + 1,
+            rooms = app.rooms.len()
+        )
+    );
+    let status = Line::from(vec![
+        Span::styled(
+            " RUST RAID ",
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            format!(" {} ", app.room().meta.title),
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+        ),
+        Span::raw("  "),
+        Span::styled(room_progress, Style::default().fg(Color::Cyan)),
+        Span::raw("  "),
+        Span::styled(
+            format!(" {} ", t!("hud-gold", gold = app.gold)),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            format!(" {} ", t!("hud-hp", hp = app.hp)),
+            Style::default().fg(if app.hp > 50 {
+                Color::Green
+            } else if app.hp > 20 {
+                Color::Yellow
+            } else {
+                Color::Red
+            }),
+        ),
+        Span::raw("  "),
+        room_stats(app),
+        Span::raw("  "),
+        ghost_status(app),
+        Span::raw("  "),
+        mode_indicator(app),
+    ]);
+    let status_block = Paragraph::new(vec![status, modifier_line(app)])
+        .block(Block::default().borders(Borders::BOTTOM));
+    f.render_widget(status_block, chunks[0]);
+    draw_toasts(f, app, chunks[0]);
+
+    // Main content: narrative + editor
+    // The cheat sheet only fits next to the editor on reasonably wide terminals
+    let show_cheat_sheet = app.settings.cheat_sheet && chunks[1].width >= 100;
+    let main_area = if show_cheat_sheet {
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(60), Constraint::Length(30)])
+            .split(chunks[1]);
+        draw_cheat_sheet(f, app, split[1]);
+        split[0]
+    } else {
+        chunks[1]
+    };
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(main_area);
+
+    let intro = &app.room().narrative.intro;
+    let pane_height = main_chunks[0].height.saturating_sub(2) as usize;
+    let chamber_title = if app.narrative_scroll > 0 || intro.lines().count() > pane_height {
+        t!("chamber-title-scroll")
+    } else {
+        t!("chamber-title")
+    };
+    let mut chamber = Block::default().borders(Borders::ALL).title(chamber_title);
+    if let Some(ambience) = app.room().meta.ambience {
+        chamber = chamber
+            .border_style(Style::default().fg(ambience.border()))
+            .title_style(Style::default().fg(ambience.accent()));
+    }
+    let narrative = Paragraph::new(mark_mentions(markdown::render(intro), &app.codex))
+        .block(chamber)
+        .wrap(Wrap { trim: false })
+        .style(Style::default().fg(Color::White))
+        .scroll((app.narrative_scroll, 0));
+    f.render_widget(narrative, main_chunks[0]);
+
+    render_editor(f, app, main_chunks[1]);
+
+    // Message area
+    if app.side_by_side && !app.command_mode && matches!(app.state, GameState::Playing) {
+        if let Some((expected, got)) = &app.last_output {
+            render_output_comparison(f, expected, got, app.message_scroll, chunks[2]);
+            return;
+        }
+    }
+
+    if let Some(celebration) = &app.celebration {
+        render_celebration(f, celebration, chunks[2]);
+        return;
+    }
+
+    let message_content = if app.command_mode {
+        let mut lines = vec![Line::from(format!(":{}", app.command_buffer))];
+        lines.extend(command_guide(&app.command_buffer));
+        lines
+    } else if let Some(typewriter) = &app.typewriter {
+        mark_mentions(
+            markdown::render(typewriter.visible(&app.message)),
+            &app.codex,
+        )
+    } else {
+        mark_mentions(markdown::render(&app.message), &app.codex)
+    };
+    let (message_style, message_title) = if app.command_mode {
+        (
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+            t!("message-title-command"),
+        )
+    } else {
+        match app.state {
+            GameState::RoomComplete | GameState::InventoryFull => (
+                Style::default().fg(Color::Black).bg(Color::Green),
+                t!("message-title-victory"),
+            ),
+            GameState::RoomTransition => (
+                Style::default().fg(Color::Cyan),
+                t!("message-title-descending"),
+            ),
+            GameState::Altar => (app.message_style, t!("message-title-altar")),
+            GameState::Shop => (app.message_style, t!("message-title-shop")),
+            GameState::Event => (app.message_style, t!("message-title-event")),
+            GameState::LevelComplete => (
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+                t!("message-title-level-complete"),
+            ),
+            GameState::GameOver => (
+                Style::default().fg(Color::White).bg(Color::Red),
+                t!("message-title-game-over"),
+            ),
+            GameState::Playing
+            | GameState::TitleScreen
+            | GameState::Attract
+            | GameState::ViewingCodex
+            | GameState::ViewingArchive
+            | GameState::ViewingCampaigns
+            | GameState::SelectingFloor
+            | GameState::ViewingHints
+            | GameState::Feedback
+            | GameState::FloorRecap
+            | GameState::Paused
+            | GameState::Settings
+            | GameState::Credits
+            | GameState::Onboarding
+            | GameState::Placement
+            | GameState::BreakReminder
+            | GameState::Compiling
+            | GameState::Confirm => (app.message_style, t!("message-title")),
+        }
+    };
+    let scroll_indicator = if app.message.lines().count() > 8 {
+        let scroll_keys = if cfg!(target_os = "macos") {
+            "Fn+Up/Down"
+        } else {
+            "PgUp/PgDn"
+        };
+        format!(
+            " {} {} ",
+            message_title,
+            t!("message-scroll", keys = scroll_keys)
+        )
+    } else {
+        format!(" {} ", message_title)
+    };
+    let message = Paragraph::new(message_content)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(scroll_indicator),
+        )
+        .wrap(Wrap { trim: false })
+        .style(message_style)
+        .scroll((app.message_scroll, 0));
+    f.render_widget(message, chunks[2]);
+}
+
+/// Underlines every unlocked codex title in `lines`; F7 or `:codex <term>`
+/// opens the entry. Titles split across differently styled spans are missed.
+fn mark_mentions(lines: Vec<Line<'static>>, codex: &Codex) -> Vec<Line<'static>> {
+    if codex.is_empty() {
+        return lines;
+    }
+    lines
+        .into_iter()
+        .map(|line| {
+            let mut spans = Vec::new();
+            for span in line.spans {
+                let mut rest = span.content.as_ref();
+                while let Some((range, _)) = codex.first_mention(rest) {
+                    if range.start > 0 {
+                        spans.push(Span::styled(rest[..range.start].to_string(), span.style));
+                    }
+                    spans.push(Span::styled(
+                        rest[range.clone()].to_string(),
+                        span.style
+                            .fg(Color::LightCyan)
+                            .add_modifier(Modifier::UNDERLINED),
+                    ));
+                    rest = &rest[range.end..];
+                }
+                if !rest.is_empty() {
+                    spans.push(Span::styled(rest.to_string(), span.style));
+                }
+            }
+            Line { spans, ..line }
+        })
+        .collect()
+}
+
+fn draw_cheat_sheet(f: &mut Frame, app: &App, area: Rect) {
+    let lines: Vec<Line> = app
+        .cheat_sheet_entries()
+        .into_iter()
+        .map(|(key, action)| {
+            Line::from(vec![
+                Span::styled(format!("{:<11}", key), Style::default().fg(Color::Yellow)),
+                Span::styled(t!(action), Style::default().fg(Color::Gray)),
+            ])
+        })
+        .collect();
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray))
+            .title(t!("cheat-sheet-title")),
+    );
+    f.render_widget(panel, area);
+}
+
+/// Live per-room HUD: attempts (out of the limit, if the room has one), hints
+/// revealed, and time spent in the room.
+fn room_stats(app: &App) -> Span<'static> {
+    let hints_total = app.room().narrative.hints.len();
+    let time = format_duration(app.room_elapsed());
+    let Some(max) = app.max_attempts() else {
+        return Span::styled(
+            format!(
+                " {} ",
+                t!(
+                    "hud-room-stats",
+                    attempts = app.attempts_room,
+                    hints = app.hints_used_room,
+                    hints_total = hints_total,
+                    time = time
+                )
+            ),
+            Style::default().fg(Color::Gray),
+        );
+    };
+    // Red once the next failure costs extra
+    let color = if app.attempts_room >= max {
+        Color::Red
+    } else {
+        Color::Gray
+    };
+    Span::styled(
+        format!(
+            " {} ",
+            t!(
+                "hud-room-stats-limited",
+                attempts = app.attempts_room,
+                max = max,
+                hints = app.hints_used_room,
+                hints_total = hints_total,
+                time = time
+            )
+        ),
+        Style::default().fg(color),
+    )
+}
+
+/// Recent HP, gold and item changes, right-aligned on the status bar's
+/// bottom border so they cover nothing.
+fn draw_toasts(f: &mut Frame, app: &App, status_area: Rect) {
+    if status_area.height < 3 || app.toasts.iter().next().is_none() {
+        return;
+    }
+    let now = std::time::Instant::now();
+    let mut spans = Vec::new();
+    for toast in app.toasts.iter() {
+        let style = if toast.fading(now) {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default()
+                .fg(match toast.kind {
+                    ToastKind::HpLost => Color::Red,
+                    ToastKind::HpGained => Color::Green,
+                    ToastKind::Gold => Color::Yellow,
+                    ToastKind::Item => Color::Cyan,
+                    ToastKind::Achievement => Color::Magenta,
+                })
+                .add_modifier(Modifier::BOLD)
+        };
+        spans.push(Span::styled(format!(" {} ", toast.text), style));
+        spans.push(Span::raw(" "));
+    }
+    let area = Rect {
+        y: status_area.y + status_area.height - 1,
+        height: 1,
+        ..status_area
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(spans)).alignment(Alignment::Right),
+        area,
+    );
+}
+
+/// How the run compares with the personal-best ghost; empty when not racing one.
+fn ghost_status(app: &App) -> Span<'static> {
+    let Some(ghost) = app.ghost.as_ref().filter(|_| app.racing) else {
+        return Span::raw("");
+    };
+    let icon = app.glyphs().ghost;
+    let elapsed = app.run_elapsed().as_secs();
+    match ghost.standing(&app.run_splits, &app.room().meta.id, elapsed) {
+        ghost::Standing::JustCleared(split) => Span::styled(
+            format!(
+                " {} {} ",
+                icon,
+                t!("ghost-cleared", room = split.title.as_str())
+            ),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::ITALIC),
+        ),
+        ghost::Standing::Delta(delta) => Span::styled(
+            format!(
+                " {} {}{} ",
+                icon,
+                if delta <= 0 { "-" } else { "+" },
+                format_duration(std::time::Duration::from_secs(delta.unsigned_abs()))
+            ),
+            Style::default().fg(if delta <= 0 { Color::Green } else { Color::Red }),
+        ),
+        ghost::Standing::InRoom(room) => Span::styled(
+            format!(" {} {} ", icon, t!("ghost-in-room", room = room)),
+            Style::default().fg(Color::DarkGray),
+        ),
+    }
+}
+
+/// Compact `EDIT 3:14 ●` segment: mode, cursor position, and unsaved-run marker.
+fn mode_indicator(app: &App) -> Span<'static> {
+    let (mode, style) = if app.command_mode {
+        (
+            t!("mode-command"),
+            Style::default().fg(Color::Black).bg(Color::Magenta),
+        )
+    } else {
+        (
+            t!("mode-edit"),
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        )
+    };
+    let (row, col) = app.editor.cursor();
+    let modified = if app.is_modified() { " ●" } else { "" };
+    let coop = if app.coop.is_some() {
+        format!(" {}", t!("mode-coop"))
+    } else {
+        String::new()
+    };
+    Span::styled(
+        format!(" {} {}:{}{}{} ", mode, row + 1, col + 1, modified, coop),
+        style,
+    )
+}
+
+fn render_editor(f: &mut Frame, app: &App, area: Rect) {
+    let tests = &app.room().challenge.tests;
+    let area = match app.focused_test.and_then(|i| Some((i, tests.get(i)?))) {
+        Some((i, test)) => {
+            let lines = test_input(test);
+            let strip_height = (lines.len() as u16 + 2).min(area.height / 3).max(3);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(5), Constraint::Length(strip_height)])
+                .split(area);
+            let strip = Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Magenta))
+                        .title(t!("test-focus-title", number = i + 1, total = tests.len())),
+                )
+                .style(Style::default().fg(Color::Magenta));
+            f.render_widget(strip, chunks[1]);
+            chunks[0]
+        }
+        None => area,
+    };
+    if !app.show_expected {
+        draw_code(f, app, area);
+        return;
+    }
+
+    let expected = app.room().challenge.expected_output.trim();
+    // Keep the strip compact so the editor always gets most of the space
+    let strip_height = (expected.lines().count() as u16 + 2)
+        .min(area.height / 3)
+        .max(3);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(strip_height)])
+        .split(area);
+
+    draw_code(f, app, chunks[0]);
+
+    let strip = Paragraph::new(expected)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray))
+                .title(t!("expected-title")),
+        )
+        .style(Style::default().fg(Color::Green));
+    f.render_widget(strip, chunks[1]);
+}
+
+/// The editor, with its code highlighted and its border tinted by the room's
+/// ambience.
+fn draw_code(f: &mut Frame, app: &App, area: Rect) {
+    f.render_widget(&app.editor, area);
+    let inner = app.editor.block().map_or(area, |block| block.inner(area));
+    highlight::paint_editor(
+        f.buffer_mut(),
+        inner,
+        app.editor.lines(),
+        &app.locked_lines,
+        &app.error_markers,
+    );
+    tint_editor_border(f, app, area);
+}
+
+/// The widget draws its own block, so the ambience goes over the box's lines
+/// once they're drawn. The scratch pad keeps its own color.
+fn tint_editor_border(f: &mut Frame, app: &App, area: Rect) {
+    let Some(ambience) = app
+        .room()
+        .meta
+        .ambience
+        .filter(|_| app.room_buffer.is_none())
+    else {
+        return;
+    };
+    if area.is_empty() {
+        return;
+    }
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+    let buf = f.buffer_mut();
+    let top_and_bottom = (area.left()..=right).flat_map(|x| [(x, area.top()), (x, bottom)]);
+    let sides = (area.top()..=bottom).flat_map(|y| [(area.left(), y), (right, y)]);
+    for (x, y) in top_and_bottom.chain(sides) {
+        let cell = &mut buf[(x, y)];
+        // The title sits on the top edge too; only the box's lines are tinted
+        if matches!(cell.symbol(), "─" | "│" | "┌" | "┐" | "└" | "┘") && cell.fg == Color::Reset
+        {
+            cell.fg = ambience.border();
+        }
+    }
+}
+
+/// A pinned hidden test's arguments and input, for the strip under the editor.
+fn test_input(test: &TestCase) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if !test.args.is_empty() {
+        let args: Vec<String> = test.args.iter().map(|arg| format!("{:?}", arg)).collect();
+        lines.push(Line::from(t!("test-focus-args", args = args.join(" "))));
+    }
+    match &test.stdin {
+        Some(stdin) => {
+            lines.push(Line::from(t!("test-focus-stdin")));
+            lines.extend(
+                stdin
+                    .trim_end()
+                    .lines()
+                    .map(|line| Line::from(line.to_string())),
+            );
+        }
+        None if test.args.is_empty() => lines.push(Line::from(t!("test-focus-no-input"))),
+        None => {}
+    }
+    lines
+}
+
+fn render_output_comparison(f: &mut Frame, expected: &str, got: &str, scroll: u16, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(t!("comparison-title"));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+
+    // Lines are paired by position so the first divergence is easy to spot
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let got_lines: Vec<&str> = got.lines().collect();
+    let rows = expected_lines.len().max(got_lines.len());
+
+    let mut left = Vec::with_capacity(rows);
+    let mut right = Vec::with_capacity(rows);
+    for i in 0..rows {
+        let exp = expected_lines.get(i).copied();
+        let act = got_lines.get(i).copied();
+        let matches = exp == act;
+        let number = Span::styled(
+            format!("{:>3} ", i + 1),
+            Style::default().fg(Color::DarkGray),
+        );
+        let (exp_style, act_style) = if matches {
+            (
+                Style::default().fg(Color::White),
+                Style::default().fg(Color::White),
+            )
+        } else {
+            (
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::White).bg(Color::Red),
+            )
+        };
+        left.push(Line::from(vec![
+            number.clone(),
+            Span::styled(exp.unwrap_or("∅").to_string(), exp_style),
+        ]));
+        right.push(Line::from(vec![
+            number,
+            Span::styled(act.unwrap_or("∅").to_string(), act_style),
+        ]));
+    }
+
+    let expected_pane = Paragraph::new(left)
+        .block(Block::default().borders(Borders::RIGHT).title(Span::styled(
+            format!(" {} ", t!("comparison-expected")),
+            Style::default().fg(Color::Green),
+        )))
+        .scroll((scroll, 0));
+    f.render_widget(expected_pane, columns[0]);
+
+    let got_pane = Paragraph::new(right)
+        .block(Block::default().title(Span::styled(
+            format!(" {} ", t!("comparison-got")),
+            Style::default().fg(Color::Red),
+        )))
+        .scroll((scroll, 0));
+    f.render_widget(got_pane, columns[1]);
+}
+
+fn render_celebration(f: &mut Frame, celebration: &Celebration, area: Rect) {
+    const COLORS: [Color; 6] = [
+        Color::Yellow,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Green,
+        Color::Red,
+        Color::LightBlue,
+    ];
+    const GLYPHS: [&str; 6] = ["*", "+", "✦", "·", "o", "✧"];
+
+    let frame = celebration.frame();
+    let border_color = COLORS[(frame / 3) as usize % COLORS.len()];
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(border_color)
+                .add_modifier(Modifier::BOLD),
+        )
+        .title(format!(" {} ", t!("message-title-victory")));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    // Each particle falls from its own column at its own speed, wrapping around
+    let buf = f.buffer_mut();
+    let count = (inner.width as u64 * inner.height as u64 / 10).max(8);
+    for p in 0..count {
+        let seed = anim::scramble(p ^ celebration.seed);
+        let x = inner.x + (seed % inner.width as u64) as u16;
+        let speed = 1 + (seed >> 16) % 3;
+        let y =
+            inner.y + ((seed >> 24).wrapping_add(frame * speed / 2) % inner.height as u64) as u16;
+        let glyph = GLYPHS[((seed >> 32) % GLYPHS.len() as u64) as usize];
+        let color = COLORS[((seed >> 40).wrapping_add(frame / 4) % COLORS.len() as u64) as usize];
+        buf[(x, y)].set_symbol(glyph).set_fg(color);
+    }
+
+    let headline = Paragraph::new(Line::from(Span::styled(
+        format!("  {}  ", celebration.headline),
+        Style::default()
+            .fg(Color::Black)
+            .bg(border_color)
+            .add_modifier(Modifier::BOLD),
+    )))
+    .alignment(ratatui::layout::Alignment::Center);
+    let middle = Rect {
+        y: inner.y + inner.height / 2,
+        height: 1,
+        ..inner
+    };
+    f.render_widget(headline, middle);
+}
@@ -0,0 +1,128 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::Screen;
+use crate::app::{App, GameState};
+use crate::engine::Flow;
+use crate::i18n::t;
+
+/// Between floors: the codex entries found on the floor just finished, and
+/// the concepts the next floor's manifest says are coming.
+pub(crate) struct FloorRecap;
+
+impl Screen for FloorRecap {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Enter => {
+                if let Err(e) = app.descend() {
+                    app.state = GameState::LevelComplete;
+                    app.message = t!("story-cannot-proceed", error = e);
+                    app.message_style = Style::default().fg(Color::Red);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.recap_scroll = app.recap_scroll.saturating_add(1)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.recap_scroll = app.recap_scroll.saturating_sub(1)
+            }
+            KeyCode::PageDown => app.recap_scroll = app.recap_scroll.saturating_add(10),
+            KeyCode::PageUp => app.recap_scroll = app.recap_scroll.saturating_sub(10),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        let level = app.current_level;
+        let dim = Style::default().fg(Color::DarkGray);
+        let heading = Style::default().add_modifier(Modifier::BOLD);
+        let mut lines = vec![
+            Line::from(Span::styled(format!("  {}", t!("recap-help")), dim)),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {}", t!("recap-learned")),
+                heading.fg(Color::Cyan),
+            )),
+        ];
+        let mut learned = 0;
+        for page in app.codex.on_floor(level) {
+            learned += 1;
+            lines.push(Line::from(vec![
+                Span::styled(format!("  • {}", page.entry.title), heading),
+                Span::styled(format!("  {}", page.concept), dim),
+            ]));
+            // The first paragraph is the gist; the codex has the rest
+            let gist = page.entry.description.trim().split("\n\n").next();
+            lines.extend(
+                gist.into_iter()
+                    .flat_map(str::lines)
+                    .map(|text| Line::from(format!("    {}", text.trim()))),
+            );
+        }
+        if learned == 0 {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", t!("recap-learned-none")),
+                dim,
+            )));
+        }
+        let titles = app.codex.titles();
+        let missed = app
+            .rooms
+            .iter()
+            .filter_map(|room| room.codex.as_ref())
+            .filter(|entry| !titles.contains(&entry.title))
+            .count();
+        if missed > 0 {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", t!("recap-missed", count = missed)),
+                dim,
+            )));
+        }
+
+        let next = level + 1;
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {}",
+                t!(
+                    "recap-ahead",
+                    level = next,
+                    name = app.puzzles.floor_name(next)
+                )
+            ),
+            heading.fg(Color::Yellow),
+        )));
+        if app.recap_ahead.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", t!("recap-ahead-none")),
+                dim,
+            )));
+        }
+        lines.extend(
+            app.recap_ahead
+                .iter()
+                .map(|concept| Line::from(format!("  • {}", concept))),
+        );
+
+        let recap = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title(t!(
+                        "recap-title",
+                        level = level,
+                        name = app.puzzles.floor_name(level)
+                    )),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((app.recap_scroll, 0));
+        f.render_widget(recap, f.area());
+    }
+}
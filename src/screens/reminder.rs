@@ -0,0 +1,63 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{playing, Screen};
+use crate::app::App;
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::{centered_rect, format_duration};
+
+/// A nudge to step away, over the room. The room and run timers stand still
+/// until it's dismissed.
+pub(crate) struct BreakReminder;
+
+impl Screen for BreakReminder {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+            app.end_break();
+        }
+        Flow::Continue
+    }
+
+    fn update(&self, app: &mut App) {
+        // The break clock counts up
+        app.needs_redraw = true;
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        let session = format_duration(app.launched.elapsed());
+        let on_break = format_duration(app.break_elapsed());
+        let lines = vec![
+            Line::from(""),
+            Line::styled(
+                t!("break-body", session = session.as_str()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::from(""),
+            Line::from(t!("break-advice")),
+            Line::from(""),
+            Line::styled(
+                t!("break-paused", time = on_break.as_str()),
+                Style::default().fg(Color::Cyan),
+            ),
+            Line::from(""),
+            Line::styled(t!("break-help"), Style::default().fg(Color::DarkGray)),
+        ];
+
+        let area = centered_rect(56, lines.len() as u16 + 4, f.area());
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green))
+                .title(t!("break-title")),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(dialog, area);
+    }
+}
@@ -0,0 +1,92 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{playing, Screen};
+use crate::app::{App, ShopOption};
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::ui::{centered_rect, menu_line};
+
+/// The floor's wares, after its last room or the room its `shop.toml` names.
+pub(crate) struct Shop;
+
+impl Screen for Shop {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.shop_menu.previous(),
+            KeyCode::Down | KeyCode::Char('j') => app.shop_menu.next(),
+            KeyCode::Enter => {
+                if let Some(option) = app.shop_menu.selected() {
+                    use_shop(app, option);
+                }
+            }
+            KeyCode::Esc => use_shop(app, ShopOption::Leave),
+            KeyCode::PageDown => playing::scroll_message(app, 3),
+            KeyCode::PageUp => playing::scroll_message(app, -3),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+        let mut lines = vec![
+            Line::from(""),
+            Line::styled(
+                t!("shop-purse", gold = app.gold),
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::from(""),
+        ];
+        let mut description = None;
+        for (entry, selected) in app.shop_menu.entries() {
+            let label = match entry.action {
+                ShopOption::Buy(index) => {
+                    let Some(ware) = app.floor.shop.get(index) else {
+                        continue;
+                    };
+                    if selected {
+                        description = ware.description.as_deref();
+                    }
+                    let label = if ware.effect.is_none() && app.inventory.contains(&ware.name) {
+                        "shop-sold"
+                    } else {
+                        entry.label
+                    };
+                    t!(label, item = ware.name.as_str(), gold = ware.price)
+                }
+                ShopOption::Leave => t!(entry.label),
+            };
+            lines.push(menu_line(&label, selected, entry.enabled));
+        }
+        if let Some(description) = description {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                description.trim().to_string(),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        let area = centered_rect(60, lines.len() as u16 + 4, f.area());
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(t!("shop-title")),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(dialog, area);
+    }
+}
+
+fn use_shop(app: &mut App, option: ShopOption) {
+    if let Err(e) = app.use_shop(option) {
+        app.message = t!("story-cannot-proceed", error = e);
+        app.message_style = Style::default().fg(Color::Red);
+    }
+}
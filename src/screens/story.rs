@@ -0,0 +1,102 @@
+//! The message-only screens between rooms. They keep the room layout and
+//! only put their text in the message pane.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Color, Style},
+    Frame,
+};
+
+use super::{playing, Screen};
+use crate::app::{App, GameState};
+use crate::engine::Flow;
+use crate::i18n::t;
+
+/// The victory message after a room is cleared.
+pub(crate) struct RoomComplete;
+
+impl Screen for RoomComplete {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Enter => app.advance_room(),
+            KeyCode::Esc => {
+                // Return to playing state (escape from stuck states)
+                app.state = GameState::Playing;
+                app.message = t!("story-run-prompt");
+                app.message_style = Style::default().fg(Color::Yellow);
+            }
+            KeyCode::PageDown => playing::scroll_message(app, 3),
+            KeyCode::PageUp => playing::scroll_message(app, -3),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+    }
+}
+
+/// The next room's entry narrative.
+pub(crate) struct RoomTransition;
+
+impl Screen for RoomTransition {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Enter => {
+                // Load the next room after showing transition
+                app.load_room(app.current_room + 1);
+            }
+            KeyCode::PageDown => playing::scroll_message(app, 3),
+            KeyCode::PageUp => playing::scroll_message(app, -3),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+    }
+}
+
+/// The end of a floor.
+pub(crate) struct LevelComplete;
+
+impl Screen for LevelComplete {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Enter => {
+                if app.current_level < app.puzzles.floor_count() {
+                    if let Err(e) = app.descend() {
+                        app.message = t!("story-cannot-proceed", error = e);
+                        app.message_style = Style::default().fg(Color::Red);
+                    }
+                } else {
+                    // Game complete!
+                    return Flow::Quit;
+                }
+            }
+            KeyCode::PageDown => playing::scroll_message(app, 3),
+            KeyCode::PageUp => playing::scroll_message(app, -3),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+    }
+}
+
+/// HP ran out; any key leaves.
+pub(crate) struct GameOver;
+
+impl Screen for GameOver {
+    fn handle_key(&self, _app: &mut App, _key: KeyEvent) -> Flow {
+        Flow::Quit
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        playing::draw_room(f, app);
+    }
+}
@@ -0,0 +1,226 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::{menus, Screen};
+use crate::app::{App, GameState, MenuOption};
+use crate::engine::Flow;
+use crate::i18n::t;
+use crate::{anim, attract};
+
+pub(crate) struct Title;
+
+impl Screen for Title {
+    fn handle_key(&self, app: &mut App, key: KeyEvent) -> Flow {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.menu.previous(),
+            KeyCode::Down | KeyCode::Char('j') => app.menu.next(),
+            KeyCode::Enter => match app.menu.selected() {
+                Some(MenuOption::Continue) => app.continue_run(),
+                Some(MenuOption::NewGame) => app.start_game(),
+                Some(MenuOption::Placement) => app.start_placement(),
+                Some(MenuOption::LevelSelect) => app.show_floor_select(),
+                Some(MenuOption::Settings) => app.open_settings(GameState::TitleScreen),
+                Some(MenuOption::Campaigns) => app.show_campaigns(),
+                Some(MenuOption::Credits) => app.show_credits(),
+                Some(MenuOption::Quit) => return Flow::Quit,
+                // Greyed-out entries can't be selected
+                _ => {}
+            },
+            KeyCode::Char('q') => return Flow::Quit,
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn update(&self, app: &mut App) {
+        // The torches flicker
+        if !app.accessible {
+            app.needs_redraw = true;
+        }
+        // Never during playback, where the recording decides what happens
+        if !app.accessible
+            && app.replay_label.is_none()
+            && app.last_key.elapsed() >= attract::IDLE_AFTER
+        {
+            app.start_attract();
+        }
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        draw_title_screen(f, app);
+    }
+}
+
+pub(super) fn draw_title_screen(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(21),
+            Constraint::Length(app.menu.len() as u16 + 1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(area);
+
+    let frame = app.ambient_frame();
+
+    // Slow warm color cycle across the logo
+    const LOGO_COLORS: [Color; 6] = [
+        Color::Yellow,
+        Color::LightYellow,
+        Color::LightRed,
+        Color::Red,
+        Color::LightRed,
+        Color::LightYellow,
+    ];
+    let logo_color = LOGO_COLORS[(frame / 18) as usize % LOGO_COLORS.len()];
+    let title = Paragraph::new(title_art(app))
+        .style(Style::default().fg(logo_color))
+        .alignment(ratatui::layout::Alignment::Center);
+    f.render_widget(title, chunks[0]);
+
+    // Torches flanking the logo (the art is indented, so it sits slightly right of center)
+    let center = chunks[0].x + chunks[0].width / 2;
+    for (side, x) in [(0, center.saturating_sub(33)), (1, center + 34)] {
+        let torch_area = Rect {
+            x,
+            y: chunks[0].y + 8,
+            width: 3,
+            height: 5,
+        };
+        if torch_area.right() <= area.right() && x >= area.x {
+            f.render_widget(torch(frame, side), torch_area);
+        }
+    }
+
+    let menu_lines: Vec<Line> = app
+        .menu
+        .entries()
+        .map(|(item, selected)| {
+            let style = if !item.enabled {
+                Style::default().fg(Color::DarkGray)
+            } else if selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(Span::styled(format!("  {}  ", t!(item.label)), style))
+        })
+        .collect();
+    let menu = Paragraph::new(menu_lines).alignment(ratatui::layout::Alignment::Center);
+    f.render_widget(menu, chunks[1]);
+
+    // Gentle pulse so the prompt draws the eye without shouting
+    let pulse = match (frame / 12) % 4 {
+        0 => Color::DarkGray,
+        1 | 3 => Color::Gray,
+        _ => Color::White,
+    };
+    let help = Paragraph::new(t!("title-help"))
+        .style(Style::default().fg(pulse))
+        .alignment(ratatui::layout::Alignment::Center);
+    f.render_widget(help, chunks[2]);
+
+    if let Some(version) = &app.update_notice {
+        let notice = Paragraph::new(t!(
+            "update-available",
+            version = version.as_str(),
+            current = env!("CARGO_PKG_VERSION")
+        ))
+        .style(Style::default().fg(Color::Green))
+        .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(notice, chunks[3]);
+    }
+
+    if matches!(app.state, GameState::Settings) {
+        menus::draw_settings(f, app);
+    }
+}
+
+/// The logo, in box-drawing characters unless the terminal can't show them.
+pub(super) fn title_art(app: &App) -> &'static str {
+    if app.ascii_only() {
+        ASCII_TITLE_ART
+    } else {
+        TITLE_ART
+    }
+}
+
+const TITLE_ART: &str = r#"
+    ╔═══════════════════════════════════════════════════════════╗
+    ║                                                           ║
+    ║              ██████╗ ██╗   ██╗███████╗████████╗           ║
+    ║              ██╔══██╗██║   ██║██╔════╝╚══██╔══╝           ║
+    ║              ██████╔╝██║   ██║███████╗   ██║              ║
+    ║              ██╔══██╗██║   ██║╚════██║   ██║              ║
+    ║              ██║  ██║╚██████╔╝███████║   ██║              ║
+    ║              ╚═╝  ╚═╝ ╚═════╝ ╚══════╝   ╚═╝              ║
+    ║                                                           ║
+    ║              ██████╗  █████╗ ██╗██████╗                   ║
+    ║              ██╔══██╗██╔══██╗██║██╔══██╗                  ║
+    ║              ██████╔╝███████║██║██║  ██║                  ║
+    ║              ██╔══██╗██╔══██║██║██║  ██║                  ║
+    ║              ██║  ██║██║  ██║██║██████╔╝                  ║
+    ║              ╚═╝  ╚═╝╚═╝  ╚═╝╚═╝╚═════╝                   ║
+    ║                                                           ║
+    ║              "Raid the Borrow Dungeon"                    ║
+    ║                                                           ║
+    ╚═══════════════════════════════════════════════════════════╝
+"#;
+
+const ASCII_TITLE_ART: &str = r#"
+    +-----------------------------------------------------------+
+    |                                                           |
+    |           ____            _     ____       _     _        |
+    |          |  _ \ _   _ ___| |_  |  _ \ __ _(_) __| |       |
+    |          | |_) | | | / __| __| | |_) / _` | |/ _` |       |
+    |          |  _ <| |_| \__ \ |_  |  _ < (_| | | (_| |       |
+    |          |_| \_\\__,_|___/\__| |_| \_\__,_|_|\__,_|       |
+    |                                                           |
+    |                                                           |
+    |                                                           |
+    |                                                           |
+    |                                                           |
+    |                                                           |
+    |                                                           |
+    |              "Raid the Borrow Dungeon"                    |
+    |                                                           |
+    +-----------------------------------------------------------+
+"#;
+
+fn torch(frame: u64, side: u64) -> Paragraph<'static> {
+    const TIPS: [&str; 4] = [" ( ", " ) ", " ' ", "   "];
+    const FLAMES: [&str; 4] = ["(()", "())", ")()", "(()"];
+    const HEAT: [Color; 3] = [Color::Yellow, Color::LightRed, Color::Red];
+
+    // Reseed a few times a second so the flicker looks organic rather than strobing
+    let seed = anim::scramble(frame / 3 * 2 + side);
+    let tip = TIPS[(seed % TIPS.len() as u64) as usize];
+    let flame = FLAMES[((seed >> 8) % FLAMES.len() as u64) as usize];
+    let tip_color = HEAT[((seed >> 16) % HEAT.len() as u64) as usize];
+    let flame_color = HEAT[((seed >> 24) % 2) as usize];
+
+    Paragraph::new(vec![
+        Line::from(Span::styled(tip, Style::default().fg(tip_color))),
+        Line::from(Span::styled(
+            flame,
+            Style::default()
+                .fg(flame_color)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled("\\=/", Style::default().fg(Color::DarkGray))),
+        Line::from(Span::styled(" | ", Style::default().fg(Color::DarkGray))),
+        Line::from(Span::styled(" | ", Style::default().fg(Color::DarkGray))),
+    ])
+}
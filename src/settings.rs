@@ -0,0 +1,170 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::i18n::t;
+use crate::modifiers::Difficulty;
+use crate::paths;
+use crate::theme::Theme;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Reveal entry and success narratives character by character.
+    pub typewriter: bool,
+    pub typewriter_chars_per_second: u32,
+    /// Play a confetti burst when a room or level is cleared.
+    pub celebrations: bool,
+    /// Replace emoji and box-drawing decorations with plain ASCII.
+    pub ascii_only: bool,
+    /// Keep a slim panel of the most useful keys next to the editor.
+    pub cheat_sheet: bool,
+    /// Color scheme; `--theme` overrides it for a single session.
+    pub theme: Theme,
+    /// Look for a newer release at startup (at most once a day, in the background).
+    pub check_for_updates: bool,
+    /// Show the current room on Discord (builds with the `discord` feature only).
+    pub discord_presence: bool,
+    /// How to announce the verdict of a slow run while the terminal is in the background.
+    pub verdict_alert: VerdictAlert,
+    /// Sound effects and ambient music (builds with the `sound` feature only).
+    pub sound: bool,
+    /// 0-100
+    pub volume: u8,
+    /// Record anonymous per-room outcomes locally (see `rust-raid telemetry`).
+    pub telemetry: bool,
+    /// Private gist that `:sync` pushes to and pulls from; created on first push.
+    pub sync_gist_id: Option<String>,
+    /// OpenAI-compatible chat completions URL for `:oracle` (`ai-hints` feature only).
+    pub ai_hint_endpoint: String,
+    pub ai_hint_model: String,
+    /// Interface language such as `de` or `pt-BR`; unset follows `LANG`.
+    pub locale: Option<String>,
+    /// `easy`, `normal` or `hard`; takes effect when a new game starts.
+    pub difficulty: Difficulty,
+    /// When progress (best times and the like) is written to disk.
+    pub autosave: Autosave,
+    /// Earlier copies kept of each progress file, as `<file>.1`, `<file>.2`, ...
+    pub save_backups: usize,
+    /// Suggest a break after this many minutes of play; 0 never does.
+    pub break_reminder_minutes: u32,
+    /// Stop the room and run clocks on the codex, the keys, the narratives
+    /// between rooms and the pause menu.
+    pub pause_clock_while_reading: bool,
+    /// Seconds rustc, and then the compiled solution, may each run before being killed.
+    pub run_timeout_seconds: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Autosave {
+    /// After every room cleared
+    #[default]
+    EveryRoom,
+    /// When a floor is finished
+    EveryFloor,
+    /// Only when the game is quit normally
+    OnQuit,
+}
+
+impl Autosave {
+    /// The value shown on the settings screen.
+    pub fn name(self) -> String {
+        t!(match self {
+            Autosave::EveryRoom => "autosave-every-room",
+            Autosave::EveryFloor => "autosave-every-floor",
+            Autosave::OnQuit => "autosave-on-quit",
+        })
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Autosave::EveryRoom => Autosave::EveryFloor,
+            Autosave::EveryFloor => Autosave::OnQuit,
+            Autosave::OnQuit => Autosave::EveryRoom,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VerdictAlert {
+    Off,
+    /// The terminal bell, which most terminals turn into an urgency hint
+    #[default]
+    Bell,
+    /// A desktop notification (builds with the `desktop-notify` feature only;
+    /// others ring the bell)
+    Desktop,
+}
+
+impl VerdictAlert {
+    /// The value shown on the settings screen.
+    pub fn name(self) -> String {
+        t!(match self {
+            VerdictAlert::Off => "setting-off",
+            VerdictAlert::Bell => "alert-bell",
+            VerdictAlert::Desktop => "alert-desktop",
+        })
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            VerdictAlert::Off => VerdictAlert::Bell,
+            VerdictAlert::Bell if cfg!(feature = "desktop-notify") => VerdictAlert::Desktop,
+            VerdictAlert::Bell | VerdictAlert::Desktop => VerdictAlert::Off,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            typewriter: true,
+            typewriter_chars_per_second: 90,
+            celebrations: true,
+            ascii_only: false,
+            cheat_sheet: true,
+            theme: Theme::Dungeon,
+            check_for_updates: false,
+            discord_presence: false,
+            verdict_alert: VerdictAlert::Bell,
+            sound: false,
+            volume: 60,
+            telemetry: false,
+            sync_gist_id: None,
+            ai_hint_endpoint: "https://api.openai.com/v1/chat/completions".to_string(),
+            ai_hint_model: "gpt-4o-mini".to_string(),
+            locale: None,
+            difficulty: Difficulty::Normal,
+            autosave: Autosave::EveryRoom,
+            save_backups: 3,
+            break_reminder_minutes: 0,
+            pause_clock_while_reading: true,
+            run_timeout_seconds: 5,
+        }
+    }
+}
+
+impl Settings {
+    /// Loads settings from disk, falling back to defaults if the file is missing or invalid.
+    pub fn load() -> Self {
+        settings_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = settings_path().ok_or_else(|| anyhow::anyhow!("No home directory found"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join("settings.toml"))
+}
@@ -0,0 +1,127 @@
+//! Sound effects and ambient music. Compiled in with `--features sound` and
+//! only audible when `sound = true` is set in the settings; it is muted by
+//! default. Everything is synthesized, so there are no audio files to ship.
+
+#[cfg(feature = "sound")]
+use rodio::{source::SineWave, OutputStream, OutputStreamHandle, Sink, Source};
+#[cfg(feature = "sound")]
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Effect {
+    Success,
+    Failure,
+    Item,
+}
+
+#[derive(Default)]
+pub struct Sound {
+    #[cfg(feature = "sound")]
+    output: Option<(OutputStream, OutputStreamHandle)>,
+    #[cfg(feature = "sound")]
+    music: Option<Sink>,
+    #[cfg(feature = "sound")]
+    enabled: bool,
+    #[cfg(feature = "sound")]
+    volume: f32,
+    floor: Option<usize>,
+}
+
+impl Sound {
+    /// Opens the default output device if enabled. No device (a server, a
+    /// container) is normal and leaves a silent player.
+    pub fn new(enabled: bool, volume: u8) -> Self {
+        let mut sound = Sound::default();
+        sound.configure(enabled, volume);
+        sound
+    }
+
+    /// Applies changed settings, starting or stopping the music as needed.
+    pub fn configure(&mut self, enabled: bool, volume: u8) {
+        #[cfg(feature = "sound")]
+        {
+            self.enabled = enabled;
+            self.volume = f32::from(volume.min(100)) / 100.0;
+            if !enabled {
+                self.music = None;
+                self.output = None;
+                return;
+            }
+            if self.output.is_none() {
+                match OutputStream::try_default() {
+                    Ok(output) => self.output = Some(output),
+                    Err(e) => tracing::debug!(error = %e, "no audio output"),
+                }
+            }
+            match &self.music {
+                Some(music) => music.set_volume(self.volume),
+                None => {
+                    if let Some(floor) = self.floor.take() {
+                        self.set_floor(floor);
+                    }
+                }
+            }
+        }
+        let _ = (enabled, volume);
+    }
+
+    pub fn play(&self, effect: Effect) {
+        #[cfg(feature = "sound")]
+        if let Some(sink) = self.new_sink() {
+            // (frequency, milliseconds) notes played one after another
+            let notes: &[(f32, u64)] = match effect {
+                Effect::Success => &[(523.25, 90), (659.25, 90), (783.99, 90), (1046.5, 220)],
+                Effect::Failure => &[(220.0, 160), (164.81, 260)],
+                Effect::Item => &[(1318.5, 60), (1760.0, 60), (1318.5, 60), (1760.0, 140)],
+            };
+            for &(frequency, ms) in notes {
+                sink.append(
+                    SineWave::new(frequency)
+                        .take_duration(Duration::from_millis(ms))
+                        .fade_in(Duration::from_millis(8))
+                        .amplify(0.25),
+                );
+            }
+            sink.detach();
+        }
+        let _ = effect;
+    }
+
+    /// Starts the drone for a floor (1-based); unchanged floors keep playing.
+    pub fn set_floor(&mut self, floor: usize) {
+        if self.floor == Some(floor) {
+            return;
+        }
+        self.floor = Some(floor);
+        #[cfg(feature = "sound")]
+        {
+            self.music = self.new_sink().inspect(|sink| {
+                let chord: [f32; 3] = match floor {
+                    1 => [110.0, 164.81, 220.0],  // bare A fifth, open and cold
+                    2 => [146.83, 174.61, 220.0], // D minor, closer
+                    _ => [82.41, 87.31, 123.47],  // E with a flat second, uneasy
+                };
+                let [root, third, fifth] = chord.map(SineWave::new);
+                sink.append(
+                    root.mix(third)
+                        .mix(fifth)
+                        .amplify(0.04)
+                        .fade_in(Duration::from_secs(3)),
+                );
+            });
+        }
+    }
+
+    #[cfg(feature = "sound")]
+    fn new_sink(&self) -> Option<Sink> {
+        if !self.enabled {
+            return None;
+        }
+        let (_, handle) = self.output.as_ref()?;
+        let sink = Sink::try_new(handle)
+            .inspect_err(|e| tracing::debug!(error = %e, "could not open a sound sink"))
+            .ok()?;
+        sink.set_volume(self.volume);
+        Some(sink)
+    }
+}
@@ -0,0 +1,69 @@
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// One standard-library item from the bundled index.
+#[derive(Debug, Deserialize)]
+pub struct DocItem {
+    pub path: String,
+    pub signature: String,
+    pub summary: String,
+}
+
+#[derive(Deserialize)]
+struct Index {
+    item: Vec<DocItem>,
+}
+
+pub enum Lookup {
+    Found(&'static DocItem),
+    /// Several items match; the player has to be more specific.
+    Ambiguous(Vec<&'static DocItem>),
+    NotFound,
+}
+
+/// Parsed on first use; the file is compiled into the binary.
+fn index() -> &'static [DocItem] {
+    static INDEX: OnceLock<Vec<DocItem>> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        toml::from_str::<Index>(include_str!("stddoc.toml"))
+            .map(|index| index.item)
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "bundled std docs are broken");
+                Vec::new()
+            })
+    })
+}
+
+fn normalize(path: &str) -> String {
+    let path = path.trim().trim_end_matches("()").to_lowercase();
+    path.strip_prefix("std::").unwrap_or(&path).to_string()
+}
+
+/// Finds `Vec::push`, `push`, `mem::take` or `std::mem::take` style queries.
+pub fn lookup(query: &str) -> Lookup {
+    let query = normalize(query);
+    if query.is_empty() {
+        return Lookup::NotFound;
+    }
+    let items = index();
+    if let Some(item) = items.iter().find(|item| normalize(&item.path) == query) {
+        return Lookup::Found(item);
+    }
+
+    let suffix = format!("::{}", query);
+    let mut matches: Vec<&DocItem> = items
+        .iter()
+        .filter(|item| normalize(&item.path).ends_with(&suffix))
+        .collect();
+    if matches.is_empty() {
+        matches = items
+            .iter()
+            .filter(|item| normalize(&item.path).contains(&query))
+            .collect();
+    }
+    match matches.len() {
+        0 => Lookup::NotFound,
+        1 => Lookup::Found(matches[0]),
+        _ => Lookup::Ambiguous(matches),
+    }
+}
@@ -0,0 +1,134 @@
+//! State files the game writes for itself (the profile, the ghost). Each one
+//! starts with a header line giving its schema version and a checksum of the
+//! rest, and the last few writes are kept as `<file>.1`, `<file>.2` and so on.
+//! A file that fails its checksum is skipped in favour of the newest backup
+//! that passes, and older schemas are migrated on load.
+
+use anyhow::{bail, Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "# rust-raid";
+
+/// Backups are looked for up to this many, whatever is kept now.
+const MAX_BACKUPS: usize = 20;
+
+/// A state file's contents, and how to bring an older schema up to date.
+pub trait Versioned: Serialize + DeserializeOwned + Default {
+    /// The schema written now. Files from before versioning count as 0.
+    const VERSION: u32;
+
+    /// Rewrites `table`, read from a file at schema `from`, as the current
+    /// schema. The default suits schemas that only ever added fields.
+    fn migrate(from: u32, table: &mut toml::Table) -> Result<()> {
+        let _ = (from, table);
+        Ok(())
+    }
+}
+
+/// Loads `path`, or its newest good backup, or the default if there's neither.
+pub fn load<T: Versioned>(path: &Path) -> T {
+    let candidates = std::iter::once(path.to_path_buf())
+        .chain((1..=MAX_BACKUPS).map(|n| backup_path(path, n)))
+        .filter(|candidate| candidate.exists());
+    for candidate in candidates {
+        match read(&candidate) {
+            Ok(value) => {
+                if candidate != path {
+                    tracing::warn!(path = %candidate.display(), "loaded from backup");
+                }
+                return value;
+            }
+            Err(e) => {
+                tracing::warn!(path = %candidate.display(), error = %e, "skipping state file")
+            }
+        }
+    }
+    T::default()
+}
+
+/// Writes `value` to `path`, first moving the previous file to `<file>.1`
+/// and so on, keeping `backups` of them.
+pub fn save<T: Versioned>(path: &Path, value: &T, backups: usize) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let body = toml::to_string_pretty(value)?;
+    let content = format!(
+        "{} version={} checksum={:016x}\n{}",
+        HEADER,
+        T::VERSION,
+        checksum(&body),
+        body
+    );
+    // Saving what's already there would only push a real backup out
+    if std::fs::read_to_string(path).is_ok_and(|old| old == content) {
+        return Ok(());
+    }
+
+    if backups > 0 && path.exists() {
+        for n in (1..backups).rev() {
+            let from = backup_path(path, n);
+            if from.exists() {
+                std::fs::rename(&from, backup_path(path, n + 1))?;
+            }
+        }
+        std::fs::rename(path, backup_path(path, 1))?;
+    }
+    // Written beside the file and renamed over it, so a crash mid-write
+    // never leaves half a file behind
+    let temp = path.with_extension("tmp");
+    std::fs::write(&temp, content)?;
+    std::fs::rename(&temp, path)?;
+    Ok(())
+}
+
+fn read<T: Versioned>(path: &Path) -> Result<T> {
+    let content = std::fs::read_to_string(path)?;
+    let (version, body) = match content.split_once('\n') {
+        Some((first, body)) if first.starts_with(HEADER) => {
+            let field = |key: &str| {
+                first
+                    .split_whitespace()
+                    .find_map(|word| word.strip_prefix(key)?.strip_prefix('='))
+            };
+            let version = field("version")
+                .context("header has no version")?
+                .parse::<u32>()
+                .context("header version is not a number")?;
+            // Leaving the checksum out is how a file is edited by hand
+            if let Some(expected) = field("checksum") {
+                let actual = format!("{:016x}", checksum(body));
+                if actual != expected {
+                    bail!("checksum mismatch");
+                }
+            }
+            (version, body)
+        }
+        _ => (0, content.as_str()),
+    };
+
+    let mut table: toml::Table = toml::from_str(body)?;
+    if version > T::VERSION {
+        // Unknown fields are ignored, so a later release's file mostly loads
+        tracing::warn!(path = %path.display(), version, "state file is from a newer release");
+    } else if version < T::VERSION {
+        T::migrate(version, &mut table)
+            .with_context(|| format!("could not migrate from version {}", version))?;
+    }
+    Ok(toml::Value::Table(table).try_into()?)
+}
+
+fn backup_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// FNV-1a: enough to catch truncation and stray bytes, not meant to stop
+/// anyone determined to edit the file.
+fn checksum(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
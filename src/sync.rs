@@ -0,0 +1,129 @@
+use anyhow::{bail, Context, Result};
+use std::sync::mpsc::{self, Receiver};
+
+use crate::{http, paths};
+
+const GISTS_URL: &str = "https://api.github.com/gists";
+
+/// Machine-local files in the data directory that should never leave it.
+const LOCAL_ONLY: [&str; 1] = ["update_check.toml"];
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Direction {
+    /// Upload local progress to the gist (creating it on first use)
+    Push,
+    /// Replace local progress with the gist's copy
+    Pull,
+}
+
+pub struct Outcome {
+    pub direction: Direction,
+    pub gist_id: String,
+    pub files: usize,
+}
+
+/// The GitHub token used for syncing; it needs the `gist` scope.
+fn token() -> Result<String> {
+    ["RUST_RAID_GITHUB_TOKEN", "GITHUB_TOKEN"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|t| !t.is_empty()))
+        .context("Set RUST_RAID_GITHUB_TOKEN to a GitHub token with the gist scope")
+}
+
+fn headers(token: &str) -> Vec<String> {
+    vec![
+        format!("Authorization: Bearer {}", token),
+        "Accept: application/vnd.github+json".to_string(),
+        "Content-Type: application/json".to_string(),
+    ]
+}
+
+/// Pushes or pulls every progress file (`*.toml` in the data directory) to or
+/// from a private gist. Pushing without a gist id creates a new gist.
+pub fn run(direction: Direction, gist_id: Option<String>) -> Result<Outcome> {
+    let token = token()?;
+    let dir = paths::data_dir().context("No home directory found")?;
+    match direction {
+        Direction::Push => {
+            let mut files = serde_json::Map::new();
+            if let Ok(entries) = std::fs::read_dir(&dir) {
+                for entry in entries.filter_map(|e| e.ok()) {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if name.ends_with(".toml") && !LOCAL_ONLY.contains(&name.as_str()) {
+                        let content = std::fs::read_to_string(entry.path())?;
+                        files.insert(name, serde_json::json!({ "content": content }));
+                    }
+                }
+            }
+            if files.is_empty() {
+                bail!("Nothing to sync yet");
+            }
+            let count = files.len();
+            let body = serde_json::json!({
+                "description": "Rust Raid progress",
+                "public": false,
+                "files": files,
+            })
+            .to_string();
+            let response = match &gist_id {
+                Some(id) => http::send(
+                    "PATCH",
+                    &format!("{}/{}", GISTS_URL, id),
+                    &headers(&token),
+                    Some(&body),
+                    15,
+                )?,
+                None => http::send("POST", GISTS_URL, &headers(&token), Some(&body), 15)?,
+            };
+            let gist: serde_json::Value = serde_json::from_str(&response)?;
+            let gist_id = gist["id"]
+                .as_str()
+                .context("GitHub did not return a gist id")?
+                .to_string();
+            Ok(Outcome {
+                direction,
+                gist_id,
+                files: count,
+            })
+        }
+        Direction::Pull => {
+            let gist_id = gist_id.context("Nothing has been pushed from this machine yet")?;
+            let response = http::send(
+                "GET",
+                &format!("{}/{}", GISTS_URL, gist_id),
+                &headers(&token),
+                None,
+                15,
+            )?;
+            let gist: serde_json::Value = serde_json::from_str(&response)?;
+            let files = gist["files"].as_object().context("The gist has no files")?;
+            std::fs::create_dir_all(&dir)?;
+            let mut count = 0;
+            for (name, file) in files {
+                // Only plain file names, so a tampered gist can't write elsewhere
+                let safe = name.ends_with(".toml")
+                    && !name.contains(['/', '\\'])
+                    && !name.starts_with('.')
+                    && !LOCAL_ONLY.contains(&name.as_str());
+                if let (true, Some(content)) = (safe, file["content"].as_str()) {
+                    std::fs::write(dir.join(name), content)?;
+                    count += 1;
+                }
+            }
+            Ok(Outcome {
+                direction,
+                gist_id,
+                files: count,
+            })
+        }
+    }
+}
+
+/// Runs a sync on a background thread so the UI keeps drawing while it waits.
+pub fn spawn(direction: Direction, gist_id: Option<String>) -> Receiver<Result<Outcome, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(run(direction, gist_id).map_err(|e| e.to_string()));
+    });
+    rx
+}
@@ -0,0 +1,129 @@
+//! Opt-in, anonymous per-room outcomes for puzzle authors.
+//!
+//! Nothing is recorded unless `telemetry = true` is set, nothing leaves the
+//! machine unless the player runs `rust-raid telemetry submit`, and records
+//! hold no code, paths, names or machine identifiers. The only free text is
+//! a comment the player wrote for the author with `:feedback`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::{http, paths};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoomOutcome {
+    pub game_version: String,
+    pub floor: usize,
+    pub room_id: String,
+    /// "cleared", "fell", or "rated" for a rating from `:feedback`
+    pub outcome: String,
+    pub attempts: u32,
+    pub hints: usize,
+    /// Distinct rustc error codes hit in the room, e.g. "E0382"
+    pub error_codes: Vec<String>,
+    pub solve_secs: u64,
+    /// "too-easy", "just-right" or "too-hard", on a "rated" record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<String>,
+    /// What the player wrote with the rating, if anything
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, clap::Subcommand)]
+pub enum Action {
+    /// Print the recorded outcomes, or write them to a file
+    Export { file: Option<PathBuf> },
+    /// Send the recorded outcomes as a JSON array to a URL the campaign author provides
+    Submit { url: String },
+    /// Delete everything recorded so far
+    Clear,
+}
+
+fn log_path() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("telemetry.jsonl"))
+}
+
+/// Appends one record. Failures are logged and otherwise ignored; telemetry must
+/// never get in the way of playing.
+pub fn record(outcome: &RoomOutcome) {
+    let write = || -> Result<()> {
+        let path = log_path().context("No home directory found")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", serde_json::to_string(outcome)?)?;
+        Ok(())
+    };
+    if let Err(e) = write() {
+        tracing::warn!(error = %e, "could not record telemetry");
+    }
+}
+
+/// Pulls `E0382`-style codes out of rustc's output.
+pub fn error_codes(compiler_output: &str) -> Vec<String> {
+    let mut codes: Vec<String> = compiler_output
+        .match_indices("error[E")
+        .filter_map(|(i, _)| {
+            let code = compiler_output.get(i + 6..i + 11)?;
+            code[1..]
+                .chars()
+                .all(|c| c.is_ascii_digit())
+                .then(|| code.to_string())
+        })
+        .collect();
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+fn load() -> Result<Vec<RoomOutcome>> {
+    let Some(path) = log_path().filter(|p| p.exists()) else {
+        return Ok(Vec::new());
+    };
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Runs `rust-raid telemetry <action>`. Returns whether it succeeded.
+pub fn run(action: Action) -> Result<bool> {
+    match action {
+        Action::Export { file } => {
+            let json = serde_json::to_string_pretty(&load()?)?;
+            match file {
+                Some(file) => {
+                    std::fs::write(&file, json)?;
+                    println!("Wrote {}", file.display());
+                }
+                None => println!("{}", json),
+            }
+        }
+        Action::Submit { url } => {
+            let outcomes = load()?;
+            if outcomes.is_empty() {
+                println!("Nothing recorded yet. Enable `telemetry` in the settings first.");
+                return Ok(false);
+            }
+            let body = serde_json::to_string(&outcomes)?;
+            let headers = ["Content-Type: application/json".to_string()];
+            http::send("POST", &url, &headers, Some(&body), 15)?;
+            println!("Sent {} room outcome(s) to {}", outcomes.len(), url);
+        }
+        Action::Clear => {
+            if let Some(path) = log_path().filter(|p| p.exists()) {
+                std::fs::remove_file(path)?;
+            }
+            println!("Telemetry cleared.");
+        }
+    }
+    Ok(true)
+}
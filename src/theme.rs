@@ -0,0 +1,127 @@
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use serde::{Deserialize, Serialize};
+
+/// Color schemes, applied as a remap over the finished frame so every screen
+/// (including Markdown and highlighted code) follows the same palette.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// The original torch-lit palette, tuned for dark terminals.
+    #[default]
+    Dungeon,
+    /// Darker text and accents for light terminal backgrounds.
+    Light,
+    /// Bright colors only; nothing dim or gray-on-black.
+    HighContrast,
+    /// No color at all; highlights are shown with reverse video.
+    Mono,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [
+        Theme::Dungeon,
+        Theme::Light,
+        Theme::HighContrast,
+        Theme::Mono,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Dungeon => "dungeon",
+            Theme::Light => "light",
+            Theme::HighContrast => "high-contrast",
+            Theme::Mono => "mono",
+        }
+    }
+
+    /// The next theme in [`Theme::ALL`], wrapping around, for the settings screen.
+    pub fn next(self) -> Theme {
+        let index = Theme::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        Theme::ALL[(index + 1) % Theme::ALL.len()]
+    }
+
+    pub fn apply(self, buf: &mut Buffer) {
+        if self == Theme::Dungeon {
+            return;
+        }
+        let area = buf.area;
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = &mut buf[(x, y)];
+                if self == Theme::Mono {
+                    // Anything drawn on a colored background was a highlight
+                    if cell.bg != Color::Reset {
+                        cell.modifier.insert(Modifier::REVERSED);
+                    }
+                    cell.fg = Color::Reset;
+                    cell.bg = Color::Reset;
+                    continue;
+                }
+                cell.fg = self.foreground(cell.fg);
+            }
+        }
+    }
+
+    fn foreground(self, color: Color) -> Color {
+        match (self, color) {
+            (Theme::Light, Color::White | Color::Reset) => Color::Black,
+            (Theme::Light, Color::Gray) => Color::DarkGray,
+            (Theme::Light, Color::Yellow) => Color::Rgb(150, 105, 0),
+            (Theme::Light, Color::LightYellow) => Color::Rgb(130, 80, 0),
+            (Theme::Light, Color::Cyan) => Color::Rgb(0, 110, 130),
+            (Theme::Light, Color::Green) => Color::Rgb(0, 125, 0),
+            (Theme::Light, Color::LightRed) => Color::Red,
+            (Theme::Light, Color::LightBlue) => Color::Blue,
+            (Theme::HighContrast, Color::DarkGray) => Color::Gray,
+            (Theme::HighContrast, Color::Gray) => Color::White,
+            (Theme::HighContrast, Color::Yellow) => Color::LightYellow,
+            (Theme::HighContrast, Color::Cyan) => Color::LightCyan,
+            (Theme::HighContrast, Color::Green) => Color::LightGreen,
+            (Theme::HighContrast, Color::Red) => Color::LightRed,
+            (Theme::HighContrast, Color::Magenta) => Color::LightMagenta,
+            (Theme::HighContrast, Color::Blue) => Color::LightBlue,
+            _ => color,
+        }
+    }
+}
+
+/// A room's surroundings, set with `ambience` under its `[meta]`. It tints the
+/// borders of the room screen and the Chamber's title; the theme then remaps
+/// the tint like any other color.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Ambience {
+    /// Cold stone and old bones
+    Crypt,
+    /// Heat and sparks
+    Forge,
+    /// Lamplight on old paper
+    Library,
+    /// Water dripping in the dark
+    Cavern,
+}
+
+impl Ambience {
+    /// The editor's and the Chamber's borders.
+    pub fn border(self) -> Color {
+        match self {
+            Ambience::Crypt => Color::DarkGray,
+            Ambience::Forge => Color::Red,
+            Ambience::Library => Color::Yellow,
+            Ambience::Cavern => Color::Blue,
+        }
+    }
+
+    /// The Chamber's title.
+    pub fn accent(self) -> Color {
+        match self {
+            Ambience::Crypt => Color::Magenta,
+            Ambience::Forge => Color::LightRed,
+            Ambience::Library => Color::LightYellow,
+            Ambience::Cavern => Color::Cyan,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+/// Fixed cadence of the main loop. Input is still handled as soon as it arrives;
+/// the tick only bounds how long the loop sleeps when nothing happens.
+pub const TICK: Duration = crate::anim::FRAME;
+
+/// A repeating timer polled from the tick handler.
+pub struct Timer {
+    interval: Duration,
+    next: Instant,
+}
+
+impl Timer {
+    pub fn every(interval: Duration) -> Self {
+        Timer {
+            interval,
+            next: Instant::now() + interval,
+        }
+    }
+
+    /// Returns true once per elapsed interval. Missed intervals are not replayed,
+    /// so a stalled loop doesn't fire a burst afterwards.
+    pub fn fire(&mut self, now: Instant) -> bool {
+        if now < self.next {
+            return false;
+        }
+        self.next = now + self.interval;
+        true
+    }
+}
+
+/// Time spent on something, less the stretches it was held for, such as a
+/// room's clock while its player reads the codex.
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    started: Instant,
+    held_since: Option<Instant>,
+}
+
+impl Stopwatch {
+    pub fn start() -> Self {
+        Stopwatch::resume_from(Duration::ZERO)
+    }
+
+    /// A stopwatch that has already run for `elapsed`, for a run picked up
+    /// from a save.
+    pub fn resume_from(elapsed: Duration) -> Self {
+        let now = Instant::now();
+        Stopwatch {
+            started: now.checked_sub(elapsed).unwrap_or(now),
+            held_since: None,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.held_since
+            .unwrap_or_else(Instant::now)
+            .saturating_duration_since(self.started)
+    }
+
+    /// Stops the count while `held`, and picks it up again where it stopped
+    /// once it isn't.
+    pub fn hold(&mut self, held: bool, now: Instant) {
+        match (self.held_since, held) {
+            (None, true) => self.held_since = Some(now),
+            (Some(since), false) => {
+                self.started += now.saturating_duration_since(since);
+                self.held_since = None;
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Short notes under the status bar ("-5 HP", "+40 gold", "ITEM: Sacred
+//! Scroll") for changes that are easy to miss. They come from comparing HP,
+//! gold and the inventory with the last tick, so nothing that changes them
+//! has to remember to announce it.
+
+use std::time::{Duration, Instant};
+
+use crate::i18n::t;
+use crate::inventory::Inventory;
+
+/// How long a toast stays up.
+pub const SHOWN_FOR: Duration = Duration::from_millis(2500);
+
+/// The last part of that, when the toast is drawn dimmed.
+const FADE: Duration = Duration::from_millis(700);
+
+/// More than this and the oldest go early.
+const MAX_SHOWN: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    HpLost,
+    HpGained,
+    Gold,
+    Item,
+    Achievement,
+}
+
+pub struct Toast {
+    pub text: String,
+    pub kind: ToastKind,
+    shown: Instant,
+}
+
+impl Toast {
+    /// Whether it's in its last moments and should be drawn dimmed.
+    pub fn fading(&self, now: Instant) -> bool {
+        now.duration_since(self.shown) + FADE >= SHOWN_FOR
+    }
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    shown: Vec<Toast>,
+    hp: u32,
+    gold: u32,
+    carried: Vec<String>,
+}
+
+impl Toasts {
+    /// Takes the current values as given, e.g. when a run starts, so the
+    /// jump from the last run's numbers isn't announced.
+    pub fn reset(&mut self, hp: u32, gold: u32, inventory: &Inventory) {
+        self.shown.clear();
+        self.hp = hp;
+        self.gold = gold;
+        self.carried = names(inventory);
+    }
+
+    /// Adds toasts for whatever changed since the last call and drops the
+    /// expired ones. Returns whether the toasts need drawing again.
+    pub fn update(&mut self, hp: u32, gold: u32, inventory: &Inventory, now: Instant) -> bool {
+        if hp < self.hp {
+            self.push(
+                t!("toast-hp-lost", amount = self.hp - hp),
+                ToastKind::HpLost,
+                now,
+            );
+        } else if hp > self.hp {
+            self.push(
+                t!("toast-hp-gained", amount = hp - self.hp),
+                ToastKind::HpGained,
+                now,
+            );
+        }
+        if gold < self.gold {
+            self.push(
+                t!("toast-gold-spent", amount = self.gold - gold),
+                ToastKind::Gold,
+                now,
+            );
+        } else if gold > self.gold {
+            self.push(
+                t!("toast-gold-gained", amount = gold - self.gold),
+                ToastKind::Gold,
+                now,
+            );
+        }
+        let carried = names(inventory);
+        let new: Vec<&String> = carried
+            .iter()
+            .filter(|name| !self.carried.contains(name))
+            .collect();
+        for name in new {
+            self.push(t!("toast-item", item = name.as_str()), ToastKind::Item, now);
+        }
+        (self.hp, self.gold, self.carried) = (hp, gold, carried);
+
+        let before = self.shown.len();
+        self.shown
+            .retain(|toast| now.duration_since(toast.shown) < SHOWN_FOR);
+        // Fading ones redraw every tick, and so does the one that just went
+        !self.shown.is_empty() || self.shown.len() != before
+    }
+
+    /// Announces something that isn't a change in HP, gold or items.
+    pub fn announce(&mut self, text: String, kind: ToastKind) {
+        self.push(text, kind, Instant::now());
+    }
+
+    /// Oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.shown.iter()
+    }
+
+    fn push(&mut self, text: String, kind: ToastKind, now: Instant) {
+        if self.shown.len() == MAX_SHOWN {
+            self.shown.remove(0);
+        }
+        self.shown.push(Toast {
+            text,
+            kind,
+            shown: now,
+        });
+    }
+}
+
+fn names(inventory: &Inventory) -> Vec<String> {
+    inventory.iter().map(|item| item.name.clone()).collect()
+}
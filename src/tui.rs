@@ -0,0 +1,404 @@
+//! The terminal frontend: sets up the screen, runs the event loop and prints
+//! the summary once the run is over.
+
+use anyhow::{Context, Result};
+use crossterm::{
+    event::{self, DisableFocusChange, EnableFocusChange, Event, PopKeyboardEnhancementFlags},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    style::{Color, Style},
+    Terminal,
+};
+use std::io;
+use std::path::PathBuf;
+
+use crate::app::{App, GameState, MenuOption};
+use crate::archive::Archive;
+use crate::compiler::{self, Backend};
+use crate::engine::{Flow, PuzzleDir, PuzzleSource};
+use crate::i18n::t;
+use crate::profile::Profile;
+use crate::settings::Settings;
+use crate::theme::Theme;
+use crate::ui::{draw_ui, format_duration};
+use crate::{
+    campaigns, card, coop, feed, ghost, glyphs, i18n, input, notify, presence, replay, save, timer,
+    update,
+};
+
+/// How to start a session; the binary fills this in from the command line.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub puzzles_dir: PathBuf,
+    /// Floor and 0-based room to start in instead of the title screen
+    pub start: Option<(usize, usize)>,
+    pub seed: Option<u64>,
+    pub theme: Option<Theme>,
+    pub accessible: bool,
+    pub compiler: Backend,
+    pub live_feed: Option<PathBuf>,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub replay_speed: f64,
+    pub host: Option<String>,
+    pub join: Option<String>,
+}
+
+/// The requested start room doesn't exist. [`run`] returns it before touching the terminal.
+#[derive(Debug)]
+pub struct BadStart(pub String);
+
+impl std::fmt::Display for BadStart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for BadStart {}
+
+/// Plays one session in the current terminal.
+pub fn run(options: Options) -> Result<()> {
+    let puzzles = PuzzleDir::new(&options.puzzles_dir);
+    let rooms = puzzles.load_floor(1).with_context(|| {
+        t!(
+            "error-puzzles-load",
+            dir = options.puzzles_dir.display().to_string()
+        )
+    })?;
+
+    if rooms.is_empty() {
+        eprintln!(
+            "{}",
+            t!(
+                "error-puzzles-empty",
+                dir = options.puzzles_dir.display().to_string()
+            )
+        );
+        return Ok(());
+    }
+
+    let settings = Settings::load();
+    i18n::init(settings.locale.as_deref());
+    compiler::set_timeout(std::time::Duration::from_secs(settings.run_timeout_seconds));
+    let mut app = App::new(rooms, settings, Profile::load())
+        .with_puzzles(puzzles)
+        .with_validator(options.compiler);
+    app.seed = options.seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    app.accessible = options.accessible;
+    // The dashboard only has something to compare with another pack installed
+    app.menu
+        .set_enabled(MenuOption::Campaigns, !campaigns::installed().is_empty());
+    // Worth offering once a run has reached past the first floor
+    let reached = campaigns::floors(app.puzzles.as_ref(), &app.profile)
+        .iter()
+        .skip(1)
+        .any(|floor| floor.unlocked);
+    app.menu.set_enabled(MenuOption::LevelSelect, reached);
+    app.ghost = ghost::Ghost::load();
+    if let Some(theme) = options.theme {
+        app.theme = theme;
+    } else if options.accessible {
+        app.theme = Theme::HighContrast;
+    }
+    let mut player = match &options.replay {
+        Some(path) => Some(replay::Player::load(path, options.replay_speed)?),
+        None => None,
+    };
+    let mut start = options.start;
+    if let Some(replay::Entry::Header {
+        seed,
+        start: recorded_start,
+        seen_onboarding,
+        ..
+    }) = player.as_ref().map(|p| &p.header)
+    {
+        // Start exactly like the recorded session, and leave no trace of the playback
+        app.seed = *seed;
+        start = *recorded_start;
+        app.profile.seen_onboarding = *seen_onboarding;
+        app.settings.telemetry = false;
+        app.settings.check_for_updates = false;
+        app.ghost = None;
+    }
+    if player.is_none() {
+        app.archive = Some(Archive::load());
+        app.saved_run = save::SaveGame::load();
+        app.menu
+            .set_enabled(MenuOption::Continue, app.saved_run.is_some());
+    }
+    if let Some((level, room)) = start {
+        app.start_at(level, room).map_err(BadStart)?;
+    }
+
+    if options.record.is_some() {
+        // A reminder comes from the clock, which a recording can't replay
+        app.settings.break_reminder_minutes = 0;
+    }
+    let mut recorder = match &options.record {
+        Some(path) => Some(replay::Recorder::create(
+            path,
+            &replay::Entry::Header {
+                game_version: env!("CARGO_PKG_VERSION").to_string(),
+                seed: app.seed,
+                start,
+                seen_onboarding: app.profile.seen_onboarding,
+            },
+        )?),
+        None => None,
+    };
+
+    // Connect before taking over the terminal so waiting and errors are visible
+    let coop_session = match (&options.host, &options.join) {
+        (Some(addr), _) => {
+            println!("{}", t!("coop-waiting", address = addr.as_str()));
+            Some(coop::Session::host(addr)?)
+        }
+        (None, Some(addr)) => Some(coop::Session::join(addr)?),
+        (None, None) => None,
+    };
+    if coop_session.is_some() && app.state == GameState::TitleScreen {
+        app.start_game();
+    }
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableFocusChange)?;
+    app.key_protocol = input::enable_enhanced_keys(&mut stdout);
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    app.unicode_supported = glyphs::probe_unicode();
+    app.coop = coop_session;
+    if let Some(session) = &app.coop {
+        app.message = t!("coop-joined", peer = session.peer.as_str());
+        app.message_style = Style::default().fg(Color::Cyan);
+    }
+    if app.settings.check_for_updates {
+        (app.update_notice, app.update_rx) = update::check();
+    }
+
+    let mut last_tick = std::time::Instant::now();
+    let mut logged_state = app.state;
+    let mut presence = presence::Presence::connect(app.settings.discord_presence);
+    let mut live_feed = options.live_feed.map(feed::LiveFeed::new);
+    let mut replay_in_sync = true;
+    loop {
+        if last_tick.elapsed() >= timer::TICK {
+            app.tick();
+            last_tick = std::time::Instant::now();
+        }
+        if let Some(verdict) = app.slow_verdict.take() {
+            if !app.focused {
+                notify::send(app.settings.verdict_alert, &verdict);
+            }
+        }
+        if app.state != logged_state {
+            tracing::debug!(from = ?logged_state, to = ?app.state, room = %app.room().meta.id, "state change");
+            logged_state = app.state;
+            presence.update(app.presence_status());
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.state(app.state);
+            }
+        }
+        if let Some(feed) = live_feed.as_mut() {
+            feed.publish(app.feed_snapshot());
+        }
+        if std::mem::take(&mut app.needs_redraw) {
+            terminal.draw(|f| draw_ui(f, &app))?;
+        }
+
+        // Sleep until input arrives or the next tick is due, whichever comes first
+        let timeout = timer::TICK.saturating_sub(last_tick.elapsed());
+        let event = match player.as_mut() {
+            // During playback the keyboard only steers the player
+            Some(player) => {
+                if event::poll(timeout)? {
+                    if let Event::Key(key) = event::read()? {
+                        if player.control(key) {
+                            break;
+                        }
+                    }
+                }
+                let label = Some(player.label(replay_in_sync));
+                if app.replay_label != label {
+                    app.replay_label = label;
+                    app.needs_redraw = true;
+                }
+                let Some(key) = player.next_due() else {
+                    continue;
+                };
+                replay_in_sync = player.in_sync(app.state);
+                Event::Key(key)
+            }
+            None => {
+                if !event::poll(timeout)? {
+                    continue;
+                }
+                event::read()?
+            }
+        };
+        match event {
+            Event::FocusGained => app.focused = true,
+            Event::FocusLost => app.focused = false,
+            _ => {}
+        }
+        app.needs_redraw = true;
+        if let (Some(recorder), Event::Key(key)) = (recorder.as_mut(), &event) {
+            recorder.key(key);
+        }
+
+        // Ignore mouse and focus events
+        if matches!(
+            event,
+            Event::Mouse(_) | Event::FocusGained | Event::FocusLost
+        ) {
+            continue;
+        }
+
+        if let Event::Key(key) = event {
+            if app.handle_key(key) == Flow::Quit {
+                if app.state == GameState::GameOver {
+                    // Let the defeat sink in before leaving the terminal
+                    terminal.draw(|f| draw_ui(f, &app))?;
+                    std::thread::sleep(std::time::Duration::from_secs(3));
+                }
+                break;
+            }
+        }
+    }
+
+    app.save_profile();
+    app.save_run();
+
+    if app.key_protocol == input::KeyProtocol::Enhanced {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        DisableFocusChange,
+        LeaveAlternateScreen
+    )?;
+
+    let victory = app.victory();
+    match app.state {
+        GameState::LevelComplete => {
+            if victory {
+                println!();
+                println!("    ╔═══════════════════════════════════════════════════╗");
+                println!("    ║                                                   ║");
+                println!("    ║         R U S T   R A I D   C O M P L E T E       ║");
+                println!("    ║                                                   ║");
+                println!("    ║{:^51}║", t!("victory-conquered"));
+                println!("    ║                                                   ║");
+                println!("    ╚═══════════════════════════════════════════════════╝");
+                println!();
+                println!("    {}", t!("victory-bows"));
+                println!();
+                println!("    ┌─────────────────────────────────────┐");
+                println!("    │  {:<35}│", t!("final-stats"));
+                println!("    ├─────────────────────────────────────┤");
+                for (id, value) in [
+                    ("final-gold", app.gold.to_string()),
+                    ("final-hp", app.hp.to_string()),
+                    (
+                        "final-codex",
+                        format!("{}/{}", app.codex.len(), app.standing().codex_total),
+                    ),
+                    ("final-items", app.inventory.len().to_string()),
+                    ("final-medals", {
+                        let [gold, silver, bronze] = app.medal_tally(None);
+                        format!("{} / {} / {}", gold, silver, bronze)
+                    }),
+                ] {
+                    println!("    │  {:<19}{:>15}  │", t!(id), value);
+                }
+                println!("    └─────────────────────────────────────┘");
+                println!();
+                println!("    {}", t!("victory-farewell"));
+                println!();
+            } else {
+                println!(
+                    "\n{}\n",
+                    t!(
+                        "level-completed",
+                        level = app.current_level,
+                        name = app.puzzles.floor_name(app.current_level)
+                    )
+                );
+            }
+        }
+        GameState::GameOver => {
+            println!("\n{}\n", t!("game-over-farewell"));
+        }
+        _ => {}
+    }
+
+    if options.replay.is_some() {
+        return Ok(());
+    }
+    if victory || app.state == GameState::GameOver {
+        share_result_card(&app.result_card(victory));
+    }
+    if victory && app.racing {
+        record_personal_best(
+            app.ghost.as_ref(),
+            app.run_splits,
+            app.settings.save_backups,
+        );
+    }
+
+    Ok(())
+}
+
+/// Prints the run's result card and offers it for sharing via the clipboard and a file.
+fn share_result_card(card: &str) {
+    println!("{}", card);
+    let copied = card::copy_to_clipboard(card);
+    let saved = card::save(card)
+        .inspect_err(|e| tracing::warn!(error = %e, "could not save result card"))
+        .ok();
+    let message = match (copied, saved) {
+        (true, Some(path)) => t!("card-copied-saved", path = path.display().to_string()),
+        (true, None) => t!("card-copied"),
+        (false, Some(path)) => t!("card-saved", path = path.display().to_string()),
+        (false, None) => return,
+    };
+    println!("{}\n", message);
+}
+
+/// Keeps a finished run's splits as the new ghost if it beat the old one.
+fn record_personal_best(
+    previous: Option<&ghost::Ghost>,
+    splits: Vec<ghost::Split>,
+    backups: usize,
+) {
+    if !previous.is_none_or(|ghost| ghost.beaten_by(&splits)) {
+        return;
+    }
+    let best = ghost::Ghost { splits };
+    let total = format_duration(std::time::Duration::from_secs(
+        best.total_secs().unwrap_or_default(),
+    ));
+    match best.save(backups) {
+        Ok(()) => match previous.and_then(ghost::Ghost::total_secs) {
+            Some(old) => println!(
+                "{}\n",
+                t!(
+                    "ghost-new-best",
+                    time = total,
+                    previous = format_duration(std::time::Duration::from_secs(old))
+                )
+            ),
+            None => println!("{}\n", t!("ghost-recorded", time = total)),
+        },
+        Err(e) => tracing::warn!(error = %e, "could not save personal best"),
+    }
+}
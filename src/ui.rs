@@ -0,0 +1,74 @@
+//! Drawing shared by every screen. Rendering never changes game state; each
+//! screen draws itself in [`crate::screens`].
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::app::App;
+use crate::{glyphs, screens};
+
+pub fn draw_ui(f: &mut Frame, app: &App) {
+    screens::for_state(app.state).render(f, app);
+    if let Some(label) = &app.replay_label {
+        let area = f.area();
+        let width = (label.chars().count() as u16).min(area.width);
+        let corner = Rect::new(area.right() - width, area.top(), width, 1);
+        f.render_widget(
+            Paragraph::new(label.as_str())
+                .style(Style::default().fg(Color::Black).bg(Color::LightRed)),
+            corner,
+        );
+    }
+    if app.ascii_only() {
+        glyphs::asciify(f.buffer_mut());
+    }
+    app.theme.apply(f.buffer_mut());
+}
+
+/// A rectangle of at most `width` x `height` centered in `area`.
+pub(crate) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+pub(crate) fn menu_line(label: &str, selected: bool, enabled: bool) -> Line<'static> {
+    if !enabled {
+        Line::from(Span::styled(
+            format!("   {} ", label),
+            Style::default().fg(Color::DarkGray),
+        ))
+    } else if selected {
+        Line::from(Span::styled(
+            format!(" ▸ {} ", label),
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+    } else {
+        Line::from(Span::styled(
+            format!("   {} ", label),
+            Style::default().fg(Color::White),
+        ))
+    }
+}
+
+pub(crate) fn format_duration(d: std::time::Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
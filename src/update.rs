@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{http, paths};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/bradleyd/rust-raid/releases/latest";
+
+/// Only ask the network once a day; launches in between use the cached answer.
+const REFRESH_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cache {
+    checked_at: u64,
+    latest: Option<String>,
+}
+
+/// Returns the newer release already known from the cache, plus a receiver that
+/// yields a fresh answer if the cache was stale and a background check started.
+///
+/// Nothing here blocks: the network request runs on its own thread, and any
+/// failure (offline, no `curl`, rate limited) just means no notice.
+pub fn check() -> (Option<String>, Option<Receiver<String>>) {
+    let cache = load_cache();
+    let known = cache.latest.filter(|v| is_newer(v));
+    if now_secs().saturating_sub(cache.checked_at) < REFRESH_AFTER.as_secs() {
+        return (known, None);
+    }
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let latest = fetch_latest();
+        tracing::debug!(?latest, "update check finished");
+        let cache = Cache {
+            checked_at: now_secs(),
+            latest: latest.clone(),
+        };
+        if let (Some(path), Ok(content)) = (cache_path(), toml::to_string(&cache)) {
+            let _ = std::fs::write(path, content);
+        }
+        if let Some(version) = latest.filter(|v| is_newer(v)) {
+            let _ = tx.send(version);
+        }
+    });
+    (known, Some(rx))
+}
+
+fn fetch_latest() -> Option<String> {
+    let headers = ["Accept: application/vnd.github+json".to_string()];
+    let body = http::send("GET", RELEASES_URL, &headers, None, 5).ok()?;
+    let release: serde_json::Value = serde_json::from_str(&body).ok()?;
+    let tag = release.get("tag_name")?.as_str()?;
+    Some(tag.trim_start_matches('v').to_string())
+}
+
+/// Whether `version` (e.g. "0.2.0") is later than the running build.
+fn is_newer(version: &str) -> bool {
+    match (parse(version), parse(env!("CARGO_PKG_VERSION"))) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+fn parse(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.split(['.', '-']).map(|p| p.parse::<u64>().ok());
+    Some((
+        parts.next()??,
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+fn load_cache() -> Cache {
+    cache_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn cache_path() -> Option<PathBuf> {
+    let dir = paths::data_dir()?;
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir.join("update_check.toml"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}